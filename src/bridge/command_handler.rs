@@ -4,8 +4,22 @@ use std::collections::HashSet;
 pub enum MatrixCommandOutcome {
     Ignored,
     Reply(String),
-    BridgeRequested { feishu_chat_id: String },
+    BridgeRequested {
+        feishu_chat_id: String,
+    },
     UnbridgeRequested,
+    CreateRequested,
+    PmRequested {
+        target: String,
+    },
+    SyncRequested,
+    LinkRequested {
+        matrix_user_id: String,
+        feishu_user_id: String,
+    },
+    UnlinkRequested {
+        matrix_user_id: String,
+    },
 }
 
 pub struct MatrixCommandHandler {
@@ -29,7 +43,7 @@ impl MatrixCommandHandler {
         &self,
         body: &str,
         is_room_bridged: bool,
-        _permission_checker: F,
+        permission_checker: F,
     ) -> MatrixCommandOutcome
     where
         F: Fn(&str) -> bool,
@@ -65,6 +79,27 @@ impl MatrixCommandHandler {
                     ),
                 }
             }
+            Some("create") => {
+                if !self.self_service_enabled {
+                    return MatrixCommandOutcome::Reply(
+                        "Self-service bridging is not enabled on this bridge.".to_string(),
+                    );
+                }
+
+                if is_room_bridged {
+                    return MatrixCommandOutcome::Reply(
+                        "This room is already bridged to a Feishu chat.".to_string(),
+                    );
+                }
+
+                if !permission_checker("full") {
+                    return MatrixCommandOutcome::Reply(
+                        "You don't have permission to create Feishu groups.".to_string(),
+                    );
+                }
+
+                MatrixCommandOutcome::CreateRequested
+            }
             Some("unbridge") => {
                 if !is_room_bridged {
                     return MatrixCommandOutcome::Reply(
@@ -74,6 +109,72 @@ impl MatrixCommandHandler {
 
                 MatrixCommandOutcome::UnbridgeRequested
             }
+            Some("sync") => {
+                if !is_room_bridged {
+                    return MatrixCommandOutcome::Reply(
+                        "This room is not bridged to any Feishu chat.".to_string(),
+                    );
+                }
+
+                if !permission_checker("admin") {
+                    return MatrixCommandOutcome::Reply(
+                        "You don't have permission to sync members.".to_string(),
+                    );
+                }
+
+                MatrixCommandOutcome::SyncRequested
+            }
+            Some("pm") => {
+                if !permission_checker("full") {
+                    return MatrixCommandOutcome::Reply(
+                        "You don't have permission to start direct chats.".to_string(),
+                    );
+                }
+
+                let target = parts.get(2..).unwrap_or_default().join(" ");
+                if target.is_empty() {
+                    return MatrixCommandOutcome::Reply(
+                        "Usage: !feishu pm <user_id|email|mxid|name>".to_string(),
+                    );
+                }
+
+                MatrixCommandOutcome::PmRequested { target }
+            }
+            Some("link") => {
+                if !permission_checker("admin") {
+                    return MatrixCommandOutcome::Reply(
+                        "You don't have permission to link users.".to_string(),
+                    );
+                }
+
+                match (parts.get(2), parts.get(3)) {
+                    (Some(matrix_user_id), Some(feishu_user_id)) => {
+                        MatrixCommandOutcome::LinkRequested {
+                            matrix_user_id: matrix_user_id.to_string(),
+                            feishu_user_id: feishu_user_id.to_string(),
+                        }
+                    }
+                    _ => MatrixCommandOutcome::Reply(
+                        "Usage: !feishu link <matrix_user_id> <feishu_user_id>".to_string(),
+                    ),
+                }
+            }
+            Some("unlink") => {
+                if !permission_checker("admin") {
+                    return MatrixCommandOutcome::Reply(
+                        "You don't have permission to unlink users.".to_string(),
+                    );
+                }
+
+                match parts.get(2) {
+                    Some(matrix_user_id) => MatrixCommandOutcome::UnlinkRequested {
+                        matrix_user_id: matrix_user_id.to_string(),
+                    },
+                    None => MatrixCommandOutcome::Reply(
+                        "Usage: !feishu unlink <matrix_user_id>".to_string(),
+                    ),
+                }
+            }
             Some("help") => MatrixCommandOutcome::Reply(self.help_text()),
             Some("ping") => MatrixCommandOutcome::Reply("Pong!".to_string()),
             _ => MatrixCommandOutcome::Reply(format!(
@@ -90,13 +191,34 @@ impl MatrixCommandHandler {
                 "{} ping - Check if the bridge is responsive",
                 self.command_prefix
             ),
+            format!(
+                "{} pm <user_id|email|mxid|name> - Start a direct chat with a Feishu user (full access)",
+                self.command_prefix
+            ),
         ];
 
+        help.push(format!(
+            "{} sync - Sync Feishu chat members into this room (admin only)",
+            self.command_prefix
+        ));
+        help.push(format!(
+            "{} link <matrix_user_id> <feishu_user_id> - Link a Matrix account to a Feishu user (admin only)",
+            self.command_prefix
+        ));
+        help.push(format!(
+            "{} unlink <matrix_user_id> - Remove a Matrix account's Feishu link (admin only)",
+            self.command_prefix
+        ));
+
         if self.self_service_enabled {
             help.push(format!(
                 "{} bridge <chat_id> - Bridge this room to a Feishu chat",
                 self.command_prefix
             ));
+            help.push(format!(
+                "{} create - Create a new Feishu group for this room (full access)",
+                self.command_prefix
+            ));
             help.push(format!(
                 "{} unbridge - Remove the bridge from this room",
                 self.command_prefix
@@ -234,6 +356,84 @@ mod tests {
         assert_eq!(result, MatrixCommandOutcome::UnbridgeRequested);
     }
 
+    #[test]
+    fn matrix_command_handler_handles_create() {
+        let handler = MatrixCommandHandler::new(true);
+        let result = handler.handle("!feishu create", false, |level| level == "full");
+        assert_eq!(result, MatrixCommandOutcome::CreateRequested);
+        assert!(matches!(
+            handler.handle("!feishu create", true, |_| true),
+            MatrixCommandOutcome::Reply(_)
+        ));
+        assert!(matches!(
+            handler.handle("!feishu create", false, |_| false),
+            MatrixCommandOutcome::Reply(_)
+        ));
+    }
+
+    #[test]
+    fn matrix_command_handler_handles_sync() {
+        let handler = MatrixCommandHandler::new(false);
+        let result = handler.handle("!feishu sync", true, |level| level == "admin");
+        assert_eq!(result, MatrixCommandOutcome::SyncRequested);
+        assert!(matches!(
+            handler.handle("!feishu sync", true, |_| false),
+            MatrixCommandOutcome::Reply(_)
+        ));
+        assert!(matches!(
+            handler.handle("!feishu sync", false, |_| true),
+            MatrixCommandOutcome::Reply(_)
+        ));
+    }
+
+    #[test]
+    fn matrix_command_handler_handles_pm() {
+        let handler = MatrixCommandHandler::new(true);
+        let result = handler.handle("!feishu pm Zhang San", false, |level| level == "full");
+        assert_eq!(
+            result,
+            MatrixCommandOutcome::PmRequested {
+                target: "Zhang San".to_string()
+            }
+        );
+        assert!(matches!(
+            handler.handle("!feishu pm", false, |_| true),
+            MatrixCommandOutcome::Reply(_)
+        ));
+        assert!(matches!(
+            handler.handle("!feishu pm Zhang San", false, |_| false),
+            MatrixCommandOutcome::Reply(_)
+        ));
+    }
+
+    #[test]
+    fn matrix_command_handler_handles_link_and_unlink() {
+        let handler = MatrixCommandHandler::new(true);
+        assert_eq!(
+            handler.handle("!feishu link @alice:localhost ou_alice", false, |level| {
+                level == "admin"
+            }),
+            MatrixCommandOutcome::LinkRequested {
+                matrix_user_id: "@alice:localhost".to_string(),
+                feishu_user_id: "ou_alice".to_string(),
+            }
+        );
+        assert_eq!(
+            handler.handle("!feishu unlink @alice:localhost", false, |_| true),
+            MatrixCommandOutcome::UnlinkRequested {
+                matrix_user_id: "@alice:localhost".to_string(),
+            }
+        );
+        assert!(matches!(
+            handler.handle("!feishu link @alice:localhost ou_alice", false, |_| false),
+            MatrixCommandOutcome::Reply(_)
+        ));
+        assert!(matches!(
+            handler.handle("!feishu link @alice:localhost", false, |_| true),
+            MatrixCommandOutcome::Reply(_)
+        ));
+    }
+
     #[test]
     fn feishu_command_handler_handles_approve() {
         let handler = FeishuCommandHandler::new();
@@ -6,18 +6,22 @@ use serde_json::Value;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use crate::bridge::FeishuBridge;
 use crate::bridge::command_handler::{MatrixCommandHandler, MatrixCommandOutcome};
 use crate::bridge::matrix_event_parser::{
     outbound_content_hash, outbound_delivery_uuid, parse_matrix_inbound,
 };
-use crate::bridge::matrix_to_feishu_dispatcher::MatrixToFeishuDispatcher;
-use crate::bridge::message_flow::{MessageFlow, OutboundFeishuMessage};
+use crate::bridge::matrix_to_feishu_dispatcher::{
+    MatrixToFeishuDispatcher, feishu_receive_id_type,
+};
+use crate::bridge::message_flow::{MatrixInboundMessage, MessageFlow, OutboundFeishuMessage};
 use crate::config::Config;
 use crate::database::{
     EventStore, MediaStore, MessageMapping, MessageStore, ProcessedEvent, RoomMapping, RoomStore,
     UserStore,
 };
 use crate::feishu::{FeishuMessageSendData, FeishuService};
+use crate::formatter::{escape_html, insert_feishu_at_tag};
 use crate::util::build_trace_id;
 use crate::web::{ScopedTimer, global_metrics};
 
@@ -29,6 +33,7 @@ pub struct MatrixEvent {
     pub sender: String,
     pub state_key: Option<String>,
     pub content: Option<Value>,
+    pub prev_content: Option<Value>,
     pub timestamp: Option<String>,
 }
 
@@ -44,6 +49,7 @@ pub struct MatrixEventProcessor {
     dispatcher: MatrixToFeishuDispatcher,
     rate_limiter: RoomRateLimiter,
     blocked_msgtypes: HashSet<String>,
+    bridge: Option<FeishuBridge>,
 }
 
 impl MatrixEventProcessor {
@@ -86,9 +92,15 @@ impl MatrixEventProcessor {
             dispatcher,
             rate_limiter,
             blocked_msgtypes,
+            bridge: None,
         }
     }
 
+    pub fn with_bridge(mut self, bridge: FeishuBridge) -> Self {
+        self.bridge = Some(bridge);
+        self
+    }
+
     pub async fn process_event(&self, event: MatrixEvent) -> anyhow::Result<()> {
         let _timer = ScopedTimer::new("matrix_event_process");
         let matrix_event_id = event.event_id.as_deref().unwrap_or("unknown");
@@ -171,6 +183,20 @@ impl MatrixEventProcessor {
                 }
                 self.handle_reaction_event(&event).await?;
             }
+            "m.room.power_levels" => {
+                println!("[Matrix Event] 🛡️  Type: m.room.power_levels");
+                println!("[Matrix Event]   Event ID: {:?}", event.event_id);
+                println!("[Matrix Event]   Room ID: {:?}", event.room_id);
+                println!("[Matrix Event]   Sender: {:?}", event.sender);
+                self.handle_power_levels_event(&event).await?;
+            }
+            "m.room.name" | "m.room.topic" => {
+                println!("[Matrix Event] 🏷️  Type: {}", event.event_type);
+                println!("[Matrix Event]   Event ID: {:?}", event.event_id);
+                println!("[Matrix Event]   Room ID: {:?}", event.room_id);
+                println!("[Matrix Event]   Sender: {:?}", event.sender);
+                self.handle_room_metadata_event(&event).await?;
+            }
             _ => {
                 println!("[Matrix Event] ❓ Type: {} (ignored)", event.event_type);
                 println!("[Matrix Event]   Event ID: {:?}", event.event_id);
@@ -276,6 +302,7 @@ impl MatrixEventProcessor {
         };
 
         let mut outbound = self.message_flow.matrix_to_feishu(&inbound);
+        self.resolve_feishu_mentions(&inbound, &mut outbound).await;
         self.prepend_matrix_sender_marker(event, &mut outbound).await;
         if self.config.bridge.max_text_length > 0 {
             let (truncated, changed) =
@@ -291,6 +318,10 @@ impl MatrixEventProcessor {
                     "Truncated outbound Matrix message due to max_text_length policy"
                 );
                 outbound.content = truncated;
+                outbound.formatted_body = None;
+                if !outbound.mentions.is_empty() && outbound.msg_type == "post" {
+                    outbound.msg_type = "text".to_string();
+                }
             }
         }
         let content_hash = outbound_content_hash(event, &outbound);
@@ -339,6 +370,7 @@ impl MatrixEventProcessor {
                     attachment_message_ids.first().cloned().map(|message_id| {
                         FeishuMessageSendData {
                             message_id,
+                            chat_id: None,
                             root_id: None,
                             parent_id: None,
                             thread_id: None,
@@ -421,6 +453,50 @@ impl MatrixEventProcessor {
         Ok(())
     }
 
+    async fn resolve_feishu_mentions(
+        &self,
+        inbound: &MatrixInboundMessage,
+        outbound: &mut OutboundFeishuMessage,
+    ) {
+        for mention in &inbound.mentions {
+            let mapping = match self
+                .user_store
+                .get_user_by_matrix_id(&mention.user_id)
+                .await
+            {
+                Ok(Some(mapping)) if !mapping.feishu_user_id.trim().is_empty() => mapping,
+                Ok(_) => continue,
+                Err(err) => {
+                    warn!(
+                        matrix_user_id = %mention.user_id,
+                        error = %err,
+                        "Failed to resolve Matrix mention to a Feishu user"
+                    );
+                    continue;
+                }
+            };
+
+            let label = mention
+                .display_name
+                .clone()
+                .or(mapping.feishu_username.clone())
+                .unwrap_or_else(|| extract_matrix_sender_name(&mention.user_id));
+            outbound.content =
+                insert_feishu_at_tag(&outbound.content, &label, &mapping.feishu_user_id);
+            outbound
+                .mentions
+                .insert(mention.user_id.clone(), mapping.feishu_user_id);
+        }
+
+        // Plain post content can't carry <at> tags; formatted posts get `at` elements instead.
+        if !outbound.mentions.is_empty()
+            && outbound.msg_type == "post"
+            && outbound.formatted_body.is_none()
+        {
+            outbound.msg_type = "text".to_string();
+        }
+    }
+
     async fn prepend_matrix_sender_marker(
         &self,
         event: &MatrixEvent,
@@ -432,6 +508,9 @@ impl MatrixEventProcessor {
 
         let (marker, has_feishu_at) = self.build_matrix_sender_marker(&event.sender).await;
         outbound.content = format!("{}{}", marker, outbound.content);
+        if let Some(formatted_body) = outbound.formatted_body.as_mut() {
+            *formatted_body = format!("{}{}", escape_html(&marker), formatted_body);
+        }
 
         // Feishu text supports <at user_id="...">...</at> directly.
         // Keep message type as text when marker contains explicit Feishu at-tag.
@@ -494,7 +573,13 @@ impl MatrixEventProcessor {
         let message = render_failure_notice(template, event, err);
         if let Err(notice_err) = self
             .feishu_service
-            .send_text_message(&mapping.feishu_chat_id, &message)
+            .send_message(
+                feishu_receive_id_type(mapping),
+                &mapping.feishu_chat_id,
+                "text",
+                serde_json::json!({ "text": message }),
+                Some(Uuid::new_v4().to_string()),
+            )
             .await
         {
             let error_chain = format!("{:#}", notice_err);
@@ -525,7 +610,13 @@ impl MatrixEventProcessor {
         let is_bridged = room_mapping.is_some();
         println!("[Matrix Command]   Is Bridged: {}", is_bridged);
 
-        let outcome = self.command_handler.handle(body, is_bridged, |_| true);
+        let outcome = self
+            .command_handler
+            .handle(body, is_bridged, |level| match level {
+                "full" => self.config.bridge.has_full_access(&event.sender),
+                "admin" => self.config.bridge.is_admin(&event.sender),
+                _ => true,
+            });
 
         match outcome {
             MatrixCommandOutcome::Ignored => {
@@ -557,6 +648,103 @@ impl MatrixEventProcessor {
                     );
                 }
             }
+            MatrixCommandOutcome::SyncRequested => {
+                println!("[Matrix Command]   Outcome: Sync Members Request");
+                let reply = match &self.bridge {
+                    Some(bridge) => match bridge.sync_room_members(&event.room_id).await {
+                        Ok(reply) => reply,
+                        Err(err) => format!("Failed to sync Feishu members: {}", err),
+                    },
+                    None => "Member sync is not available on this bridge.".to_string(),
+                };
+                if let Err(err) = self.send_matrix_command_reply(&event.room_id, &reply).await {
+                    warn!(
+                        room_id = %event.room_id,
+                        error = %err,
+                        "Failed to send Matrix command reply"
+                    );
+                }
+            }
+            MatrixCommandOutcome::CreateRequested => {
+                println!("[Matrix Command]   Outcome: Create Feishu Chat Request");
+                let reply = match &self.bridge {
+                    Some(bridge) => match bridge
+                        .create_feishu_chat_for_room(&event.room_id, &event.sender)
+                        .await
+                    {
+                        Ok(mapping) => format!("Created Feishu chat: {}", mapping.feishu_chat_id),
+                        Err(err) => format!("Failed to create Feishu chat: {}", err),
+                    },
+                    None => "Creating Feishu chats is not available on this bridge.".to_string(),
+                };
+                if let Err(err) = self.send_matrix_command_reply(&event.room_id, &reply).await {
+                    warn!(
+                        room_id = %event.room_id,
+                        error = %err,
+                        "Failed to send Matrix command reply"
+                    );
+                }
+            }
+            MatrixCommandOutcome::PmRequested { target } => {
+                println!("[Matrix Command]   Outcome: PM Request to {}", target);
+                let reply = match &self.bridge {
+                    Some(bridge) => match bridge.start_direct_chat(&event.sender, &target).await {
+                        Ok(reply) => reply,
+                        Err(err) => format!("Failed to start Feishu direct chat: {}", err),
+                    },
+                    None => "Direct chats are not available on this bridge.".to_string(),
+                };
+                if let Err(err) = self.send_matrix_command_reply(&event.room_id, &reply).await {
+                    warn!(
+                        room_id = %event.room_id,
+                        error = %err,
+                        "Failed to send Matrix command reply"
+                    );
+                }
+            }
+            MatrixCommandOutcome::LinkRequested {
+                matrix_user_id,
+                feishu_user_id,
+            } => {
+                println!(
+                    "[Matrix Command]   Outcome: Link {} to {}",
+                    matrix_user_id, feishu_user_id
+                );
+                let reply = match &self.bridge {
+                    Some(bridge) => match bridge
+                        .link_matrix_user(&matrix_user_id, Some(&feishu_user_id))
+                        .await
+                    {
+                        Ok(reply) => reply,
+                        Err(err) => format!("Failed to link Feishu user: {}", err),
+                    },
+                    None => "Linking users is not available on this bridge.".to_string(),
+                };
+                if let Err(err) = self.send_matrix_command_reply(&event.room_id, &reply).await {
+                    warn!(
+                        room_id = %event.room_id,
+                        error = %err,
+                        "Failed to send Matrix command reply"
+                    );
+                }
+            }
+            MatrixCommandOutcome::UnlinkRequested { matrix_user_id } => {
+                println!("[Matrix Command]   Outcome: Unlink {}", matrix_user_id);
+                let reply = match &self.bridge {
+                    Some(bridge) => match bridge.link_matrix_user(&matrix_user_id, None).await {
+                        Ok(reply) => reply,
+                        Err(err) => format!("Failed to unlink Feishu user: {}", err),
+                    },
+                    None => "Linking users is not available on this bridge.".to_string(),
+                };
+                if let Err(err) = self.send_matrix_command_reply(&event.room_id, &reply).await {
+                    warn!(
+                        room_id = %event.room_id,
+                        error = %err,
+                        "Failed to send Matrix command reply"
+                    );
+                }
+            }
             MatrixCommandOutcome::UnbridgeRequested => {
                 println!("[Matrix Command]   Outcome: Unbridge Request");
                 let reply = self.handle_unbridge_request(&event.room_id).await?;
@@ -658,6 +846,16 @@ impl MatrixEventProcessor {
 
         self.room_store.create_room_mapping(&mapping).await?;
         info!("Created bridge mapping: {} <-> {}", room_id, feishu_chat_id);
+        if let Some(bridge) = &self.bridge {
+            bridge.spawn_portal_member_sync(feishu_chat_id, room_id);
+            if let Err(err) = bridge.add_bridged_room_to_spaces(room_id, sender).await {
+                warn!(
+                    room_id = %room_id,
+                    error = %err,
+                    "Failed to add bridged room to spaces"
+                );
+            }
+        }
 
         Ok(format!("Bridged to Feishu chat: {}", feishu_chat_id))
     }
@@ -674,6 +872,9 @@ impl MatrixEventProcessor {
 
         println!("[Bridge Action]   Feishu Chat: {}", mapping.feishu_chat_id);
         self.room_store.delete_room_mapping(mapping.id).await?;
+        if let Some(bridge) = &self.bridge {
+            bridge.delete_portal_by_mxid(room_id).await?;
+        }
         println!("[Bridge Action]   ✅ Bridge removed");
         info!("Removed bridge mapping for room {}", room_id);
 
@@ -758,13 +959,65 @@ impl MatrixEventProcessor {
                         error!("Failed to join room {}: {:?}", event.room_id, e);
                     }
                 }
+            } else if self.is_bridge_puppet(state_key) && !self.is_bridge_bot_sender(&event.sender)
+            {
+                if !self.config.bridge.has_full_access(&event.sender) {
+                    debug!(
+                        room_id = %event.room_id,
+                        sender = %event.sender,
+                        "Ignoring puppet invite from user without bridge permission"
+                    );
+                } else if let Some(bridge) = &self.bridge
+                    && let Err(err) = bridge
+                        .add_puppet_to_feishu_chat(&event.room_id, state_key)
+                        .await
+                {
+                    warn!(
+                        room_id = %event.room_id,
+                        puppet = %state_key,
+                        error = %err,
+                        "Failed to add invited puppet to Feishu chat"
+                    );
+                }
             } else {
                 println!("[Member Event] ⏭️  Not invited to this room, skipping auto-join");
             }
         } else if membership == "join" {
-            println!("[Member Event] ✅ User joined room (no action needed)");
-        } else if membership == "leave" {
+            println!("[Member Event] ✅ User joined room");
+            if let Some(bridge) = &self.bridge
+                && let Err(err) = bridge
+                    .add_portal_to_user_space(state_key, &event.room_id)
+                    .await
+            {
+                warn!(
+                    room_id = %event.room_id,
+                    user_id = %state_key,
+                    error = %err,
+                    "Failed to add portal to user space"
+                );
+            }
+        } else if membership == "leave" || membership == "ban" {
             println!("[Member Event] 👋 User left room");
+            if let Err(err) = self.bridge_matrix_departure(event, state_key).await {
+                warn!(
+                    room_id = %event.room_id,
+                    user_id = %state_key,
+                    error = %err,
+                    "Failed to bridge Matrix membership change to Feishu"
+                );
+            }
+            if let Some(bridge) = &self.bridge
+                && let Err(err) = bridge
+                    .remove_portal_from_user_space(state_key, &event.room_id)
+                    .await
+            {
+                warn!(
+                    room_id = %event.room_id,
+                    user_id = %state_key,
+                    error = %err,
+                    "Failed to remove portal from user space"
+                );
+            }
         } else {
             println!("[Member Event] ❓ Other membership change: {}", membership);
         }
@@ -772,7 +1025,91 @@ impl MatrixEventProcessor {
         Ok(())
     }
 
+    async fn handle_power_levels_event(&self, event: &MatrixEvent) -> anyhow::Result<()> {
+        if !self.config.bridge.bridge_matrix_power_levels
+            || self.is_bridge_bot_sender(&event.sender)
+            || !self.config.bridge.has_full_access(&event.sender)
+        {
+            return Ok(());
+        }
+        let (Some(bridge), Some(content)) = (&self.bridge, &event.content) else {
+            return Ok(());
+        };
+
+        bridge
+            .push_power_levels_to_feishu(&event.room_id, content, event.prev_content.as_ref())
+            .await
+    }
+
+    async fn handle_room_metadata_event(&self, event: &MatrixEvent) -> anyhow::Result<()> {
+        if self.is_bridge_bot_sender(&event.sender) {
+            debug!(
+                room_id = %event.room_id,
+                event_type = %event.event_type,
+                "Ignoring room metadata change sent by bridge bot to prevent self-loop"
+            );
+            return Ok(());
+        }
+        if !self.config.bridge.has_full_access(&event.sender) {
+            debug!(
+                room_id = %event.room_id,
+                sender = %event.sender,
+                "Ignoring room metadata change from user without bridge permission"
+            );
+            return Ok(());
+        }
+        let (Some(bridge), Some(content)) = (&self.bridge, &event.content) else {
+            return Ok(());
+        };
+
+        bridge
+            .sync_matrix_room_metadata(&event.room_id, &event.event_type, content)
+            .await
+    }
+
+    async fn bridge_matrix_departure(
+        &self,
+        event: &MatrixEvent,
+        state_key: &str,
+    ) -> anyhow::Result<()> {
+        let Some(bridge) = &self.bridge else {
+            return Ok(());
+        };
+        if self.is_bridge_bot_sender(&event.sender)
+            || !self.config.bridge.has_full_access(&event.sender)
+        {
+            return Ok(());
+        }
+
+        if event.sender != state_key {
+            if self.is_bridge_puppet(state_key) {
+                bridge
+                    .remove_puppet_from_feishu_chat(&event.room_id, state_key)
+                    .await?;
+            }
+        } else if self.config.bridge.bridge_matrix_leave {
+            bridge
+                .handle_linked_user_leave(&event.room_id, state_key)
+                .await?;
+        }
+        Ok(())
+    }
+
     async fn handle_redaction_event(&self, event: &MatrixEvent) -> anyhow::Result<()> {
+        if self.config.bridge.bridge_matrix_reactions
+            && !self.is_bridge_bot_sender(&event.sender)
+            && let Some(bridge) = &self.bridge
+            && let Some(redacts) = event
+                .content
+                .as_ref()
+                .and_then(|c| c.get("redacts"))
+                .and_then(Value::as_str)
+            && bridge.redact_bridged_reaction(redacts).await?
+        {
+            println!("[Redaction]   ✅ Reaction removed on Feishu");
+            return Ok(());
+        }
+
         if !self.config.bridge.bridge_matrix_redactions {
             println!("[Redaction] ⚠️  Redactions disabled in config, skipping");
             return Ok(());
@@ -851,6 +1188,37 @@ impl MatrixEventProcessor {
         println!("[Reaction]   Sender: {:?}", event.sender);
         println!("[Reaction]   Relates To: {:?}", relates_to);
         debug!("Reaction event: {:?}", relates_to);
+
+        if self.is_bridge_bot_sender(&event.sender) {
+            return Ok(());
+        }
+        let Some(relates_to) = relates_to
+            .filter(|value| value.get("rel_type").and_then(Value::as_str) == Some("m.annotation"))
+        else {
+            return Ok(());
+        };
+        let (Some(event_id), Some(target_event_id), Some(key)) = (
+            event.event_id.as_deref(),
+            relates_to.get("event_id").and_then(Value::as_str),
+            relates_to.get("key").and_then(Value::as_str),
+        ) else {
+            println!("[Reaction] ⚠️  Incomplete annotation, skipping");
+            return Ok(());
+        };
+        let Some(bridge) = &self.bridge else {
+            return Ok(());
+        };
+
+        bridge
+            .bridge_matrix_reaction(
+                event_id,
+                &event.room_id,
+                &event.sender,
+                target_event_id,
+                key,
+            )
+            .await?;
+        println!("[Reaction]   ✅ Reaction sent to Feishu");
         Ok(())
     }
 
@@ -944,6 +1312,14 @@ impl MatrixEventProcessor {
         }
     }
 
+    fn is_bridge_puppet(&self, user_id: &str) -> bool {
+        sender_matches_bridge_puppet(
+            user_id,
+            &self.config.bridge.username_template,
+            &self.config.bridge.domain,
+        )
+    }
+
     fn is_bridge_bot_sender(&self, sender: &str) -> bool {
         sender_matches_bridge_bot(
             sender,
@@ -980,7 +1356,11 @@ fn sender_matches_bridge_bot(
     sender_matches_bridge_puppet(sender, username_template, domain)
 }
 
-fn sender_matches_bridge_puppet(sender: &str, username_template: &str, domain: &str) -> bool {
+pub(crate) fn sender_matches_bridge_puppet(
+    sender: &str,
+    username_template: &str,
+    domain: &str,
+) -> bool {
     let sender = sender.trim();
     let Some(stripped) = sender.strip_prefix('@') else {
         return false;
@@ -1061,14 +1441,14 @@ fn sanitize_marker_fragment(value: &str) -> String {
     }
 }
 
-struct RoomRateLimiter {
+pub(crate) struct RoomRateLimiter {
     limit: usize,
     window: Duration,
     events_by_room: Mutex<HashMap<String, VecDeque<Instant>>>,
 }
 
 impl RoomRateLimiter {
-    fn new(limit: u32, window_millis: u64) -> Self {
+    pub(crate) fn new(limit: u32, window_millis: u64) -> Self {
         Self {
             limit: limit as usize,
             window: Duration::from_millis(window_millis),
@@ -1076,7 +1456,7 @@ impl RoomRateLimiter {
         }
     }
 
-    fn allow(&self, room_id: &str) -> bool {
+    pub(crate) fn allow(&self, room_id: &str) -> bool {
         if self.limit == 0 || self.window.is_zero() {
             return true;
         }
@@ -1103,6 +1483,24 @@ impl RoomRateLimiter {
         queue.push_back(now);
         true
     }
+
+    /// How long until `allow` can succeed again for `room_id`.
+    pub(crate) fn retry_after(&self, room_id: &str) -> Duration {
+        let guard = self
+            .events_by_room
+            .lock()
+            .expect("rate limiter mutex poisoned");
+        match guard.get(room_id) {
+            Some(queue) if queue.len() >= self.limit => queue
+                .front()
+                .map(|oldest| {
+                    // `allow` only evicts entries strictly older than the window.
+                    self.window.saturating_sub(oldest.elapsed()) + Duration::from_millis(1)
+                })
+                .unwrap_or_default(),
+            _ => Duration::ZERO,
+        }
+    }
 }
 
 fn truncate_text(text: &str, max_chars: usize) -> (String, bool) {
@@ -1132,7 +1530,24 @@ fn render_failure_notice(template: &str, event: &MatrixEvent, err: &anyhow::Erro
 
 #[cfg(test)]
 mod tests {
-    use super::sender_matches_bridge_bot;
+    use super::{RoomRateLimiter, sender_matches_bridge_bot};
+
+    #[test]
+    fn room_rate_limiter_reports_when_the_window_reopens() {
+        let limiter = RoomRateLimiter::new(1, 50);
+        assert_eq!(
+            limiter.retry_after("!room:localhost"),
+            std::time::Duration::ZERO
+        );
+        assert!(limiter.allow("!room:localhost"));
+        assert!(!limiter.allow("!room:localhost"));
+        assert!(limiter.allow("!other:localhost"));
+
+        let wait = limiter.retry_after("!room:localhost");
+        assert!(!wait.is_zero() && wait <= std::time::Duration::from_millis(51));
+        std::thread::sleep(wait);
+        assert!(limiter.allow("!room:localhost"));
+    }
 
     #[test]
     fn sender_matches_configured_bot_username() {
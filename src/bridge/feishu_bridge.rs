@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -20,27 +20,44 @@ use url::Url;
 use uuid::Uuid;
 
 use super::MatrixEvent;
-use super::message::{BridgeMessage, MessageType};
+use super::message::{BridgeMessage, MessageMention, MessageType};
 use super::portal::{BridgePortal, RoomType};
 use super::puppet::BridgePuppet;
 use super::user::{BridgeUser, UserSyncPolicy};
+use crate::bridge::event_processor::{RoomRateLimiter, sender_matches_bridge_puppet};
+use crate::bridge::matrix_to_feishu_dispatcher::{feishu_user_id_type, sha256_hex};
 use crate::bridge::{
     MatrixCommandHandler, MatrixCommandOutcome, MatrixEventProcessor, MessageFlow, PresenceHandler,
-    ProvisioningCoordinator,
+    ProvisioningCoordinator, ProvisioningError,
 };
 use crate::config::Config;
 use crate::database::sqlite_stores::SqliteStores;
 use crate::database::{
-    Database, DeadLetterEvent, DeadLetterStore, EventStore, MediaStore, MessageMapping,
-    MessageStore, ProcessedEvent, RoomStore, UserMapping, UserStore,
+    Database, DeadLetterEvent, DeadLetterStore, DirectChat, DirectChatStore, EventStore,
+    MediaCacheEntry, MediaStore, MessageMapping, MessageStore, PortalStore, ProcessedEvent,
+    ReactionMapping, ReactionStore, RoomMapping, RoomStore, SpaceStore, UserMapping, UserStore,
 };
-use crate::feishu::FeishuService;
+use crate::feishu::{FeishuChatProfile, FeishuService};
 use crate::formatter;
 use crate::util::build_trace_id;
 use crate::web::{ProvisioningApi, ScopedTimer, global_metrics, metrics_endpoint};
 
 type SqlitePool = Pool<ConnectionManager<SqliteConnection>>;
 
+const USER_LOOKUP_PAGE_SIZE: i64 = 200;
+const DEFAULT_TENANT_KEY: &str = "default";
+const THIRDPARTY_PROTOCOL: &str = "feishu";
+const THIRDPARTY_RESULT_LIMIT: usize = 20;
+const CHAT_LOOKUP_MAX_PAGES: usize = 5;
+const CONTACT_BATCH_SIZE: usize = 50;
+const READ_RECEIPT_ROOM_LIMIT: u32 = 20;
+const READ_RECEIPT_WINDOW_MILLIS: u64 = 10_000;
+// Below the bridge bot's 100 so the bot can still demote owners and lock rooms.
+const FEISHU_OWNER_POWER_LEVEL: i64 = 95;
+const FEISHU_MANAGER_POWER_LEVEL: i64 = 50;
+const ALIAS_PORTAL_WAIT_ATTEMPTS: usize = 20;
+const ALIAS_PORTAL_WAIT_INTERVAL_MILLIS: u64 = 250;
+
 #[derive(Clone)]
 pub struct FeishuBridge {
     pub config: Arc<Config>,
@@ -50,8 +67,6 @@ pub struct FeishuBridge {
     pub bot_intent: Intent,
     stores: SqliteStores,
     _users_by_mxid: Arc<RwLock<HashMap<String, BridgeUser>>>,
-    portals_by_mxid: Arc<RwLock<HashMap<String, BridgePortal>>>,
-    portals_by_feishu_room: Arc<RwLock<HashMap<String, String>>>,
     _puppets: Arc<RwLock<HashMap<String, BridgePuppet>>>,
     intents: Arc<RwLock<HashMap<String, Intent>>>,
     command_handler: Arc<MatrixCommandHandler>,
@@ -60,6 +75,10 @@ pub struct FeishuBridge {
     started_at: Instant,
     user_sync_policy: UserSyncPolicy,
     user_last_synced_at: Arc<RwLock<HashMap<String, Instant>>>,
+    read_receipt_limiter: Arc<RoomRateLimiter>,
+    // Receipts held back by the limiter: Matrix room -> reader MXID -> newest read message.
+    pending_read_receipts: Arc<RwLock<HashMap<String, HashMap<String, MessageMapping>>>>,
+    alias_portal_reservations: Arc<RwLock<HashSet<String>>>,
 }
 
 impl FeishuBridge {
@@ -130,8 +149,6 @@ impl FeishuBridge {
             bot_intent,
             stores,
             _users_by_mxid: Arc::new(RwLock::new(HashMap::new())),
-            portals_by_mxid: Arc::new(RwLock::new(HashMap::new())),
-            portals_by_feishu_room: Arc::new(RwLock::new(HashMap::new())),
             _puppets: Arc::new(RwLock::new(HashMap::new())),
             intents: Arc::new(RwLock::new(HashMap::new())),
             command_handler,
@@ -140,6 +157,12 @@ impl FeishuBridge {
             started_at: Instant::now(),
             user_sync_policy,
             user_last_synced_at: Arc::new(RwLock::new(HashMap::new())),
+            read_receipt_limiter: Arc::new(RoomRateLimiter::new(
+                READ_RECEIPT_ROOM_LIMIT,
+                READ_RECEIPT_WINDOW_MILLIS,
+            )),
+            pending_read_receipts: Arc::new(RwLock::new(HashMap::new())),
+            alias_portal_reservations: Arc::new(RwLock::new(HashSet::new())),
         })
     }
 
@@ -185,6 +208,20 @@ impl FeishuBridge {
             maintenance_bridge.run_user_sync_maintenance_loop().await;
         });
 
+        let backfill_bridge = self.clone();
+        tokio::spawn(async move {
+            match backfill_bridge.backfill_portal_spaces().await {
+                Ok(0) => {}
+                Ok(added) => info!(added, "Backfilled bridged rooms into tenant spaces"),
+                Err(err) => warn!(error = %err, "Failed to backfill tenant spaces"),
+            }
+            match backfill_bridge.backfill_portal_bridge_info().await {
+                Ok(0) => {}
+                Ok(updated) => info!(updated, "Backfilled bridge info into bridged rooms"),
+                Err(err) => warn!(error = %err, "Failed to backfill bridge info"),
+            }
+        });
+
         let room_store = self.stores.room_store();
         let message_store = self.stores.message_store();
         let event_store = self.stores.event_store();
@@ -194,16 +231,19 @@ impl FeishuBridge {
             self.feishu_service.clone(),
         ));
 
-        let event_processor = Arc::new(MatrixEventProcessor::new(
-            self.config.clone(),
-            self.feishu_service.clone(),
-            room_store,
-            self.user_store(),
-            message_store,
-            event_store,
-            media_store,
-            message_flow,
-        ));
+        let event_processor = Arc::new(
+            MatrixEventProcessor::new(
+                self.config.clone(),
+                self.feishu_service.clone(),
+                room_store,
+                self.user_store(),
+                message_store,
+                event_store,
+                media_store,
+                message_flow,
+            )
+            .with_bridge(self.clone()),
+        );
 
         let handler = Arc::new(BridgeHandler {
             bridge: self.clone(),
@@ -260,7 +300,19 @@ impl FeishuBridge {
             .push(Router::with_path("/_matrix/app/v1").push(provisioning_api.clone().router()))
             .push(Router::with_path("/admin").push(provisioning_api.router()));
 
+        // Alias queries are answered here rather than by matrix-bot-sdk, which creates the
+        // room itself and never reports the room ID back to the bridge.
+        let alias_state = AliasQueryState {
+            bridge: self.clone(),
+            hs_token: self.config.registration.hs_token.clone(),
+        };
+        let alias_router = Router::new()
+            .hoop(affix_state::inject(alias_state))
+            .push(Router::with_path("/_matrix/app/v1/rooms/{room_alias}").get(alias_query_handler))
+            .push(Router::with_path("/rooms/{room_alias}").get(alias_query_handler));
+
         let router = Router::new()
+            .push(alias_router)
             .push(base_router)
             .push(provisioning_router)
             .push(health_router);
@@ -304,6 +356,18 @@ impl FeishuBridge {
         self.stores.room_store()
     }
 
+    pub fn space_store(&self) -> Arc<dyn SpaceStore> {
+        self.stores.space_store()
+    }
+
+    pub fn direct_chat_store(&self) -> Arc<dyn DirectChatStore> {
+        self.stores.direct_chat_store()
+    }
+
+    pub fn portal_store(&self) -> Arc<dyn PortalStore> {
+        self.stores.portal_store()
+    }
+
     pub fn user_store(&self) -> Arc<dyn UserStore> {
         self.stores.user_store()
     }
@@ -316,6 +380,10 @@ impl FeishuBridge {
         self.stores.message_store()
     }
 
+    pub fn reaction_store(&self) -> Arc<dyn ReactionStore> {
+        self.stores.reaction_store()
+    }
+
     pub fn dead_letter_store(&self) -> Arc<dyn DeadLetterStore> {
         self.stores.dead_letter_store()
     }
@@ -452,6 +520,15 @@ impl FeishuBridge {
                 )?;
                 self.handle_feishu_message(message).await
             }
+            "im.message.updated_v1" => {
+                let message: BridgeMessage = serde_json::from_value(
+                    payload
+                        .get("message")
+                        .cloned()
+                        .ok_or_else(|| anyhow::anyhow!("dead-letter missing message field"))?,
+                )?;
+                self.handle_feishu_message_updated(message).await
+            }
             "im.message.recalled_v1" => {
                 let chat_id = payload
                     .get("chat_id")
@@ -505,6 +582,10 @@ impl FeishuBridge {
                     .get("chat_name")
                     .and_then(Value::as_str)
                     .map(ToOwned::to_owned);
+                let description = payload
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .map(ToOwned::to_owned);
                 let chat_mode = payload
                     .get("chat_mode")
                     .and_then(Value::as_str)
@@ -513,8 +594,14 @@ impl FeishuBridge {
                     .get("chat_type")
                     .and_then(Value::as_str)
                     .map(ToOwned::to_owned);
-                self.handle_feishu_chat_updated(chat_id, chat_name, chat_mode, chat_type)
-                    .await
+                self.handle_feishu_chat_updated(
+                    chat_id,
+                    chat_name,
+                    description,
+                    chat_mode,
+                    chat_type,
+                )
+                .await
             }
             "im.chat.disbanded_v1" => {
                 let chat_id = payload
@@ -523,6 +610,43 @@ impl FeishuBridge {
                     .ok_or_else(|| anyhow::anyhow!("dead-letter missing chat_id"))?;
                 self.handle_feishu_chat_disbanded(chat_id).await
             }
+            "im.message.message_read_v1" => {
+                let reader_id = payload
+                    .get("reader_id")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| anyhow::anyhow!("dead-letter missing reader_id"))?;
+                let message_ids = payload
+                    .get("message_ids")
+                    .and_then(Value::as_array)
+                    .ok_or_else(|| anyhow::anyhow!("dead-letter missing message_ids"))?
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(ToOwned::to_owned)
+                    .collect::<Vec<_>>();
+                self.handle_feishu_message_read(reader_id, &message_ids)
+                    .await
+            }
+            "im.message.reaction.created_v1" | "im.message.reaction.deleted_v1" => {
+                let message_id = payload
+                    .get("message_id")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| anyhow::anyhow!("dead-letter missing message_id"))?;
+                let emoji_type = payload
+                    .get("emoji_type")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| anyhow::anyhow!("dead-letter missing emoji_type"))?;
+                let user_id = payload
+                    .get("user_id")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| anyhow::anyhow!("dead-letter missing user_id"))?;
+                if event_type == "im.message.reaction.created_v1" {
+                    self.handle_feishu_reaction_created(message_id, emoji_type, user_id)
+                        .await
+                } else {
+                    self.handle_feishu_reaction_deleted(message_id, emoji_type, user_id)
+                        .await
+                }
+            }
             _ => anyhow::bail!("unsupported dead-letter event_type '{}'", event_type),
         }
     }
@@ -618,919 +742,4362 @@ impl FeishuBridge {
         Ok(Some(profile.user_id))
     }
 
-    async fn run_user_sync_maintenance_loop(self) {
-        let interval_secs = self.config.bridge.user_sync_interval_secs.max(30);
-        let ticker = Duration::from_secs(interval_secs);
-        let user_store = self.user_store();
-
+    pub async fn sync_portal_members(
+        &self,
+        feishu_chat_id: &str,
+        matrix_room_id: &str,
+    ) -> anyhow::Result<usize> {
+        let mut joined = 0;
+        let mut present = HashSet::new();
+        let mut complete = true;
+        let mut page_token: Option<String> = None;
         loop {
-            tokio::time::sleep(ticker).await;
-
-            let cutoff = self.user_sync_policy.stale_cutoff(Utc::now());
-            match user_store.cleanup_stale_user_mappings(cutoff).await {
-                Ok(removed) => {
-                    if removed > 0 {
-                        info!(
-                            removed = removed,
-                            stale_cutoff = %cutoff,
-                            "Cleaned stale user mappings"
+            let page = self
+                .feishu_service
+                .list_chat_members(feishu_chat_id, page_token.as_deref())
+                .await?;
+            for member in page.items {
+                match self
+                    .join_feishu_user_puppet(&member.member_id, matrix_room_id)
+                    .await
+                {
+                    Ok(Some(puppet_mxid)) => {
+                        joined += 1;
+                        present.insert(puppet_mxid);
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        complete = false;
+                        warn!(
+                            chat_id = %feishu_chat_id,
+                            matrix_room_id = %matrix_room_id,
+                            feishu_user_id = %member.member_id,
+                            error = %err,
+                            "Failed to join Feishu member puppet to Matrix room"
                         );
                     }
                 }
-                Err(err) => warn!(
-                    error = %err,
-                    "Failed to clean stale user mappings"
-                ),
             }
+            page_token = page.page_token.filter(|token| !token.is_empty());
+            if !page.has_more || page_token.is_none() {
+                break;
+            }
+        }
 
-            let retention = Duration::from_secs(interval_secs.saturating_mul(4).max(120));
-            let now = Instant::now();
-            self.user_last_synced_at
-                .write()
-                .await
-                .retain(|_, synced_at| now.duration_since(*synced_at) <= retention);
+        // A member whose puppet could not be resolved might still be joined, so only prune
+        // when every listed member was accounted for.
+        let mut removed = 0;
+        if complete {
+            for member in self
+                .appservice
+                .client
+                .get_joined_room_members(matrix_room_id)
+                .await?
+            {
+                if !self.is_bridge_puppet_mxid(&member) || present.contains(&member) {
+                    continue;
+                }
+                match self.leave_matrix_room_as(&member, matrix_room_id).await {
+                    Ok(()) => removed += 1,
+                    Err(err) => warn!(
+                        chat_id = %feishu_chat_id,
+                        matrix_room_id = %matrix_room_id,
+                        user_id = %member,
+                        error = %err,
+                        "Failed to remove stale Feishu puppet from Matrix room"
+                    ),
+                }
+            }
         }
-    }
 
-    pub async fn handle_feishu_message(&self, message: BridgeMessage) -> anyhow::Result<()> {
-        let _timer = ScopedTimer::new("feishu_message_process");
-        let trace_id = build_trace_id("feishu_to_matrix", None, Some(&message.id));
-        let content_preview = summarize_for_log(&message.content, 120);
-        global_metrics().record_trace_event("feishu_to_matrix", "received");
         info!(
-            trace_id = %trace_id,
-            feishu_message_id = %message.id,
-            chat_id = %message.room_id,
-            sender = %message.sender,
-            msg_type = ?message.msg_type,
-            content_preview = %content_preview,
-            attachment_count = message.attachments.len(),
-            "Handling Feishu message"
+            chat_id = %feishu_chat_id,
+            matrix_room_id = %matrix_room_id,
+            joined = joined,
+            removed = removed,
+            "Synced Feishu chat members into Matrix room"
         );
+        Ok(joined)
+    }
 
-        if self
-            .stores
-            .message_store()
-            .get_message_by_feishu_id(&message.id)
+    async fn join_feishu_user_puppet(
+        &self,
+        feishu_user_id: &str,
+        matrix_room_id: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let Some(feishu_user_id) = self.sync_feishu_user_mapping(feishu_user_id).await? else {
+            return Ok(None);
+        };
+        let Some(mapping) = self
+            .user_store()
+            .get_user_by_feishu_id(&feishu_user_id)
             .await?
-            .is_some()
-        {
-            global_metrics().record_trace_event("feishu_to_matrix", "duplicate");
-            debug!(
-                trace_id = %trace_id,
-                feishu_message_id = %message.id,
-                chat_id = %message.room_id,
-                "Skipping already bridged Feishu message (duplicate or echo)"
-            );
-            return Ok(());
-        }
-
+        else {
+            return Ok(None);
+        };
         let bridge_bot_mxid = format!(
             "@{}:{}",
             self.config.bridge.bot_username, self.config.bridge.domain
         );
-        let mut matrix_sender_mxid = bridge_bot_mxid.clone();
-        let mut canonical_sender_feishu_id = message.sender.clone();
+        let intent = self.get_or_create_intent(&mapping.matrix_user_id).await;
+        self.ensure_matrix_sender_joined_room(
+            &intent,
+            &mapping.matrix_user_id,
+            &bridge_bot_mxid,
+            matrix_room_id,
+        )
+        .await?;
+        Ok(Some(mapping.matrix_user_id))
+    }
+
+    async fn remove_feishu_user_puppet(
+        &self,
+        feishu_user_id: &str,
+        matrix_room_id: &str,
+    ) -> anyhow::Result<bool> {
+        let user_store = self.user_store();
+        let mapping = match user_store.get_user_by_feishu_id(feishu_user_id).await? {
+            Some(mapping) => Some(mapping),
+            None => match self.sync_feishu_user_mapping(feishu_user_id).await? {
+                Some(canonical_id) => user_store.get_user_by_feishu_id(&canonical_id).await?,
+                None => None,
+            },
+        };
+        let Some(mapping) = mapping else {
+            return Ok(false);
+        };
+        self.leave_matrix_room_as(&mapping.matrix_user_id, matrix_room_id)
+            .await?;
+        Ok(true)
+    }
 
+    pub async fn add_puppet_to_feishu_chat(
+        &self,
+        matrix_room_id: &str,
+        puppet_mxid: &str,
+    ) -> anyhow::Result<()> {
+        let Some((feishu_chat_id, feishu_user_id)) = self
+            .resolve_group_membership(matrix_room_id, puppet_mxid)
+            .await?
+        else {
+            return Ok(());
+        };
+        self.feishu_service
+            .add_chat_members(&feishu_chat_id, std::slice::from_ref(&feishu_user_id))
+            .await?;
         info!(
-            trace_id = %trace_id,
-            feishu_user_id = %message.sender,
-            "Syncing Feishu user metadata before Matrix delivery"
+            chat_id = %feishu_chat_id,
+            feishu_user_id = %feishu_user_id,
+            matrix_room_id = %matrix_room_id,
+            "Added Feishu user to chat after Matrix invite"
         );
-        match self.sync_feishu_user_mapping(&message.sender).await {
-            Ok(Some(canonical_feishu_user_id)) => {
-                canonical_sender_feishu_id = canonical_feishu_user_id.clone();
-                match self
-                    .user_store()
-                    .get_user_by_feishu_id(&canonical_feishu_user_id)
-                    .await
-                {
-                    Ok(Some(mapping)) => {
-                        matrix_sender_mxid = mapping.matrix_user_id.clone();
-                        println!(
-                            "[Feishu->Matrix Sender] mapping found: msg_id={} feishu_sender={} canonical_feishu_sender={} matrix_sender={}",
-                            message.id, message.sender, canonical_feishu_user_id, matrix_sender_mxid
-                        );
-                        info!(
-                            trace_id = %trace_id,
-                            feishu_user_id = %message.sender,
-                            canonical_feishu_user_id = %canonical_feishu_user_id,
-                            matrix_user_id = %matrix_sender_mxid,
-                            "Resolved mapped Matrix sender for Feishu message"
-                        );
-                    }
-                    Ok(None) => warn!(
-                        trace_id = %trace_id,
-                        feishu_user_id = %message.sender,
-                        canonical_feishu_user_id = %canonical_feishu_user_id,
-                        "Feishu user sync completed but no user mapping found; falling back to bridge bot sender"
-                    ),
-                    Err(err) => warn!(
-                        trace_id = %trace_id,
-                        feishu_user_id = %message.sender,
-                        canonical_feishu_user_id = %canonical_feishu_user_id,
-                        error = %err,
-                        "Failed to load synced Feishu user mapping; falling back to bridge bot sender"
-                    ),
-                }
-            }
-            Ok(None) => warn!(
-                trace_id = %trace_id,
-                feishu_user_id = %message.sender,
-                "Feishu message sender is empty; falling back to bridge bot sender"
-            ),
-            Err(err) => warn!(
-                trace_id = %trace_id,
-                feishu_user_id = %message.sender,
-                error = %err,
-                "Failed to sync Feishu user mapping metadata; falling back to bridge bot sender"
-            ),
-        }
-        if matrix_sender_mxid == bridge_bot_mxid {
-            println!(
-                "[Feishu->Matrix Sender] fallback to bot: msg_id={} feishu_sender={} canonical_feishu_sender={} bot_sender={}",
-                message.id, message.sender, canonical_sender_feishu_id, bridge_bot_mxid
-            );
-        }
-
-        let room_mapping = self
-            .stores
-            .room_store()
-            .get_room_by_feishu_id(&message.room_id)
+        self.ensure_matrix_user_registered(puppet_mxid).await?;
+        self.join_matrix_room_as_user(matrix_room_id, puppet_mxid)
             .await?;
+        Ok(())
+    }
 
-        let chat_profile = if room_mapping
-            .as_ref()
-            .and_then(|mapping| mapping.feishu_chat_name.as_deref())
-            .is_none()
-        {
-            self.feishu_service.get_chat(&message.room_id).await.ok()
-        } else {
-            None
+    pub async fn remove_puppet_from_feishu_chat(
+        &self,
+        matrix_room_id: &str,
+        puppet_mxid: &str,
+    ) -> anyhow::Result<()> {
+        let Some((feishu_chat_id, feishu_user_id)) = self
+            .resolve_group_membership(matrix_room_id, puppet_mxid)
+            .await?
+        else {
+            return Ok(());
         };
+        self.feishu_service
+            .remove_chat_members(&feishu_chat_id, std::slice::from_ref(&feishu_user_id))
+            .await?;
+        info!(
+            chat_id = %feishu_chat_id,
+            feishu_user_id = %feishu_user_id,
+            matrix_room_id = %matrix_room_id,
+            "Removed Feishu user from chat after Matrix kick"
+        );
+        Ok(())
+    }
 
-        let portal = if let Some(mut mapping) = room_mapping {
-            info!(
+    pub async fn handle_linked_user_leave(
+        &self,
+        matrix_room_id: &str,
+        matrix_user_id: &str,
+    ) -> anyhow::Result<()> {
+        let action = self.config.bridge.matrix_leave_action.as_str();
+        if action == "ignore" {
+            return Ok(());
+        }
+        let Some(feishu_user_id) = self
+            .user_store()
+            .get_linked_feishu_user(matrix_user_id)
+            .await?
+        else {
+            return Ok(());
+        };
+        let Some(mapping) = self
+            .stores
+            .room_store()
+            .get_room_by_matrix_id(matrix_room_id)
+            .await?
+        else {
+            return Ok(());
+        };
+        if is_feishu_p2p_chat(Some(&mapping.feishu_chat_type), None) {
+            return Ok(());
+        }
+
+        if action == "notify" {
+            self.feishu_service
+                .send_text_message(
+                    &mapping.feishu_chat_id,
+                    &format!("{} left the Matrix room", matrix_user_id),
+                )
+                .await?;
+        } else {
+            self.feishu_service
+                .remove_chat_members(
+                    &mapping.feishu_chat_id,
+                    std::slice::from_ref(&feishu_user_id),
+                )
+                .await?;
+        }
+        info!(
+            chat_id = %mapping.feishu_chat_id,
+            feishu_user_id = %feishu_user_id,
+            matrix_user_id = %matrix_user_id,
+            action = %action,
+            "Linked Matrix user left portal"
+        );
+        Ok(())
+    }
+
+    pub async fn link_matrix_user(
+        &self,
+        matrix_user_id: &str,
+        feishu_user_id: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let Some(feishu_user_id) = feishu_user_id else {
+            self.user_store()
+                .set_linked_feishu_user(matrix_user_id, None)
+                .await?;
+            return Ok(format!("Unlinked {} from Feishu", matrix_user_id));
+        };
+        if self.is_bridge_puppet_mxid(matrix_user_id) {
+            anyhow::bail!("{} is a bridge puppet and cannot be linked", matrix_user_id);
+        }
+
+        let profile = self.feishu_service.get_user(feishu_user_id).await?;
+        self.user_store()
+            .set_linked_feishu_user(matrix_user_id, Some(&profile.user_id))
+            .await?;
+        info!(
+            matrix_user_id = %matrix_user_id,
+            feishu_user_id = %profile.user_id,
+            "Linked Matrix user to Feishu account"
+        );
+        Ok(format!(
+            "Linked {} to Feishu user {} ({})",
+            matrix_user_id, profile.name, profile.user_id
+        ))
+    }
+
+    async fn resolve_group_membership(
+        &self,
+        matrix_room_id: &str,
+        puppet_mxid: &str,
+    ) -> anyhow::Result<Option<(String, String)>> {
+        let Some(mapping) = self
+            .stores
+            .room_store()
+            .get_room_by_matrix_id(matrix_room_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+        if is_feishu_p2p_chat(Some(&mapping.feishu_chat_type), None) {
+            return Ok(None);
+        }
+        let Some(user) = self.user_store().get_user_by_matrix_id(puppet_mxid).await? else {
+            return Ok(None);
+        };
+        Ok(Some((mapping.feishu_chat_id, user.feishu_user_id)))
+    }
+
+    pub fn spawn_portal_member_sync(&self, feishu_chat_id: &str, matrix_room_id: &str) {
+        let bridge = self.clone();
+        let feishu_chat_id = feishu_chat_id.to_string();
+        let matrix_room_id = matrix_room_id.to_string();
+        tokio::spawn(async move {
+            if let Err(err) = bridge
+                .sync_portal_members(&feishu_chat_id, &matrix_room_id)
+                .await
+            {
+                warn!(
+                    chat_id = %feishu_chat_id,
+                    matrix_room_id = %matrix_room_id,
+                    error = %err,
+                    "Initial Feishu member sync failed"
+                );
+            }
+            if let Err(err) = bridge
+                .sync_portal_power_levels(&feishu_chat_id, &matrix_room_id)
+                .await
+            {
+                warn!(
+                    chat_id = %feishu_chat_id,
+                    matrix_room_id = %matrix_room_id,
+                    error = %err,
+                    "Initial Feishu power level sync failed"
+                );
+            }
+        });
+    }
+
+    pub async fn sync_portal_power_levels(
+        &self,
+        feishu_chat_id: &str,
+        matrix_room_id: &str,
+    ) -> anyhow::Result<()> {
+        let chat = self.feishu_service.get_chat(feishu_chat_id).await?;
+        let mut desired: HashMap<String, i64> = HashMap::new();
+        for manager in &chat.user_manager_id_list {
+            for matrix_user_id in self.matrix_users_for_feishu_user(manager).await? {
+                desired.insert(matrix_user_id, FEISHU_MANAGER_POWER_LEVEL);
+            }
+        }
+        if let Some(owner) = chat.owner_id.as_deref().filter(|value| !value.is_empty()) {
+            for matrix_user_id in self.matrix_users_for_feishu_user(owner).await? {
+                desired.insert(matrix_user_id, FEISHU_OWNER_POWER_LEVEL);
+            }
+        }
+
+        let mut power_levels = self
+            .get_matrix_room_state(matrix_room_id, "m.room.power_levels")
+            .await?
+            .unwrap_or_else(|| json!({}));
+        let current = power_levels
+            .get("users")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        let bot_mxid = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
+        );
+        let bot_level = current
+            .get(&bot_mxid)
+            .and_then(Value::as_i64)
+            .unwrap_or(100);
+        // Only levels the bridge hands out are lowered or removed; levels granted on Matrix
+        // and anyone at or above the bot (which the homeserver would refuse) are left alone.
+        let mut users = current.clone();
+        for (user_id, level) in &current {
+            let Some(level) = level.as_i64() else {
+                continue;
+            };
+            if *user_id == bot_mxid
+                || desired.contains_key(user_id)
+                || level >= bot_level
+                || !is_feishu_role_power_level(level)
+            {
+                continue;
+            }
+            if self.feishu_user_for_matrix_user(user_id).await?.is_some() {
+                users.remove(user_id);
+            }
+        }
+        for (user_id, level) in desired {
+            let assignable = match current.get(&user_id).and_then(Value::as_i64) {
+                None => true,
+                Some(current_level) => {
+                    current_level < bot_level
+                        && (is_feishu_role_power_level(current_level) || current_level < level)
+                }
+            };
+            if assignable {
+                users.insert(user_id, json!(level));
+            }
+        }
+        if users == current {
+            return Ok(());
+        }
+
+        power_levels["users"] = Value::Object(users);
+        self.send_matrix_state_event(matrix_room_id, "m.room.power_levels", "", power_levels)
+            .await?;
+        info!(
+            chat_id = %feishu_chat_id,
+            matrix_room_id = %matrix_room_id,
+            "Synced Feishu owner/managers to Matrix power levels"
+        );
+        Ok(())
+    }
+
+    pub async fn push_power_levels_to_feishu(
+        &self,
+        matrix_room_id: &str,
+        content: &Value,
+        prev_content: Option<&Value>,
+    ) -> anyhow::Result<()> {
+        let (promoted, demoted) = manager_level_changes(prev_content, content);
+        if promoted.is_empty() && demoted.is_empty() {
+            return Ok(());
+        }
+        let Some(mapping) = self
+            .stores
+            .room_store()
+            .get_room_by_matrix_id(matrix_room_id)
+            .await?
+        else {
+            return Ok(());
+        };
+        if is_feishu_p2p_chat(Some(&mapping.feishu_chat_type), None) {
+            return Ok(());
+        }
+        let chat = self
+            .feishu_service
+            .get_chat(&mapping.feishu_chat_id)
+            .await?;
+
+        let mut added = Vec::new();
+        for user_id in &promoted {
+            if let Some(feishu_user_id) = self.feishu_user_for_matrix_user(user_id).await?
+                && chat.owner_id.as_deref() != Some(feishu_user_id.as_str())
+                && !chat.user_manager_id_list.contains(&feishu_user_id)
+                && !added.contains(&feishu_user_id)
+            {
+                added.push(feishu_user_id);
+            }
+        }
+        let mut removed = Vec::new();
+        for user_id in &demoted {
+            if let Some(feishu_user_id) = self.feishu_user_for_matrix_user(user_id).await?
+                && chat.user_manager_id_list.contains(&feishu_user_id)
+                && !removed.contains(&feishu_user_id)
+            {
+                removed.push(feishu_user_id);
+            }
+        }
+        if !added.is_empty() {
+            self.feishu_service
+                .add_chat_managers(&mapping.feishu_chat_id, &added)
+                .await?;
+        }
+        if !removed.is_empty() {
+            self.feishu_service
+                .remove_chat_managers(&mapping.feishu_chat_id, &removed)
+                .await?;
+        }
+        if !added.is_empty() || !removed.is_empty() {
+            info!(
+                chat_id = %mapping.feishu_chat_id,
+                matrix_room_id = %matrix_room_id,
+                added = added.len(),
+                removed = removed.len(),
+                "Pushed Matrix power level changes to Feishu chat managers"
+            );
+        }
+        Ok(())
+    }
+
+    async fn matrix_users_for_feishu_user(
+        &self,
+        feishu_user_id: &str,
+    ) -> anyhow::Result<Vec<String>> {
+        let Some(feishu_user_id) = self.sync_feishu_user_mapping(feishu_user_id).await? else {
+            return Ok(Vec::new());
+        };
+        let mut matrix_user_ids = Vec::new();
+        if let Some(mapping) = self
+            .user_store()
+            .get_user_by_feishu_id(&feishu_user_id)
+            .await?
+        {
+            matrix_user_ids.push(mapping.matrix_user_id);
+        }
+        if let Some(linked) = self
+            .user_store()
+            .get_linked_matrix_user(&feishu_user_id)
+            .await?
+            && !matrix_user_ids.contains(&linked)
+        {
+            matrix_user_ids.push(linked);
+        }
+        Ok(matrix_user_ids)
+    }
+
+    async fn feishu_user_for_matrix_user(
+        &self,
+        matrix_user_id: &str,
+    ) -> anyhow::Result<Option<String>> {
+        if let Some(mapping) = self
+            .user_store()
+            .get_user_by_matrix_id(matrix_user_id)
+            .await?
+        {
+            return Ok(Some(mapping.feishu_user_id));
+        }
+        Ok(self
+            .user_store()
+            .get_linked_feishu_user(matrix_user_id)
+            .await?)
+    }
+
+    async fn run_user_sync_maintenance_loop(self) {
+        let interval_secs = self.config.bridge.user_sync_interval_secs.max(30);
+        let ticker = Duration::from_secs(interval_secs);
+        let user_store = self.user_store();
+
+        loop {
+            tokio::time::sleep(ticker).await;
+
+            let cutoff = self.user_sync_policy.stale_cutoff(Utc::now());
+            match user_store.cleanup_stale_user_mappings(cutoff).await {
+                Ok(removed) => {
+                    if removed > 0 {
+                        info!(
+                            removed = removed,
+                            stale_cutoff = %cutoff,
+                            "Cleaned stale user mappings"
+                        );
+                    }
+                }
+                Err(err) => warn!(
+                    error = %err,
+                    "Failed to clean stale user mappings"
+                ),
+            }
+
+            let retention = Duration::from_secs(interval_secs.saturating_mul(4).max(120));
+            let now = Instant::now();
+            self.user_last_synced_at
+                .write()
+                .await
+                .retain(|_, synced_at| now.duration_since(*synced_at) <= retention);
+        }
+    }
+
+    pub async fn handle_feishu_message(&self, message: BridgeMessage) -> anyhow::Result<()> {
+        let _timer = ScopedTimer::new("feishu_message_process");
+        let trace_id = build_trace_id("feishu_to_matrix", None, Some(&message.id));
+        let content_preview = summarize_for_log(&message.content, 120);
+        global_metrics().record_trace_event("feishu_to_matrix", "received");
+        info!(
+            trace_id = %trace_id,
+            feishu_message_id = %message.id,
+            chat_id = %message.room_id,
+            sender = %message.sender,
+            msg_type = ?message.msg_type,
+            content_preview = %content_preview,
+            attachment_count = message.attachments.len(),
+            "Handling Feishu message"
+        );
+
+        if self
+            .stores
+            .message_store()
+            .get_message_by_feishu_id(&message.id)
+            .await?
+            .is_some()
+        {
+            global_metrics().record_trace_event("feishu_to_matrix", "duplicate");
+            debug!(
                 trace_id = %trace_id,
-                feishu_chat_id = %message.room_id,
-                matrix_room_id = %mapping.matrix_room_id,
-                chat_type = %mapping.feishu_chat_type,
-                "Found room mapping for Feishu message"
+                feishu_message_id = %message.id,
+                chat_id = %message.room_id,
+                "Skipping already bridged Feishu message (duplicate or echo)"
             );
-            if mapping.feishu_chat_name.is_none() {
-                if let Some(name) = chat_profile.as_ref().and_then(|chat| chat.name.clone()) {
-                    mapping.feishu_chat_name = Some(name.clone());
-                    mapping.updated_at = Utc::now();
-                    if let Err(err) = self.stores.room_store().update_room_mapping(&mapping).await {
+            return Ok(());
+        }
+
+        let bridge_bot_mxid = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
+        );
+        let mut matrix_sender_mxid = bridge_bot_mxid.clone();
+        let mut canonical_sender_feishu_id = message.sender.clone();
+
+        info!(
+            trace_id = %trace_id,
+            feishu_user_id = %message.sender,
+            "Syncing Feishu user metadata before Matrix delivery"
+        );
+        match self.sync_feishu_user_mapping(&message.sender).await {
+            Ok(Some(canonical_feishu_user_id)) => {
+                canonical_sender_feishu_id = canonical_feishu_user_id.clone();
+                match self
+                    .user_store()
+                    .get_user_by_feishu_id(&canonical_feishu_user_id)
+                    .await
+                {
+                    Ok(Some(mapping)) => {
+                        matrix_sender_mxid = mapping.matrix_user_id.clone();
+                        println!(
+                            "[Feishu->Matrix Sender] mapping found: msg_id={} feishu_sender={} canonical_feishu_sender={} matrix_sender={}",
+                            message.id, message.sender, canonical_feishu_user_id, matrix_sender_mxid
+                        );
+                        info!(
+                            trace_id = %trace_id,
+                            feishu_user_id = %message.sender,
+                            canonical_feishu_user_id = %canonical_feishu_user_id,
+                            matrix_user_id = %matrix_sender_mxid,
+                            "Resolved mapped Matrix sender for Feishu message"
+                        );
+                    }
+                    Ok(None) => warn!(
+                        trace_id = %trace_id,
+                        feishu_user_id = %message.sender,
+                        canonical_feishu_user_id = %canonical_feishu_user_id,
+                        "Feishu user sync completed but no user mapping found; falling back to bridge bot sender"
+                    ),
+                    Err(err) => warn!(
+                        trace_id = %trace_id,
+                        feishu_user_id = %message.sender,
+                        canonical_feishu_user_id = %canonical_feishu_user_id,
+                        error = %err,
+                        "Failed to load synced Feishu user mapping; falling back to bridge bot sender"
+                    ),
+                }
+            }
+            Ok(None) => warn!(
+                trace_id = %trace_id,
+                feishu_user_id = %message.sender,
+                "Feishu message sender is empty; falling back to bridge bot sender"
+            ),
+            Err(err) => warn!(
+                trace_id = %trace_id,
+                feishu_user_id = %message.sender,
+                error = %err,
+                "Failed to sync Feishu user mapping metadata; falling back to bridge bot sender"
+            ),
+        }
+        if matrix_sender_mxid == bridge_bot_mxid {
+            println!(
+                "[Feishu->Matrix Sender] fallback to bot: msg_id={} feishu_sender={} canonical_feishu_sender={} bot_sender={}",
+                message.id, message.sender, canonical_sender_feishu_id, bridge_bot_mxid
+            );
+        }
+
+        let room_mapping = self
+            .stores
+            .room_store()
+            .get_room_by_feishu_id(&message.room_id)
+            .await?;
+
+        let chat_profile = if room_mapping
+            .as_ref()
+            .and_then(|mapping| mapping.feishu_chat_name.as_deref())
+            .is_none()
+        {
+            self.feishu_service.get_chat(&message.room_id).await.ok()
+        } else {
+            None
+        };
+
+        let portal = if let Some(mut mapping) = room_mapping {
+            info!(
+                trace_id = %trace_id,
+                feishu_chat_id = %message.room_id,
+                matrix_room_id = %mapping.matrix_room_id,
+                chat_type = %mapping.feishu_chat_type,
+                "Found room mapping for Feishu message"
+            );
+            if mapping.feishu_chat_name.is_none() {
+                if let Some(name) = chat_profile.as_ref().and_then(|chat| chat.name.clone()) {
+                    mapping.feishu_chat_name = Some(name.clone());
+                    mapping.updated_at = Utc::now();
+                    if let Err(err) = self.stores.room_store().update_room_mapping(&mapping).await {
+                        warn!(
+                            chat_id = %message.room_id,
+                            error = %err,
+                            "Failed to backfill Feishu chat name into room mapping"
+                        );
+                    }
+                }
+            }
+
+            match self.load_portal_by_feishu_room(&message.room_id).await? {
+                Some(portal) if portal.mxid == mapping.matrix_room_id => portal,
+                _ => {
+                    let mut portal = BridgePortal::new(
+                        message.room_id.clone(),
+                        mapping.matrix_room_id.clone(),
+                        mapping
+                            .feishu_chat_name
+                            .or_else(|| chat_profile.as_ref().and_then(|chat| chat.name.clone()))
+                            .unwrap_or_else(|| message.room_id.clone()),
+                        bridge_bot_mxid.clone(),
+                    );
+                    if let Some(chat) = &chat_profile {
+                        portal.topic = chat.description.clone();
+                        portal.room_type = room_type_from_chat_type(chat.chat_type.as_deref());
+                    }
+                    self.save_portal(&portal).await?
+                }
+            }
+        } else {
+            warn!(
+                trace_id = %trace_id,
+                feishu_chat_id = %message.room_id,
+                "No room mapping found; creating Matrix portal room for Feishu chat"
+            );
+            let sender_mxid = if matrix_sender_mxid == bridge_bot_mxid {
+                None
+            } else {
+                Some(matrix_sender_mxid.as_str())
+            };
+            let Some(portal) = self
+                .get_or_create_portal_by_feishu_room(&message.room_id, sender_mxid)
+                .await?
+            else {
+                return Ok(());
+            };
+            portal
+        };
+
+        info!(
+            trace_id = %trace_id,
+            feishu_message_id = %message.id,
+            matrix_room_id = %portal.mxid,
+            "Resolved Matrix destination for Feishu message"
+        );
+
+        let mut intent = self.get_or_create_intent(&matrix_sender_mxid).await;
+        if matrix_sender_mxid == bridge_bot_mxid {
+            intent.ensure_registered().await?;
+        } else if let Err(err) = self
+            .ensure_matrix_sender_joined_room(
+                &intent,
+                &matrix_sender_mxid,
+                &bridge_bot_mxid,
+                &portal.mxid,
+            )
+            .await
+        {
+            warn!(
+                trace_id = %trace_id,
+                matrix_room_id = %portal.mxid,
+                feishu_user_id = %canonical_sender_feishu_id,
+                matrix_user_id = %matrix_sender_mxid,
+                error = %err,
+                "Failed to prepare mapped Matrix sender; falling back to bridge bot sender"
+            );
+            println!(
+                "[Feishu->Matrix Sender] sender preparation failed, fallback to bot: msg_id={} matrix_sender={} room_id={} err={}",
+                message.id, matrix_sender_mxid, portal.mxid, err
+            );
+            matrix_sender_mxid = bridge_bot_mxid.clone();
+            intent = self.get_or_create_intent(&bridge_bot_mxid).await;
+            intent.ensure_registered().await?;
+        }
+        println!(
+            "[Feishu->Matrix Sender] effective sender selected: msg_id={} matrix_sender={} room_id={}",
+            message.id, matrix_sender_mxid, portal.mxid
+        );
+
+        let mut reply_to_matrix_event_id = None;
+        if let Some(parent_id) = message.parent_id.as_deref() {
+            if let Some(parent_mapping) = self
+                .stores
+                .message_store()
+                .get_message_by_feishu_id(parent_id)
+                .await?
+            {
+                reply_to_matrix_event_id = Some(parent_mapping.matrix_event_id);
+            } else {
+                debug!(
+                    "No Matrix mapping found for Feishu parent message {}",
+                    parent_id
+                );
+            }
+        }
+
+        let mut thread_root_matrix_event_id = None;
+        let in_thread = message.thread_id.is_some()
+            || portal
+                .bridge_info
+                .channel
+                .get("chat_mode")
+                .and_then(Value::as_str)
+                .is_some_and(|mode| mode.eq_ignore_ascii_case("thread"));
+        if in_thread
+            && let Some(root_id) = message.root_id.as_deref()
+            && root_id != message.id
+        {
+            if let Some(root_mapping) = self
+                .stores
+                .message_store()
+                .get_message_by_feishu_id(root_id)
+                .await?
+            {
+                thread_root_matrix_event_id = Some(root_mapping.matrix_event_id);
+            } else {
+                debug!(
+                    "No Matrix mapping found for Feishu thread root message {}",
+                    root_id
+                );
+            }
+        }
+        let relates_to = matrix_relates_to(
+            thread_root_matrix_event_id.as_deref(),
+            reply_to_matrix_event_id.as_deref(),
+        );
+
+        let formatted = self.render_feishu_message_for_matrix(&message).await;
+        let body = formatted
+            .as_ref()
+            .map(|formatted| formatted.body.as_str())
+            .unwrap_or(message.content.as_str());
+
+        let mut primary_matrix_event_id = None;
+        if !body.trim().is_empty() {
+            info!(
+                trace_id = %trace_id,
+                feishu_message_id = %message.id,
+                matrix_room_id = %portal.mxid,
+                reply_to = ?reply_to_matrix_event_id,
+                thread_root = ?thread_root_matrix_event_id,
+                "Sending Feishu text content to Matrix"
+            );
+            let event_id = self
+                .send_matrix_text_message(
+                    &matrix_sender_mxid,
+                    &portal.mxid,
+                    body,
+                    formatted.as_ref(),
+                    relates_to.as_ref(),
+                )
+                .await?;
+            println!(
+                "[Feishu->Matrix Sender] text delivered: msg_id={} matrix_event_id={} matrix_sender={} room_id={}",
+                message.id, event_id, matrix_sender_mxid, portal.mxid
+            );
+            info!(
+                trace_id = %trace_id,
+                feishu_message_id = %message.id,
+                matrix_event_id = %event_id,
+                "Sent Feishu text content to Matrix"
+            );
+            primary_matrix_event_id = Some(event_id);
+        }
+
+        if !message.attachments.is_empty() {
+            info!(
+                trace_id = %trace_id,
+                feishu_message_id = %message.id,
+                attachment_count = message.attachments.len(),
+                "Forwarding Feishu attachments to Matrix"
+            );
+        }
+        let attachment_event_ids = self
+            .forward_feishu_attachments_to_matrix(
+                &intent,
+                &matrix_sender_mxid,
+                &portal.mxid,
+                &message,
+                match primary_matrix_event_id.as_deref() {
+                    Some(event_id) => next_thread_relation(relates_to.as_ref(), event_id),
+                    None => relates_to.clone(),
+                },
+            )
+            .await?;
+        if !attachment_event_ids.is_empty() {
+            info!(
+                trace_id = %trace_id,
+                feishu_message_id = %message.id,
+                matrix_event_count = attachment_event_ids.len(),
+                "Sent Feishu attachments to Matrix"
+            );
+        }
+        if primary_matrix_event_id.is_none() {
+            primary_matrix_event_id = attachment_event_ids.first().cloned();
+        }
+
+        if let Some(matrix_event_id) = primary_matrix_event_id {
+            let link = MessageMapping::new(
+                matrix_event_id,
+                message.id,
+                portal.mxid.clone(),
+                matrix_sender_mxid.clone(),
+                canonical_sender_feishu_id.clone(),
+            )
+            .with_threading(
+                message.thread_id.clone(),
+                message.root_id.clone(),
+                message.parent_id.clone(),
+            );
+            if let Err(err) = self
+                .stores
+                .message_store()
+                .create_message_mapping(&link)
+                .await
+            {
+                global_metrics().record_trace_event("feishu_to_matrix", "mapping_store_failed");
+                warn!(
+                    trace_id = %trace_id,
+                    matrix_event_id = %link.matrix_event_id,
+                    feishu_message_id = %link.feishu_message_id,
+                    chat_id = %message.room_id,
+                    error = %err,
+                    "Failed to persist Feishu->Matrix message mapping"
+                );
+            }
+
+            global_metrics().record_trace_event("feishu_to_matrix", "success");
+            info!(
+                trace_id = %trace_id,
+                matrix_event_id = %link.matrix_event_id,
+                feishu_message_id = %link.feishu_message_id,
+                chat_id = %message.room_id,
+                "Bridged Feishu message to Matrix"
+            );
+            return Ok(());
+        }
+
+        global_metrics().record_trace_event("feishu_to_matrix", "attachments_only_or_empty");
+
+        Ok(())
+    }
+
+    pub async fn handle_feishu_message_recalled(
+        &self,
+        feishu_chat_id: &str,
+        feishu_message_id: &str,
+    ) -> anyhow::Result<()> {
+        info!(
+            chat_id = %feishu_chat_id,
+            feishu_message_id = %feishu_message_id,
+            "Handling Feishu recalled event"
+        );
+
+        let mapping = self
+            .stores
+            .message_store()
+            .get_message_by_feishu_id(feishu_message_id)
+            .await?;
+
+        let Some(mapping) = mapping else {
+            debug!(
+                feishu_message_id = %feishu_message_id,
+                chat_id = %feishu_chat_id,
+                "No message mapping found for recalled Feishu message"
+            );
+            return Ok(());
+        };
+
+        if let Err(err) = self
+            .bot_intent
+            .redact_event(
+                &mapping.room_id,
+                &mapping.matrix_event_id,
+                Some("Message recalled in Feishu"),
+            )
+            .await
+        {
+            warn!(
+                "Failed to redact Matrix event {} for Feishu message {}: {}",
+                mapping.matrix_event_id, feishu_message_id, err
+            );
+        }
+
+        if let Err(err) = self
+            .stores
+            .message_store()
+            .delete_message_mapping(mapping.id)
+            .await
+        {
+            warn!(
+                "Failed to delete recalled message mapping {} (Feishu {}): {}",
+                mapping.id, feishu_message_id, err
+            );
+        }
+
+        Ok(())
+    }
+
+    pub async fn handle_feishu_message_updated(
+        &self,
+        message: BridgeMessage,
+    ) -> anyhow::Result<()> {
+        info!(
+            chat_id = %message.room_id,
+            feishu_message_id = %message.id,
+            "Handling Feishu message updated event"
+        );
+
+        let Some(mapping) = self
+            .message_store()
+            .get_message_by_feishu_id(&message.id)
+            .await?
+        else {
+            debug!(
+                feishu_message_id = %message.id,
+                chat_id = %message.room_id,
+                "No message mapping found for updated Feishu message"
+            );
+            return Ok(());
+        };
+
+        // Messages that originated on Matrix are already up to date there.
+        if mapping.sender_feishu_id == "matrix" {
+            return Ok(());
+        }
+        if message.content.trim().is_empty() {
+            debug!(
+                feishu_message_id = %message.id,
+                "Updated Feishu message has no text content to bridge"
+            );
+            return Ok(());
+        }
+
+        let formatted = self.render_feishu_message_for_matrix(&message).await;
+        let mut new_content = json!({
+            "msgtype": "m.text",
+            "body": formatted
+                .as_ref()
+                .map(|formatted| formatted.body.as_str())
+                .unwrap_or(message.content.as_str())
+        });
+        if let Some(formatted) = &formatted {
+            new_content["format"] = json!("org.matrix.custom.html");
+            new_content["formatted_body"] = json!(formatted.formatted_body);
+            if let Some(mentions) = formatted.m_mentions() {
+                new_content["m.mentions"] = mentions;
+            }
+        }
+        let content = json!({
+            "msgtype": "m.text",
+            "body": format!("* {}", new_content["body"].as_str().unwrap_or_default()),
+            "m.new_content": new_content,
+            "m.relates_to": {
+                "rel_type": "m.replace",
+                "event_id": mapping.matrix_event_id
+            }
+        });
+        self.send_matrix_room_message_as_user(&mapping.sender_mxid, &mapping.room_id, content)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn handle_feishu_message_read(
+        &self,
+        feishu_reader_id: &str,
+        feishu_message_ids: &[String],
+    ) -> anyhow::Result<()> {
+        if !self.config.bridge.bridge_feishu_read_receipts {
+            return Ok(());
+        }
+
+        // A receipt on the newest bridged message implies the earlier ones.
+        let mut latest: Option<MessageMapping> = None;
+        for feishu_message_id in feishu_message_ids {
+            if let Some(mapping) = self
+                .message_store()
+                .get_message_by_feishu_id(feishu_message_id)
+                .await?
+                && latest
+                    .as_ref()
+                    .is_none_or(|current| mapping.created_at > current.created_at)
+            {
+                latest = Some(mapping);
+            }
+        }
+        let Some(target) = latest else {
+            debug!(
+                feishu_reader_id = %feishu_reader_id,
+                "No message mapping found for Feishu read receipt"
+            );
+            return Ok(());
+        };
+
+        let Some(canonical_reader_id) = self.sync_feishu_user_mapping(feishu_reader_id).await?
+        else {
+            return Ok(());
+        };
+        let Some(reader) = self
+            .user_store()
+            .get_user_by_feishu_id(&canonical_reader_id)
+            .await?
+        else {
+            return Ok(());
+        };
+        if reader.matrix_user_id == target.sender_mxid {
+            return Ok(());
+        }
+
+        {
+            let mut pending = self.pending_read_receipts.write().await;
+            // While a flush is scheduled, newer receipts queue behind it so a reader's
+            // marker never moves backwards.
+            if let Some(readers) = pending.get_mut(&target.room_id) {
+                keep_latest_read_receipt(readers, &reader.matrix_user_id, target);
+                return Ok(());
+            }
+            if !self.read_receipt_limiter.allow(&target.room_id) {
+                debug!(
+                    matrix_room_id = %target.room_id,
+                    feishu_reader_id = %feishu_reader_id,
+                    "Deferring Feishu read receipt until the room rate limit window reopens"
+                );
+                let room_id = target.room_id.clone();
+                let mut readers = HashMap::new();
+                keep_latest_read_receipt(&mut readers, &reader.matrix_user_id, target);
+                pending.insert(room_id.clone(), readers);
+                self.spawn_read_receipt_flush(room_id);
+                return Ok(());
+            }
+        }
+
+        self.deliver_read_receipt(&reader.matrix_user_id, &target)
+            .await
+    }
+
+    fn spawn_read_receipt_flush(&self, matrix_room_id: String) {
+        let bridge = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(bridge.read_receipt_limiter.retry_after(&matrix_room_id)).await;
+                let due = {
+                    let mut pending = bridge.pending_read_receipts.write().await;
+                    let Some(readers) = pending.get_mut(&matrix_room_id) else {
+                        return;
+                    };
+                    let reader_ids = readers.keys().cloned().collect::<Vec<_>>();
+                    let mut due = Vec::new();
+                    for reader_id in reader_ids {
+                        if !bridge.read_receipt_limiter.allow(&matrix_room_id) {
+                            break;
+                        }
+                        if let Some(target) = readers.remove(&reader_id) {
+                            due.push((reader_id, target));
+                        }
+                    }
+                    due
+                };
+                for (reader_id, target) in due {
+                    if let Err(err) = bridge.deliver_read_receipt(&reader_id, &target).await {
+                        warn!(
+                            matrix_room_id = %matrix_room_id,
+                            user_id = %reader_id,
+                            error = %err,
+                            "Failed to send deferred Feishu read receipt"
+                        );
+                    }
+                }
+                let mut pending = bridge.pending_read_receipts.write().await;
+                if pending
+                    .get(&matrix_room_id)
+                    .is_none_or(|readers| readers.is_empty())
+                {
+                    pending.remove(&matrix_room_id);
+                    return;
+                }
+            }
+        });
+    }
+
+    async fn deliver_read_receipt(
+        &self,
+        reader_mxid: &str,
+        target: &MessageMapping,
+    ) -> anyhow::Result<()> {
+        let bridge_bot_mxid = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
+        );
+        let intent = self.get_or_create_intent(reader_mxid).await;
+        self.ensure_matrix_sender_joined_room(
+            &intent,
+            reader_mxid,
+            &bridge_bot_mxid,
+            &target.room_id,
+        )
+        .await?;
+        self.send_matrix_read_receipt_as(reader_mxid, &target.room_id, &target.matrix_event_id)
+            .await
+    }
+
+    pub async fn resolve_feishu_chat_for_message(
+        &self,
+        feishu_message_id: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let Some(mapping) = self
+            .message_store()
+            .get_message_by_feishu_id(feishu_message_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+        Ok(self
+            .room_store()
+            .get_room_by_matrix_id(&mapping.room_id)
+            .await?
+            .map(|room| room.feishu_chat_id))
+    }
+
+    pub async fn handle_feishu_reaction_created(
+        &self,
+        feishu_message_id: &str,
+        emoji_type: &str,
+        feishu_user_id: &str,
+    ) -> anyhow::Result<()> {
+        if !self.config.bridge.bridge_feishu_reactions {
+            return Ok(());
+        }
+
+        let reaction_key = feishu_reaction_key(feishu_message_id, feishu_user_id, emoji_type);
+        if self
+            .reaction_store()
+            .get_reaction_by_feishu_id(&reaction_key)
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        let Some(target) = self
+            .message_store()
+            .get_message_by_feishu_id(feishu_message_id)
+            .await?
+        else {
+            debug!(
+                feishu_message_id = %feishu_message_id,
+                emoji_type = %emoji_type,
+                "No message mapping found for Feishu reaction"
+            );
+            return Ok(());
+        };
+
+        let Some(canonical_user_id) = self.sync_feishu_user_mapping(feishu_user_id).await? else {
+            return Ok(());
+        };
+        let Some(user_mapping) = self
+            .user_store()
+            .get_user_by_feishu_id(&canonical_user_id)
+            .await?
+        else {
+            debug!(
+                feishu_user_id = %feishu_user_id,
+                "No Matrix puppet found for Feishu reaction sender"
+            );
+            return Ok(());
+        };
+
+        let bridge_bot_mxid = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
+        );
+        let intent = self
+            .get_or_create_intent(&user_mapping.matrix_user_id)
+            .await;
+        self.ensure_matrix_sender_joined_room(
+            &intent,
+            &user_mapping.matrix_user_id,
+            &bridge_bot_mxid,
+            &target.room_id,
+        )
+        .await?;
+
+        let key = formatter::unicode_for_feishu_emoji_type(emoji_type).unwrap_or(emoji_type);
+        let event_id = self
+            .send_matrix_room_event_as_user(
+                &user_mapping.matrix_user_id,
+                &target.room_id,
+                "m.reaction",
+                json!({
+                    "m.relates_to": {
+                        "rel_type": "m.annotation",
+                        "event_id": target.matrix_event_id,
+                        "key": key
+                    }
+                }),
+            )
+            .await?;
+
+        let mapping = ReactionMapping::new(
+            event_id,
+            feishu_message_id.to_string(),
+            reaction_key,
+            target.room_id.clone(),
+            user_mapping.matrix_user_id.clone(),
+            key.to_string(),
+        )
+        .with_origin(ReactionMapping::ORIGIN_FEISHU);
+        if let Err(err) = self
+            .reaction_store()
+            .create_reaction_mapping(&mapping)
+            .await
+        {
+            warn!(
+                matrix_event_id = %mapping.matrix_event_id,
+                feishu_message_id = %feishu_message_id,
+                error = %err,
+                "Failed to persist Feishu->Matrix reaction mapping"
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn handle_feishu_reaction_deleted(
+        &self,
+        feishu_message_id: &str,
+        emoji_type: &str,
+        feishu_user_id: &str,
+    ) -> anyhow::Result<()> {
+        if !self.config.bridge.bridge_feishu_reactions {
+            return Ok(());
+        }
+
+        let reaction_key = feishu_reaction_key(feishu_message_id, feishu_user_id, emoji_type);
+        let Some(mapping) = self
+            .reaction_store()
+            .get_reaction_by_feishu_id(&reaction_key)
+            .await?
+        else {
+            debug!(
+                feishu_message_id = %feishu_message_id,
+                emoji_type = %emoji_type,
+                "No reaction mapping found for removed Feishu reaction"
+            );
+            return Ok(());
+        };
+
+        self.redact_matrix_event_as(
+            &mapping.sender_mxid,
+            &mapping.room_id,
+            &mapping.matrix_event_id,
+            "Reaction removed in Feishu",
+        )
+        .await?;
+        if let Err(err) = self
+            .reaction_store()
+            .delete_reaction_mapping(mapping.id)
+            .await
+        {
+            warn!(
+                matrix_event_id = %mapping.matrix_event_id,
+                feishu_message_id = %feishu_message_id,
+                error = %err,
+                "Failed to delete reaction mapping after Feishu removal"
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn handle_feishu_chat_member_added(
+        &self,
+        feishu_chat_id: &str,
+        user_ids: &[String],
+    ) -> anyhow::Result<()> {
+        info!(
+            "Handling Feishu member added event: chat={} users={}",
+            feishu_chat_id,
+            user_ids.join(",")
+        );
+
+        let Some(mapping) = self
+            .stores
+            .room_store()
+            .get_room_by_feishu_id(feishu_chat_id)
+            .await?
+        else {
+            debug!(
+                "No room mapping found for member added event in chat {}",
+                feishu_chat_id
+            );
+            return Ok(());
+        };
+
+        if !user_ids.is_empty() {
+            for user_id in user_ids {
+                let result = if self.config.bridge.bridge_feishu_join {
+                    self.join_feishu_user_puppet(user_id, &mapping.matrix_room_id)
+                        .await
+                        .map(|_| ())
+                } else {
+                    self.sync_feishu_user_mapping(user_id).await.map(|_| ())
+                };
+                if let Err(err) = result {
+                    warn!(
+                        feishu_user_id = %user_id,
+                        chat_id = %feishu_chat_id,
+                        error = %err,
+                        "Failed to sync Feishu user on member-added event"
+                    );
+                }
+            }
+            let labels = self.resolve_feishu_user_labels(user_ids).await;
+            let notice = format!("Feishu members joined: {}", labels.join(", "));
+            if let Err(err) = self
+                .bot_intent
+                .send_notice(&mapping.matrix_room_id, &notice)
+                .await
+            {
+                warn!(
+                    "Failed to send Matrix join notice for Feishu chat {}: {}",
+                    feishu_chat_id, err
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn handle_feishu_chat_member_deleted(
+        &self,
+        feishu_chat_id: &str,
+        user_ids: &[String],
+    ) -> anyhow::Result<()> {
+        info!(
+            "Handling Feishu member deleted event: chat={} users={}",
+            feishu_chat_id,
+            user_ids.join(",")
+        );
+
+        let Some(mapping) = self
+            .stores
+            .room_store()
+            .get_room_by_feishu_id(feishu_chat_id)
+            .await?
+        else {
+            debug!(
+                "No room mapping found for member deleted event in chat {}",
+                feishu_chat_id
+            );
+            return Ok(());
+        };
+
+        if !user_ids.is_empty() {
+            for user_id in user_ids {
+                let result = if self.config.bridge.bridge_feishu_leave {
+                    self.remove_feishu_user_puppet(user_id, &mapping.matrix_room_id)
+                        .await
+                        .map(|_| ())
+                } else {
+                    self.sync_feishu_user_mapping(user_id).await.map(|_| ())
+                };
+                if let Err(err) = result {
+                    warn!(
+                        feishu_user_id = %user_id,
+                        chat_id = %feishu_chat_id,
+                        error = %err,
+                        "Failed to sync Feishu user on member-deleted event"
+                    );
+                }
+            }
+            let labels = self.resolve_feishu_user_labels(user_ids).await;
+            let notice = format!("Feishu members left: {}", labels.join(", "));
+            if let Err(err) = self
+                .bot_intent
+                .send_notice(&mapping.matrix_room_id, &notice)
+                .await
+            {
+                warn!(
+                    "Failed to send Matrix leave notice for Feishu chat {}: {}",
+                    feishu_chat_id, err
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn resolve_feishu_user_labels(&self, user_ids: &[String]) -> Vec<String> {
+        let mut labels = Vec::with_capacity(user_ids.len());
+        for user_id in user_ids {
+            match self.feishu_service.get_user(user_id).await {
+                Ok(user) => {
+                    if user.name.trim().is_empty() {
+                        labels.push(user_id.clone());
+                    } else {
+                        labels.push(format!("{}({})", user.name, user_id));
+                    }
+                }
+                Err(_) => labels.push(user_id.clone()),
+            }
+        }
+        labels
+    }
+
+    pub async fn handle_feishu_chat_updated(
+        &self,
+        feishu_chat_id: &str,
+        chat_name: Option<String>,
+        description: Option<String>,
+        chat_mode: Option<String>,
+        chat_type: Option<String>,
+    ) -> anyhow::Result<()> {
+        info!(
+            "Handling Feishu chat updated event: chat={} name={:?} description={:?} mode={:?} type={:?}",
+            feishu_chat_id, chat_name, description, chat_mode, chat_type
+        );
+
+        let Some(mut mapping) = self
+            .stores
+            .room_store()
+            .get_room_by_feishu_id(feishu_chat_id)
+            .await?
+        else {
+            debug!(
+                "No room mapping found for chat updated event in chat {}",
+                feishu_chat_id
+            );
+            return Ok(());
+        };
+
+        let normalized_name = chat_name.and_then(|value| {
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                None
+            } else {
+                Some(trimmed.to_string())
+            }
+        });
+        let normalized_mode = chat_mode.and_then(|value| {
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                None
+            } else {
+                Some(trimmed.to_string())
+            }
+        });
+        let normalized_type = chat_type.and_then(|value| {
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                None
+            } else {
+                Some(trimmed.to_string())
+            }
+        });
+
+        let mut changed = false;
+        if let Some(name) = &normalized_name {
+            if mapping.feishu_chat_name.as_deref() != Some(name.as_str()) {
+                mapping.feishu_chat_name = Some(name.clone());
+                changed = true;
+            }
+        }
+
+        if let Some(mode) = &normalized_mode {
+            if mapping.feishu_chat_type != *mode {
+                mapping.feishu_chat_type = mode.clone();
+                changed = true;
+            }
+        } else if let Some(chat_type) = &normalized_type {
+            if mapping.feishu_chat_type != *chat_type {
+                mapping.feishu_chat_type = chat_type.clone();
+                changed = true;
+            }
+        }
+
+        if changed {
+            mapping.updated_at = Utc::now();
+            self.stores
+                .room_store()
+                .update_room_mapping(&mapping)
+                .await?;
+        }
+
+        if let Some(mut portal) = self.load_portal_by_feishu_room(feishu_chat_id).await? {
+            if let Some(name) = normalized_name
+                && portal.name != name
+            {
+                match self
+                    .send_matrix_state_event(
+                        &portal.mxid,
+                        "m.room.name",
+                        "",
+                        json!({ "name": name }),
+                    )
+                    .await
+                {
+                    Ok(_) => portal.name = name,
+                    Err(err) => warn!(
+                        matrix_room_id = %portal.mxid,
+                        error = %err,
+                        "Failed to update Matrix room name"
+                    ),
+                }
+            }
+            if let Some(topic) = description.map(|value| value.trim().to_string())
+                && portal.topic.as_deref().unwrap_or_default() != topic
+            {
+                match self
+                    .send_matrix_state_event(
+                        &portal.mxid,
+                        "m.room.topic",
+                        "",
+                        json!({ "topic": topic }),
+                    )
+                    .await
+                {
+                    Ok(_) => portal.topic = Some(topic).filter(|value| !value.is_empty()),
+                    Err(err) => warn!(
+                        matrix_room_id = %portal.mxid,
+                        error = %err,
+                        "Failed to update Matrix room topic"
+                    ),
+                }
+            }
+            if let Some(mode) = &normalized_mode {
+                portal
+                    .bridge_info
+                    .channel
+                    .insert("chat_mode".to_string(), Value::String(mode.clone()));
+                portal.room_type = room_type_from_chat_type(normalized_type.as_deref());
+            }
+            if let Some(kind) = normalized_type {
+                portal
+                    .bridge_info
+                    .channel
+                    .insert("chat_type".to_string(), Value::String(kind.clone()));
+                portal.room_type = room_type_from_chat_type(Some(&kind));
+            }
+            self.refresh_portal_avatar(&mut portal).await;
+            self.send_portal_bridge_info(&mut portal).await;
+            portal.last_event = Some("im.chat.updated_v1".to_string());
+            self.save_portal(&portal).await?;
+        }
+
+        if let Err(err) = self
+            .sync_portal_power_levels(feishu_chat_id, &mapping.matrix_room_id)
+            .await
+        {
+            warn!(
+                chat_id = %feishu_chat_id,
+                matrix_room_id = %mapping.matrix_room_id,
+                error = %err,
+                "Failed to sync Feishu owner/managers to Matrix power levels"
+            );
+        }
+
+        if let Some(mode) = &normalized_mode {
+            let notice = if mode.eq_ignore_ascii_case("thread") {
+                "Feishu chat mode changed to thread; bridge reply strategy is now thread mode."
+            } else {
+                "Feishu chat mode changed; bridge reply strategy switched to non-thread mode."
+            };
+            if let Err(err) = self
+                .bot_intent
+                .send_notice(&mapping.matrix_room_id, notice)
+                .await
+            {
+                warn!(
+                    "Failed to send chat mode update notice for chat {}: {}",
+                    feishu_chat_id, err
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn handle_feishu_chat_disbanded(&self, feishu_chat_id: &str) -> anyhow::Result<()> {
+        info!(
+            "Handling Feishu chat disbanded event: chat={}",
+            feishu_chat_id
+        );
+
+        let mapping = self
+            .stores
+            .room_store()
+            .get_room_by_feishu_id(feishu_chat_id)
+            .await?;
+
+        let Some(mapping) = mapping else {
+            debug!(
+                "No room mapping found for disbanded chat {}; cleaning portal record only",
+                feishu_chat_id
+            );
+            self.delete_portal_by_feishu_room(feishu_chat_id).await?;
+            return Ok(());
+        };
+
+        let historical_mappings = self
+            .stores
+            .message_store()
+            .get_messages_by_room(&mapping.matrix_room_id, Some(2000))
+            .await?;
+        for message in historical_mappings {
+            if let Err(err) = self
+                .stores
+                .message_store()
+                .delete_message_mapping(message.id)
+                .await
+            {
+                warn!(
+                    "Failed to delete message mapping {} while disbanding chat {}: {}",
+                    message.id, feishu_chat_id, err
+                );
+            }
+        }
+
+        self.stores
+            .room_store()
+            .delete_room_mapping(mapping.id)
+            .await?;
+
+        if let Some(portal) = self.load_portal_by_mxid(&mapping.matrix_room_id).await? {
+            self.remove_portal_from_spaces(&portal).await;
+        }
+        self.delete_portal_by_feishu_room(feishu_chat_id).await?;
+
+        if let Err(err) = self
+            .bot_intent
+            .send_notice(
+                &mapping.matrix_room_id,
+                "Feishu chat has been disbanded; bridge mapping was removed automatically.",
+            )
+            .await
+        {
+            warn!(
+                "Failed to send disband notice to Matrix room {}: {}",
+                mapping.matrix_room_id, err
+            );
+        }
+
+        if let Err(err) = self.apply_disband_policy(&mapping.matrix_room_id).await {
+            warn!(
+                matrix_room_id = %mapping.matrix_room_id,
+                policy = %self.config.bridge.disband_policy,
+                error = %err,
+                "Failed to apply disband policy to Matrix room"
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn apply_disband_policy(&self, matrix_room_id: &str) -> anyhow::Result<()> {
+        match self.config.bridge.disband_policy.as_str() {
+            "tombstone" => {
+                let replacement_room = self.create_disband_archive_room(matrix_room_id).await?;
+                self.lock_matrix_room(matrix_room_id).await?;
+                self.send_matrix_state_event(
+                    matrix_room_id,
+                    "m.room.tombstone",
+                    "",
+                    json!({
+                        "body": "The Feishu chat bridged to this room has been disbanded.",
+                        "replacement_room": replacement_room
+                    }),
+                )
+                .await?;
+            }
+            "kick" => {
+                for member in self
+                    .appservice
+                    .client
+                    .get_joined_room_members(matrix_room_id)
+                    .await?
+                {
+                    if self.is_bridge_puppet_mxid(&member)
+                        && let Err(err) = self.leave_matrix_room_as(&member, matrix_room_id).await
+                    {
+                        warn!(
+                            matrix_room_id = %matrix_room_id,
+                            user_id = %member,
+                            error = %err,
+                            "Failed to remove puppet from disbanded portal"
+                        );
+                    }
+                }
+                let bot_mxid = format!(
+                    "@{}:{}",
+                    self.config.bridge.bot_username, self.config.bridge.domain
+                );
+                self.leave_matrix_room_as(&bot_mxid, matrix_room_id).await?;
+            }
+            _ => self.lock_matrix_room(matrix_room_id).await?,
+        }
+        info!(
+            matrix_room_id = %matrix_room_id,
+            policy = %self.config.bridge.disband_policy,
+            "Applied disband policy to Matrix room"
+        );
+        Ok(())
+    }
+
+    // Successor for the tombstone: an unbridged room holding a notice, with the portal's
+    // human members invited so they can follow the tombstone link.
+    async fn create_disband_archive_room(&self, matrix_room_id: &str) -> anyhow::Result<String> {
+        let bot_mxid = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
+        );
+        let name = self
+            .get_matrix_room_state(matrix_room_id, "m.room.name")
+            .await?
+            .and_then(|content| {
+                content
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .map(str::to_owned)
+            })
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| "Feishu chat".to_string());
+        let invitees: Vec<String> = self
+            .appservice
+            .client
+            .get_joined_room_members(matrix_room_id)
+            .await?
+            .into_iter()
+            .filter(|member| *member != bot_mxid && !self.is_bridge_puppet_mxid(member))
+            .collect();
+        let replacement_room = self
+            .create_matrix_room_as_user(
+                &bot_mxid,
+                json!({
+                    "name": format!("{} (archived)", name),
+                    "topic": "The Feishu chat bridged to the previous room has been disbanded.",
+                    "preset": "private_chat",
+                    "invite": invitees,
+                    "creation_content": {
+                        "predecessor": { "room_id": matrix_room_id }
+                    }
+                }),
+            )
+            .await?;
+        if let Err(err) = self
+            .bot_intent
+            .send_notice(
+                &replacement_room,
+                "The Feishu chat bridged to the previous room has been disbanded. This room is not bridged.",
+            )
+            .await
+        {
+            warn!(
+                matrix_room_id = %replacement_room,
+                error = %err,
+                "Failed to send notice to disband archive room"
+            );
+        }
+        Ok(replacement_room)
+    }
+
+    // Makes the room read-only for everyone below the bridge bot. Feishu owners sit at
+    // FEISHU_OWNER_POWER_LEVEL so this covers them too.
+    async fn lock_matrix_room(&self, matrix_room_id: &str) -> anyhow::Result<()> {
+        let mut power_levels = self
+            .get_matrix_room_state(matrix_room_id, "m.room.power_levels")
+            .await?
+            .unwrap_or_else(|| json!({}));
+        for key in ["events_default", "state_default", "invite", "redact"] {
+            power_levels[key] = json!(100);
+        }
+        if let Some(events) = power_levels
+            .get_mut("events")
+            .and_then(Value::as_object_mut)
+        {
+            for level in events.values_mut() {
+                if level.as_i64().is_some_and(|value| value < 100) {
+                    *level = json!(100);
+                }
+            }
+        }
+        self.send_matrix_state_event(matrix_room_id, "m.room.power_levels", "", power_levels)
+            .await?;
+        Ok(())
+    }
+
+    async fn send_matrix_read_receipt_as(
+        &self,
+        matrix_user_id: &str,
+        matrix_room_id: &str,
+        matrix_event_id: &str,
+    ) -> anyhow::Result<()> {
+        let endpoint = format!(
+            "/_matrix/client/v3/rooms/{}/receipt/m.read/{}?user_id={}",
+            urlencoding::encode(matrix_room_id),
+            urlencoding::encode(matrix_event_id),
+            urlencoding::encode(matrix_user_id)
+        );
+        let response = self
+            .appservice
+            .client
+            .raw_json(Method::POST, &endpoint, Some(json!({})))
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to send read receipt for {} in {} as {}",
+                    matrix_event_id, matrix_room_id, matrix_user_id
+                )
+            })?;
+        if response.get("errcode").is_some() {
+            anyhow::bail!(
+                "Matrix read receipt failed for {} in {}: {}",
+                matrix_event_id,
+                matrix_room_id,
+                response
+            );
+        }
+        Ok(())
+    }
+
+    async fn redact_matrix_event_as(
+        &self,
+        matrix_user_id: &str,
+        matrix_room_id: &str,
+        matrix_event_id: &str,
+        reason: &str,
+    ) -> anyhow::Result<()> {
+        let endpoint = format!(
+            "/_matrix/client/v3/rooms/{}/redact/{}/{}?user_id={}",
+            urlencoding::encode(matrix_room_id),
+            urlencoding::encode(matrix_event_id),
+            Uuid::new_v4(),
+            urlencoding::encode(matrix_user_id)
+        );
+        let response = self
+            .appservice
+            .client
+            .raw_json(Method::PUT, &endpoint, Some(json!({ "reason": reason })))
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to redact {} in {} as {}",
+                    matrix_event_id, matrix_room_id, matrix_user_id
+                )
+            })?;
+        if response.get("errcode").is_some() {
+            anyhow::bail!(
+                "Matrix redaction failed for {} in {}: {}",
+                matrix_event_id,
+                matrix_room_id,
+                response
+            );
+        }
+        Ok(())
+    }
+
+    async fn leave_matrix_room_as(
+        &self,
+        matrix_user_id: &str,
+        matrix_room_id: &str,
+    ) -> anyhow::Result<()> {
+        let endpoint = format!(
+            "/_matrix/client/v3/rooms/{}/leave?user_id={}",
+            urlencoding::encode(matrix_room_id),
+            urlencoding::encode(matrix_user_id)
+        );
+        let response = self
+            .appservice
+            .client
+            .raw_json(Method::POST, &endpoint, Some(json!({})))
+            .await
+            .with_context(|| format!("failed to leave {} as {}", matrix_room_id, matrix_user_id))?;
+        if response.get("errcode").is_some() {
+            anyhow::bail!(
+                "Matrix leave failed for {} in {}: {}",
+                matrix_user_id,
+                matrix_room_id,
+                response
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn handle_matrix_message(&self, room_id: &str, event: Value) -> anyhow::Result<()> {
+        info!("Handling Matrix message in room {}", room_id);
+        let sender = event
+            .get("sender")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        if self.is_bridge_bot_sender(sender) {
+            debug!(
+                room_id = %room_id,
+                sender = %sender,
+                "Ignoring Matrix message sent by bridge bot to prevent self-loop"
+            );
+            return Ok(());
+        }
+
+        let body = event
+            .get("content")
+            .and_then(|c| c.get("body"))
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+
+        if self.command_handler.is_command(body) {
+            debug!("Matrix command detected: {}", body);
+            let room_mapping: Option<crate::database::RoomMapping> = self
+                .stores
+                .room_store()
+                .get_room_by_matrix_id(room_id)
+                .await?;
+
+            let outcome = self
+                .command_handler
+                .handle(body, room_mapping.is_some(), |level| match level {
+                    "full" => self.config.bridge.has_full_access(sender),
+                    "admin" => self.config.bridge.is_admin(sender),
+                    _ => true,
+                });
+            self.handle_command_outcome(outcome, room_id, sender)
+                .await?;
+            return Ok(());
+        }
+
+        let message = self.matrix_event_to_bridge_message(room_id, event)?;
+        let portal = self.get_or_create_portal_by_matrix_room(room_id).await?;
+
+        if portal.feishu_room_id.starts_with("mx_") {
+            warn!(
+                "Skipping Matrix->Feishu forward for room {} because no Feishu room mapping exists yet",
+                room_id
+            );
+            return Ok(());
+        }
+
+        let feishu_content = formatter::format_matrix_to_feishu(message)?;
+        self.feishu_service
+            .send_text_message(&portal.feishu_room_id, &feishu_content)
+            .await?;
+
+        Ok(())
+    }
+
+    fn is_bridge_bot_sender(&self, sender: &str) -> bool {
+        let configured_bot = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
+        );
+        if sender.eq_ignore_ascii_case(&configured_bot) {
+            return true;
+        }
+
+        let sender_localpart = self.config.registration.sender_localpart.trim();
+        if sender_localpart.is_empty() {
+            return false;
+        }
+
+        let registration_bot = format!("@{}:{}", sender_localpart, self.config.bridge.domain);
+        sender.eq_ignore_ascii_case(&registration_bot)
+    }
+
+    async fn handle_command_outcome(
+        &self,
+        outcome: MatrixCommandOutcome,
+        room_id: &str,
+        sender: &str,
+    ) -> anyhow::Result<()> {
+        match outcome {
+            MatrixCommandOutcome::Ignored => {}
+            MatrixCommandOutcome::Reply(reply) => {
+                self.bot_intent.send_text(room_id, &reply).await?;
+            }
+            MatrixCommandOutcome::BridgeRequested { feishu_chat_id } => {
+                let mapping = crate::database::RoomMapping::new(
+                    room_id.to_string(),
+                    feishu_chat_id.clone(),
+                    Some(format!("Feishu {}", feishu_chat_id)),
+                );
+                self.stores
+                    .room_store()
+                    .create_room_mapping(&mapping)
+                    .await?;
+                info!("Created bridge: {} <-> {}", room_id, feishu_chat_id);
+                self.spawn_portal_member_sync(&feishu_chat_id, room_id);
+                if let Err(err) = self.add_bridged_room_to_spaces(room_id, sender).await {
+                    warn!(
+                        matrix_room_id = %room_id,
+                        error = %err,
+                        "Failed to add bridged room to spaces"
+                    );
+                }
+                self.bot_intent
+                    .send_text(
+                        room_id,
+                        &format!("Bridged to Feishu chat: {}", feishu_chat_id),
+                    )
+                    .await?;
+            }
+            MatrixCommandOutcome::UnbridgeRequested => {
+                if let Some(mapping) = self
+                    .stores
+                    .room_store()
+                    .get_room_by_matrix_id(room_id)
+                    .await?
+                {
+                    self.stores
+                        .room_store()
+                        .delete_room_mapping(mapping.id)
+                        .await?;
+                    self.delete_portal_by_mxid(room_id).await?;
+                    info!("Removed bridge for room {}", room_id);
+                    self.bot_intent.send_text(room_id, "Bridge removed").await?;
+                }
+            }
+            MatrixCommandOutcome::CreateRequested => {
+                let reply = match self.create_feishu_chat_for_room(room_id, sender).await {
+                    Ok(mapping) => format!("Created Feishu chat: {}", mapping.feishu_chat_id),
+                    Err(err) => format!("Failed to create Feishu chat: {}", err),
+                };
+                self.bot_intent.send_text(room_id, &reply).await?;
+            }
+            MatrixCommandOutcome::PmRequested { target } => {
+                let reply = match self.start_direct_chat(sender, &target).await {
+                    Ok(reply) => reply,
+                    Err(err) => format!("Failed to start Feishu direct chat: {}", err),
+                };
+                self.bot_intent.send_text(room_id, &reply).await?;
+            }
+            MatrixCommandOutcome::SyncRequested => {
+                let reply = match self.sync_room_members(room_id).await {
+                    Ok(reply) => reply,
+                    Err(err) => format!("Failed to sync Feishu members: {}", err),
+                };
+                self.bot_intent.send_text(room_id, &reply).await?;
+            }
+            MatrixCommandOutcome::LinkRequested {
+                matrix_user_id,
+                feishu_user_id,
+            } => {
+                let reply = match self
+                    .link_matrix_user(&matrix_user_id, Some(&feishu_user_id))
+                    .await
+                {
+                    Ok(reply) => reply,
+                    Err(err) => format!("Failed to link Feishu user: {}", err),
+                };
+                self.bot_intent.send_text(room_id, &reply).await?;
+            }
+            MatrixCommandOutcome::UnlinkRequested { matrix_user_id } => {
+                let reply = match self.link_matrix_user(&matrix_user_id, None).await {
+                    Ok(reply) => reply,
+                    Err(err) => format!("Failed to unlink Feishu user: {}", err),
+                };
+                self.bot_intent.send_text(room_id, &reply).await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn bridge_matrix_reaction(
+        &self,
+        matrix_event_id: &str,
+        matrix_room_id: &str,
+        sender: &str,
+        target_event_id: &str,
+        key: &str,
+    ) -> anyhow::Result<()> {
+        if self
+            .reaction_store()
+            .get_reaction_by_matrix_id(matrix_event_id)
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        let Some(target) = self
+            .message_store()
+            .get_message_by_matrix_id(target_event_id)
+            .await?
+        else {
+            debug!(
+                matrix_event_id = %matrix_event_id,
+                target_event_id = %target_event_id,
+                "Matrix reaction target has no Feishu mapping"
+            );
+            return Ok(());
+        };
+
+        let Some(emoji_type) = formatter::feishu_emoji_type_for_unicode(key) else {
+            debug!(
+                matrix_event_id = %matrix_event_id,
+                key = %key,
+                "Matrix reaction key has no Feishu emoji type"
+            );
+            return Ok(());
+        };
+
+        // Every Matrix reaction is sent as the app, so Matrix users sharing an
+        // emoji share one Feishu reaction; reuse it instead of adding another.
+        let shared_reaction_id = self
+            .reaction_store()
+            .get_reactions_by_feishu_message(&target.feishu_message_id)
+            .await?
+            .into_iter()
+            .find(|existing| {
+                !existing.is_from_feishu()
+                    && formatter::feishu_emoji_type_for_unicode(&existing.emoji) == Some(emoji_type)
+            })
+            .map(|existing| existing.feishu_reaction_id);
+        let feishu_reaction_id = match shared_reaction_id {
+            Some(reaction_id) => reaction_id,
+            None => {
+                self.feishu_service
+                    .add_message_reaction(&target.feishu_message_id, emoji_type)
+                    .await?
+                    .reaction_id
+            }
+        };
+        let mapping = ReactionMapping::new(
+            matrix_event_id.to_string(),
+            target.feishu_message_id.clone(),
+            feishu_reaction_id.clone(),
+            matrix_room_id.to_string(),
+            sender.to_string(),
+            key.to_string(),
+        );
+        if let Err(err) = self
+            .reaction_store()
+            .create_reaction_mapping(&mapping)
+            .await
+        {
+            warn!(
+                matrix_event_id = %matrix_event_id,
+                feishu_message_id = %target.feishu_message_id,
+                feishu_reaction_id = %feishu_reaction_id,
+                error = %err,
+                "Failed to persist Matrix->Feishu reaction mapping"
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn redact_bridged_reaction(&self, redacted_event_id: &str) -> anyhow::Result<bool> {
+        let Some(mapping) = self
+            .reaction_store()
+            .get_reaction_by_matrix_id(redacted_event_id)
+            .await?
+        else {
+            return Ok(false);
+        };
+        if mapping.is_from_feishu() {
+            // Puppet reactions mirror a Feishu user's reaction, which the app
+            // cannot remove on their behalf.
+            return Ok(false);
+        }
+
+        let still_shared = self
+            .reaction_store()
+            .get_reactions_by_feishu_message(&mapping.feishu_message_id)
+            .await?
+            .iter()
+            .any(|other| {
+                other.id != mapping.id
+                    && !other.is_from_feishu()
+                    && other.feishu_reaction_id == mapping.feishu_reaction_id
+            });
+        if !still_shared {
+            self.feishu_service
+                .delete_message_reaction(&mapping.feishu_message_id, &mapping.feishu_reaction_id)
+                .await?;
+        }
+        if let Err(err) = self
+            .reaction_store()
+            .delete_reaction_mapping(mapping.id)
+            .await
+        {
+            warn!(
+                matrix_event_id = %redacted_event_id,
+                feishu_reaction_id = %mapping.feishu_reaction_id,
+                error = %err,
+                "Failed to delete reaction mapping after redaction"
+            );
+        }
+        Ok(true)
+    }
+
+    pub async fn sync_room_members(&self, matrix_room_id: &str) -> anyhow::Result<String> {
+        let Some(mapping) = self
+            .stores
+            .room_store()
+            .get_room_by_matrix_id(matrix_room_id)
+            .await?
+        else {
+            return Ok("This room is not bridged to any Feishu chat.".to_string());
+        };
+        let joined = self
+            .sync_portal_members(&mapping.feishu_chat_id, matrix_room_id)
+            .await?;
+        Ok(format!("Synced {} Feishu members into this room.", joined))
+    }
+
+    pub async fn start_direct_chat(
+        &self,
+        matrix_user_id: &str,
+        target: &str,
+    ) -> anyhow::Result<String> {
+        let Some(user) = self.resolve_feishu_user(target).await? else {
+            return Ok(format!(
+                "Could not find a Feishu user matching `{}`.",
+                target
+            ));
+        };
+        self.sync_feishu_user_mapping(&user.feishu_user_id).await?;
+        let display_name = user
+            .feishu_username
+            .clone()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| user.feishu_user_id.clone());
+        let bot_mxid = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
+        );
+
+        if let Some(direct_chat) = self
+            .direct_chat_store()
+            .get_direct_chat_by_feishu_user(&user.feishu_user_id)
+            .await?
+        {
+            // Feishu keeps a single p2p chat between the bot and each user, so the DM
+            // cannot be shared without exposing its history to another Matrix user.
+            if direct_chat.owner_mxid != matrix_user_id {
+                return Ok(format!(
+                    "{} already has a direct chat with another Matrix user; Feishu allows only one chat between the bridge and each user.",
+                    display_name
+                ));
+            }
+            let matrix_room_id = direct_chat.matrix_room_id;
+            self.ensure_matrix_sender_joined_room(
+                &self.bot_intent,
+                &bot_mxid,
+                &bot_mxid,
+                &matrix_room_id,
+            )
+            .await?;
+            self.invite_matrix_user(&matrix_room_id, matrix_user_id)
+                .await?;
+            if let Err(err) = self
+                .add_portal_to_user_space(matrix_user_id, &matrix_room_id)
+                .await
+            {
+                warn!(
+                    matrix_room_id = %matrix_room_id,
+                    user_id = %matrix_user_id,
+                    error = %err,
+                    "Failed to add direct chat to user space"
+                );
+            }
+            if let Err(err) = self
+                .add_matrix_direct_room(matrix_user_id, &user.matrix_user_id, &matrix_room_id)
+                .await
+            {
+                warn!(
+                    matrix_room_id = %matrix_room_id,
+                    user_id = %matrix_user_id,
+                    error = %err,
+                    "Failed to update m.direct for Matrix user"
+                );
+            }
+            return Ok(format!(
+                "Reusing direct chat with {}: {}",
+                display_name, matrix_room_id
+            ));
+        }
+
+        // Feishu only reveals the p2p chat ID once the bot has messaged the user.
+        let sent = self
+            .feishu_service
+            .send_message(
+                feishu_user_id_type(&user.feishu_user_id),
+                &user.feishu_user_id,
+                "text",
+                json!({ "text": format!("{} started a chat with you from Matrix.", matrix_user_id) }),
+                Some(Uuid::new_v4().to_string()),
+            )
+            .await
+            .with_context(|| format!("failed to message Feishu user {}", user.feishu_user_id))?;
+        let Some(feishu_chat_id) = sent.chat_id.filter(|value| !value.is_empty()) else {
+            anyhow::bail!(
+                "Feishu did not report the chat ID for the direct chat with {}",
+                user.feishu_user_id
+            );
+        };
+        if let Some(existing) = self
+            .stores
+            .room_store()
+            .get_room_by_feishu_id(&feishu_chat_id)
+            .await?
+        {
+            return Ok(format!(
+                "The Feishu chat with {} is already bridged to {}.",
+                display_name, existing.matrix_room_id
+            ));
+        }
+
+        let mxid = self
+            .create_direct_portal_room(
+                &feishu_chat_id,
+                &user.matrix_user_id,
+                matrix_user_id,
+                &bot_mxid,
+            )
+            .await?;
+
+        let mut mapping = RoomMapping::new(
+            mxid.clone(),
+            feishu_chat_id.clone(),
+            Some(display_name.clone()),
+        );
+        mapping.feishu_chat_type = "p2p".to_string();
+        self.stores
+            .room_store()
+            .create_room_mapping(&mapping)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to persist direct chat mapping {} <-> {}",
+                    mxid, feishu_chat_id
+                )
+            })?;
+        self.direct_chat_store()
+            .create_direct_chat(&DirectChat::new(
+                user.feishu_user_id.clone(),
+                feishu_chat_id.clone(),
+                mxid.clone(),
+                matrix_user_id.to_string(),
+            ))
+            .await?;
+
+        let mut portal = BridgePortal::new(
+            feishu_chat_id.clone(),
+            mxid.clone(),
+            display_name.clone(),
+            bot_mxid,
+        );
+        portal.room_type = RoomType::Direct;
+        portal.bridge_info.channel.insert(
+            "feishu_user_id".to_string(),
+            Value::String(user.feishu_user_id.clone()),
+        );
+        self.send_portal_bridge_info(&mut portal).await;
+        let portal = self.save_portal(&portal).await?;
+        self.add_portal_to_spaces(&portal, &[matrix_user_id.to_string()])
+            .await;
+
+        info!(
+            matrix_user_id = %matrix_user_id,
+            feishu_user_id = %user.feishu_user_id,
+            feishu_chat_id = %feishu_chat_id,
+            matrix_room_id = %mxid,
+            "Started Feishu direct chat from Matrix"
+        );
+        Ok(format!(
+            "Started direct chat with {}: {}",
+            display_name, mxid
+        ))
+    }
+
+    pub async fn create_feishu_chat_for_room(
+        &self,
+        matrix_room_id: &str,
+        requester: &str,
+    ) -> anyhow::Result<RoomMapping> {
+        if self
+            .stores
+            .room_store()
+            .get_room_by_matrix_id(matrix_room_id)
+            .await?
+            .is_some()
+        {
+            return Err(ProvisioningError::AlreadyExists.into());
+        }
+        let upstream = |err: anyhow::Error| ProvisioningError::Upstream(format!("{:#}", err));
+        let bot_mxid = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
+        );
+
+        let name = self
+            .get_matrix_room_state(matrix_room_id, "m.room.name")
+            .await
+            .map_err(upstream)?
+            .and_then(|content| {
+                content
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .map(str::trim)
+                    .map(ToOwned::to_owned)
+            })
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| format!("Matrix {}", matrix_room_id));
+        let topic = self
+            .get_matrix_room_state(matrix_room_id, "m.room.topic")
+            .await
+            .map_err(upstream)?
+            .and_then(|content| {
+                content
+                    .get("topic")
+                    .and_then(Value::as_str)
+                    .map(str::trim)
+                    .map(ToOwned::to_owned)
+            })
+            .filter(|value| !value.is_empty());
+        let avatar_key = match self
+            .get_matrix_room_state(matrix_room_id, "m.room.avatar")
+            .await
+            .map_err(upstream)?
+            .and_then(|content| {
+                content
+                    .get("url")
+                    .and_then(Value::as_str)
+                    .map(ToOwned::to_owned)
+            }) {
+            Some(mxc) => match self.export_matrix_avatar(&mxc).await {
+                Ok(image_key) => Some(image_key),
+                Err(err) => {
+                    warn!(
+                        matrix_room_id = %matrix_room_id,
+                        error = %err,
+                        "Failed to upload Matrix room avatar to Feishu; creating chat without avatar"
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let mut matrix_members = self
+            .appservice
+            .client
+            .get_joined_room_members(matrix_room_id)
+            .await
+            .map_err(upstream)?;
+        matrix_members.retain(|member| *member != bot_mxid);
+        if !matrix_members.iter().any(|member| member == requester) {
+            matrix_members.push(requester.to_string());
+        }
+        // Puppets map to their Feishu user; real Matrix users only join the chat
+        // when they have linked a Feishu account.
+        let mut feishu_user_ids = Vec::new();
+        for member in &matrix_members {
+            if let Some(feishu_user_id) = self.feishu_user_for_matrix_user(member).await?
+                && !feishu_user_ids.contains(&feishu_user_id)
+            {
+                feishu_user_ids.push(feishu_user_id);
+            }
+        }
+
+        let feishu_chat_id = self
+            .feishu_service
+            .create_chat(
+                &name,
+                topic.as_deref(),
+                avatar_key.as_deref(),
+                &feishu_user_ids,
+            )
+            .await
+            .map_err(upstream)?;
+        info!(
+            matrix_room_id = %matrix_room_id,
+            feishu_chat_id = %feishu_chat_id,
+            requester = %requester,
+            member_count = feishu_user_ids.len(),
+            "Created Feishu chat for Matrix room"
+        );
+
+        let mapping = match self
+            .stores
+            .room_store()
+            .create_room_mapping(&RoomMapping::new(
+                matrix_room_id.to_string(),
+                feishu_chat_id.clone(),
+                Some(name.clone()),
+            ))
+            .await
+        {
+            Ok(mapping) => mapping,
+            Err(err) => {
+                // Without a mapping nothing would ever bridge into the new chat, so
+                // remove it rather than leave an orphan behind.
+                let rollback = match self.feishu_service.delete_chat(&feishu_chat_id).await {
+                    Ok(()) => "the Feishu chat was removed again".to_string(),
+                    Err(delete_err) => format!(
+                        "removing the Feishu chat {} also failed: {:#}",
+                        feishu_chat_id, delete_err
+                    ),
+                };
+                error!(
+                    matrix_room_id = %matrix_room_id,
+                    feishu_chat_id = %feishu_chat_id,
+                    error = %err,
+                    rollback = %rollback,
+                    "Failed to persist room mapping for created Feishu chat"
+                );
+                return Err(ProvisioningError::Other(format!(
+                    "failed to persist room mapping for created chat {} <-> {}: {}; {}",
+                    matrix_room_id, feishu_chat_id, err, rollback
+                ))
+                .into());
+            }
+        };
+
+        let mut portal =
+            BridgePortal::new(feishu_chat_id, matrix_room_id.to_string(), name, bot_mxid);
+        portal.topic = topic;
+        self.send_portal_bridge_info(&mut portal).await;
+        let portal = self.save_portal(&portal).await?;
+        self.add_portal_to_spaces(&portal, &matrix_members).await;
+        Ok(mapping)
+    }
+
+    async fn get_matrix_room_state(
+        &self,
+        matrix_room_id: &str,
+        event_type: &str,
+    ) -> anyhow::Result<Option<Value>> {
+        self.get_matrix_room_state_key(matrix_room_id, event_type, "")
+            .await
+    }
+
+    async fn get_matrix_room_state_key(
+        &self,
+        matrix_room_id: &str,
+        event_type: &str,
+        state_key: &str,
+    ) -> anyhow::Result<Option<Value>> {
+        let bot_mxid = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
+        );
+        let endpoint = format!(
+            "/_matrix/client/v3/rooms/{}/state/{}/{}?user_id={}",
+            urlencoding::encode(matrix_room_id),
+            urlencoding::encode(event_type),
+            urlencoding::encode(state_key),
+            urlencoding::encode(&bot_mxid)
+        );
+        let response = self
+            .appservice
+            .client
+            .raw_json(Method::GET, &endpoint, None)
+            .await
+            .with_context(|| format!("failed to read {} in {}", event_type, matrix_room_id))?;
+        match response.get("errcode").and_then(Value::as_str) {
+            Some("M_NOT_FOUND") => Ok(None),
+            Some(_) => anyhow::bail!(
+                "Matrix state read failed for {} in {}: {}",
+                event_type,
+                matrix_room_id,
+                response
+            ),
+            None => Ok(Some(response)),
+        }
+    }
+
+    async fn export_matrix_avatar(&self, mxc_url: &str) -> anyhow::Result<String> {
+        let content = self
+            .appservice
+            .client
+            .download_content(mxc_url, true)
+            .await
+            .with_context(|| format!("failed to download Matrix avatar {}", mxc_url))?;
+        if self.config.bridge.max_media_size > 0
+            && content.data.len() > self.config.bridge.max_media_size
+        {
+            anyhow::bail!(
+                "Matrix avatar exceeds configured max_media_size: {} > {}",
+                content.data.len(),
+                self.config.bridge.max_media_size
+            );
+        }
+        let mime_type = resolve_attachment_mime_type(
+            "image",
+            content.content_type.as_deref().unwrap_or_default(),
+            &content.data,
+        );
+        self.feishu_service
+            .upload_avatar(content.data, &mime_type)
+            .await
+    }
+
+    async fn resolve_feishu_user(&self, target: &str) -> anyhow::Result<Option<UserMapping>> {
+        let target = target.trim();
+        if target.is_empty() {
+            return Ok(None);
+        }
+        let user_store = self.user_store();
+
+        let lookup_id = if is_matrix_user_id(target) {
+            // A bridge puppet, or a Matrix account linked to a Feishu user.
+            match self.feishu_user_for_matrix_user(target).await? {
+                Some(feishu_user_id) => Some(feishu_user_id),
+                None => return Ok(None),
+            }
+        } else if looks_like_email(target) {
+            self.feishu_service.find_user_id_by_email(target).await?
+        } else if target.contains(char::is_whitespace) {
+            None
+        } else {
+            Some(target.to_string())
+        };
+        if let Some(lookup_id) = lookup_id {
+            if let Some(mapping) = user_store.get_user_by_feishu_id(&lookup_id).await? {
+                return Ok(Some(mapping));
+            }
+            // Lookups must not create user mappings; callers that act on the result sync the
+            // puppet themselves.
+            match self.feishu_service.get_user(&lookup_id).await {
+                Ok(profile) => {
+                    if let Some(mapping) =
+                        user_store.get_user_by_feishu_id(&profile.user_id).await?
+                    {
+                        return Ok(Some(mapping));
+                    }
+                    let matrix_user_id = format!(
+                        "@{}:{}",
+                        self.config.format_username(&profile.user_id),
+                        self.config.bridge.domain
+                    );
+                    let mut mapping = UserMapping::new(
+                        matrix_user_id,
+                        profile.user_id.clone(),
+                        Some(profile.name.trim().to_string()),
+                    );
+                    mapping.feishu_avatar = profile
+                        .avatar
+                        .as_ref()
+                        .map(|avatar| avatar.avatar_240.trim().to_string())
+                        .filter(|value| !value.is_empty());
+                    return Ok(Some(mapping));
+                }
+                Err(err) => debug!(
+                    target = %target,
+                    error = %err,
+                    "Feishu user lookup by ID failed; falling back to display name"
+                ),
+            }
+        }
+
+        let mut offset = 0;
+        loop {
+            let page = user_store
+                .list_user_mappings(Some(USER_LOOKUP_PAGE_SIZE), Some(offset))
+                .await?;
+            if let Some(mapping) = page.iter().find(|mapping| {
+                mapping
+                    .feishu_username
+                    .as_deref()
+                    .is_some_and(|name| name.trim().eq_ignore_ascii_case(target))
+            }) {
+                return Ok(Some(mapping.clone()));
+            }
+            if (page.len() as i64) < USER_LOOKUP_PAGE_SIZE {
+                break;
+            }
+            offset += USER_LOOKUP_PAGE_SIZE;
+        }
+
+        // Users the bridge has not seen yet are only reachable through the contact directory.
+        let found = match self.search_feishu_contacts(target).await {
+            Ok(found) => found,
+            Err(err) => {
+                warn!(
+                    target = %target,
+                    error = %err,
+                    "Feishu contact search failed while resolving user"
+                );
+                return Ok(None);
+            }
+        };
+        let exact = found.iter().find(|mapping| {
+            mapping
+                .feishu_username
+                .as_deref()
+                .is_some_and(|name| name.trim().eq_ignore_ascii_case(target))
+        });
+        Ok(match (exact, found.as_slice()) {
+            (Some(mapping), _) => Some(mapping.clone()),
+            (None, [only]) => Some(only.clone()),
+            _ => None,
+        })
+    }
+
+    pub async fn thirdparty_users(
+        &self,
+        fields: &HashMap<String, String>,
+    ) -> anyhow::Result<Vec<Value>> {
+        let mut mappings = Vec::new();
+        if let Some(id) = thirdparty_field(fields, "id")
+            && let Some(mapping) = self.resolve_feishu_user(id).await?
+        {
+            mappings.push(mapping);
+        }
+        if mappings.is_empty()
+            && let Some(name) = thirdparty_field(fields, "name")
+        {
+            if looks_like_email(name) {
+                mappings.extend(self.resolve_feishu_user(name).await?);
+            } else {
+                mappings = match self.search_feishu_contacts(name).await {
+                    Ok(found) => found,
+                    Err(err) => {
                         warn!(
-                            chat_id = %message.room_id,
+                            name = %name,
                             error = %err,
-                            "Failed to backfill Feishu chat name into room mapping"
+                            "Feishu contact search failed; falling back to known users"
                         );
+                        self.search_user_mappings_by_name(name).await?
                     }
-                }
+                };
             }
+        }
+        Ok(mappings.iter().map(thirdparty_user_entry).collect())
+    }
 
-            BridgePortal::new(
-                message.room_id.clone(),
-                mapping.matrix_room_id.clone(),
-                mapping
-                    .feishu_chat_name
-                    .or_else(|| chat_profile.as_ref().and_then(|chat| chat.name.clone()))
-                    .unwrap_or_else(|| message.room_id.clone()),
-                format!(
-                    "@{}:{}",
-                    self.config.bridge.bot_username, self.config.bridge.domain
+    pub async fn thirdparty_users_for_matrix_id(
+        &self,
+        matrix_user_id: &str,
+    ) -> anyhow::Result<Vec<Value>> {
+        Ok(self
+            .user_store()
+            .get_user_by_matrix_id(matrix_user_id)
+            .await?
+            .iter()
+            .map(thirdparty_user_entry)
+            .collect())
+    }
+
+    pub async fn thirdparty_locations(
+        &self,
+        fields: &HashMap<String, String>,
+    ) -> anyhow::Result<Vec<Value>> {
+        let mut chats = Vec::new();
+        if let Some(id) = thirdparty_field(fields, "id") {
+            match self.feishu_service.get_chat(id).await {
+                Ok(chat) => chats.push(chat),
+                Err(err) => debug!(
+                    chat_id = %id,
+                    error = %err,
+                    "Feishu chat lookup for third-party location failed"
                 ),
-            )
-        } else {
-            warn!(
-                trace_id = %trace_id,
-                feishu_chat_id = %message.room_id,
-                "No room mapping found; creating fallback portal (message may not reach intended Matrix room)"
-            );
-            self.get_or_create_portal_by_feishu_room(&message.room_id)
-                .await?
-        };
+            }
+        }
+        if chats.is_empty()
+            && let Some(name) = thirdparty_field(fields, "name")
+        {
+            let needle = name.to_lowercase();
+            let mut page_token: Option<String> = None;
+            for _ in 0..CHAT_LOOKUP_MAX_PAGES {
+                let page = self
+                    .feishu_service
+                    .list_chats(page_token.as_deref())
+                    .await?;
+                chats.extend(page.items.into_iter().filter(|chat| {
+                    chat.name
+                        .as_deref()
+                        .is_some_and(|value| value.to_lowercase().contains(&needle))
+                }));
+                page_token = page.page_token.filter(|token| !token.is_empty());
+                if !page.has_more || page_token.is_none() || chats.len() >= THIRDPARTY_RESULT_LIMIT
+                {
+                    break;
+                }
+            }
+            chats.truncate(THIRDPARTY_RESULT_LIMIT);
+        }
 
-        info!(
-            trace_id = %trace_id,
-            feishu_message_id = %message.id,
-            matrix_room_id = %portal.mxid,
-            "Resolved Matrix destination for Feishu message"
-        );
+        Ok(chats
+            .iter()
+            .filter(|chat| !chat.chat_id.is_empty())
+            .map(|chat| self.thirdparty_location_entry(&chat.chat_id, chat.name.as_deref()))
+            .collect())
+    }
 
-        let mut intent = self.get_or_create_intent(&matrix_sender_mxid).await;
-        if matrix_sender_mxid == bridge_bot_mxid {
-            intent.ensure_registered().await?;
-        } else if let Err(err) = self
-            .ensure_matrix_sender_joined_room(
-                &intent,
-                &matrix_sender_mxid,
-                &bridge_bot_mxid,
-                &portal.mxid,
-            )
-            .await
+    pub async fn thirdparty_locations_for_alias(
+        &self,
+        room_alias: &str,
+    ) -> anyhow::Result<Vec<Value>> {
+        let Some(feishu_chat_id) = room_alias
+            .strip_prefix('#')
+            .and_then(|value| value.split(':').next())
+            .and_then(|localpart| localpart.strip_prefix("feishu_"))
+            .filter(|chat_id| !chat_id.is_empty())
+        else {
+            return Ok(Vec::new());
+        };
+
+        let name = match self
+            .stores
+            .room_store()
+            .get_room_by_feishu_id(feishu_chat_id)
+            .await?
         {
-            warn!(
-                trace_id = %trace_id,
-                matrix_room_id = %portal.mxid,
-                feishu_user_id = %canonical_sender_feishu_id,
-                matrix_user_id = %matrix_sender_mxid,
-                error = %err,
-                "Failed to prepare mapped Matrix sender; falling back to bridge bot sender"
-            );
-            println!(
-                "[Feishu->Matrix Sender] sender preparation failed, fallback to bot: msg_id={} matrix_sender={} room_id={} err={}",
-                message.id, matrix_sender_mxid, portal.mxid, err
-            );
-            matrix_sender_mxid = bridge_bot_mxid.clone();
-            intent = self.get_or_create_intent(&bridge_bot_mxid).await;
-            intent.ensure_registered().await?;
-        }
-        println!(
-            "[Feishu->Matrix Sender] effective sender selected: msg_id={} matrix_sender={} room_id={}",
-            message.id, matrix_sender_mxid, portal.mxid
-        );
+            Some(mapping) => mapping.feishu_chat_name,
+            None => match self.feishu_service.get_chat(feishu_chat_id).await {
+                Ok(chat) => chat.name,
+                Err(_) => return Ok(Vec::new()),
+            },
+        };
+        Ok(vec![self.thirdparty_location_entry(
+            feishu_chat_id,
+            name.as_deref(),
+        )])
+    }
 
-        let mut reply_to_matrix_event_id = None;
-        if let Some(parent_id) = message.parent_id.as_deref() {
-            if let Some(parent_mapping) = self
-                .stores
-                .message_store()
-                .get_message_by_feishu_id(parent_id)
-                .await?
-            {
-                reply_to_matrix_event_id = Some(parent_mapping.matrix_event_id);
-            } else {
-                debug!(
-                    "No Matrix mapping found for Feishu parent message {}",
-                    parent_id
-                );
+    fn thirdparty_location_entry(&self, feishu_chat_id: &str, name: Option<&str>) -> Value {
+        json!({
+            "alias": format!("#feishu_{}:{}", feishu_chat_id, self.config.bridge.domain),
+            "protocol": THIRDPARTY_PROTOCOL,
+            "fields": {
+                "id": feishu_chat_id,
+                "name": name.unwrap_or(feishu_chat_id)
+            }
+        })
+    }
+
+    // Searches the users in the app's contact scope by name. Results are not persisted;
+    // known users keep their stored mapping.
+    async fn search_feishu_contacts(&self, name: &str) -> anyhow::Result<Vec<UserMapping>> {
+        let needle = name.to_lowercase();
+        let user_store = self.user_store();
+        let mut matches = Vec::new();
+        let mut page_token: Option<String> = None;
+        for _ in 0..CHAT_LOOKUP_MAX_PAGES {
+            let page = self
+                .feishu_service
+                .list_contact_scope_users(page_token.as_deref())
+                .await?;
+            for user_ids in page.user_ids.chunks(CONTACT_BATCH_SIZE) {
+                let users = self.feishu_service.batch_get_users(user_ids).await?;
+                for user in users.into_iter().filter(|user| {
+                    user.name.to_lowercase().contains(&needle)
+                        || user
+                            .en_name
+                            .as_deref()
+                            .is_some_and(|value| value.to_lowercase().contains(&needle))
+                }) {
+                    let mapping = match user_store.get_user_by_feishu_id(&user.user_id).await? {
+                        Some(mapping) => mapping,
+                        None => {
+                            let mut mapping = UserMapping::new(
+                                format!(
+                                    "@{}:{}",
+                                    self.config.format_username(&user.user_id),
+                                    self.config.bridge.domain
+                                ),
+                                user.user_id.clone(),
+                                Some(user.name.trim().to_string()),
+                            );
+                            mapping.feishu_avatar = user
+                                .avatar
+                                .as_ref()
+                                .map(|avatar| avatar.avatar_240.trim().to_string())
+                                .filter(|value| !value.is_empty());
+                            mapping
+                        }
+                    };
+                    matches.push(mapping);
+                    if matches.len() >= THIRDPARTY_RESULT_LIMIT {
+                        return Ok(matches);
+                    }
+                }
+            }
+            page_token = page.page_token.filter(|token| !token.is_empty());
+            if !page.has_more || page_token.is_none() {
+                break;
             }
         }
+        Ok(matches)
+    }
 
-        let mut primary_matrix_event_id = None;
-        if !message.content.trim().is_empty() {
-            info!(
-                trace_id = %trace_id,
-                feishu_message_id = %message.id,
-                matrix_room_id = %portal.mxid,
-                reply_to = ?reply_to_matrix_event_id,
-                "Sending Feishu text content to Matrix"
-            );
-            let event_id = self
-                .send_matrix_text_message(
-                    &matrix_sender_mxid,
-                    &portal.mxid,
-                    &message.content,
-                    reply_to_matrix_event_id.as_deref(),
-                )
+    async fn search_user_mappings_by_name(&self, name: &str) -> anyhow::Result<Vec<UserMapping>> {
+        let needle = name.to_lowercase();
+        let user_store = self.user_store();
+        let mut matches = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = user_store
+                .list_user_mappings(Some(USER_LOOKUP_PAGE_SIZE), Some(offset))
                 .await?;
-            println!(
-                "[Feishu->Matrix Sender] text delivered: msg_id={} matrix_event_id={} matrix_sender={} room_id={}",
-                message.id, event_id, matrix_sender_mxid, portal.mxid
-            );
-            info!(
-                trace_id = %trace_id,
-                feishu_message_id = %message.id,
-                matrix_event_id = %event_id,
-                "Sent Feishu text content to Matrix"
+            matches.extend(
+                page.iter()
+                    .filter(|mapping| {
+                        mapping
+                            .feishu_username
+                            .as_deref()
+                            .is_some_and(|value| value.to_lowercase().contains(&needle))
+                    })
+                    .cloned(),
             );
-            primary_matrix_event_id = Some(event_id);
+            if matches.len() >= THIRDPARTY_RESULT_LIMIT
+                || (page.len() as i64) < USER_LOOKUP_PAGE_SIZE
+            {
+                break;
+            }
+            offset += USER_LOOKUP_PAGE_SIZE;
         }
+        matches.truncate(THIRDPARTY_RESULT_LIMIT);
+        Ok(matches)
+    }
 
-        if !message.attachments.is_empty() {
-            info!(
-                trace_id = %trace_id,
-                feishu_message_id = %message.id,
-                attachment_count = message.attachments.len(),
-                "Forwarding Feishu attachments to Matrix"
-            );
+    async fn get_or_create_portal_by_feishu_room(
+        &self,
+        feishu_room_id: &str,
+        sender_mxid: Option<&str>,
+    ) -> anyhow::Result<Option<BridgePortal>> {
+        self.wait_for_alias_portal_binding(feishu_room_id).await;
+        if let Some(portal) = self.load_portal_by_feishu_room(feishu_room_id).await? {
+            return Ok(Some(portal));
         }
-        let attachment_event_ids = self
-            .forward_feishu_attachments_to_matrix(
-                &intent,
-                &matrix_sender_mxid,
-                &portal.mxid,
-                &message,
-                if primary_matrix_event_id.is_none() {
-                    reply_to_matrix_event_id.as_deref()
-                } else {
-                    None
-                },
-            )
-            .await?;
-        if !attachment_event_ids.is_empty() {
-            info!(
-                trace_id = %trace_id,
-                feishu_message_id = %message.id,
-                matrix_event_count = attachment_event_ids.len(),
-                "Sent Feishu attachments to Matrix"
+
+        let bot_mxid = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
+        );
+
+        if let Some(mapping) = self
+            .stores
+            .room_store()
+            .get_room_by_feishu_id(feishu_room_id)
+            .await?
+        {
+            let portal = BridgePortal::new(
+                feishu_room_id.to_string(),
+                mapping.matrix_room_id.clone(),
+                mapping
+                    .feishu_chat_name
+                    .unwrap_or_else(|| format!("Feishu {}", feishu_room_id)),
+                bot_mxid,
             );
-        }
-        if primary_matrix_event_id.is_none() {
-            primary_matrix_event_id = attachment_event_ids.first().cloned();
+            return self.save_portal(&portal).await.map(Some);
         }
 
-        if let Some(matrix_event_id) = primary_matrix_event_id {
-            let link = MessageMapping::new(
-                matrix_event_id,
-                message.id,
-                portal.mxid.clone(),
-                matrix_sender_mxid.clone(),
-                canonical_sender_feishu_id.clone(),
-            )
-            .with_threading(
-                message.thread_id.clone(),
-                message.root_id.clone(),
-                message.parent_id.clone(),
-            );
-            if let Err(err) = self
-                .stores
-                .message_store()
-                .create_message_mapping(&link)
-                .await
-            {
-                global_metrics().record_trace_event("feishu_to_matrix", "mapping_store_failed");
+        let chat_profile = match self.feishu_service.get_chat(feishu_room_id).await {
+            Ok(chat) => Some(chat),
+            Err(err) => {
                 warn!(
-                    trace_id = %trace_id,
-                    matrix_event_id = %link.matrix_event_id,
-                    feishu_message_id = %link.feishu_message_id,
-                    chat_id = %message.room_id,
+                    chat_id = %feishu_room_id,
                     error = %err,
-                    "Failed to persist Feishu->Matrix message mapping"
+                    "Failed to load Feishu chat profile; creating portal with fallback metadata"
+                );
+                None
+            }
+        };
+        let is_direct = chat_profile.as_ref().is_some_and(|chat| {
+            is_feishu_p2p_chat(chat.chat_mode.as_deref(), chat.chat_type.as_deref())
+        });
+        let direct_feishu_user_id = match sender_mxid.filter(|_| is_direct) {
+            Some(puppet_mxid) => self
+                .user_store()
+                .get_user_by_matrix_id(puppet_mxid)
+                .await?
+                .map(|user| user.feishu_user_id),
+            None => None,
+        };
+        // A p2p chat is between the Feishu user and the bot. When that user is
+        // double-puppeted, the DM belongs to their linked Matrix account.
+        let direct_owner_mxid = match &direct_feishu_user_id {
+            Some(feishu_user_id) => {
+                self.user_store()
+                    .get_linked_matrix_user(feishu_user_id)
+                    .await?
+            }
+            None => None,
+        };
+        // Without a linked account nobody on Matrix would read the DM, so it is not
+        // bridged until a Matrix user opens it with `!feishu pm`.
+        if is_direct && sender_mxid.is_some() && direct_owner_mxid.is_none() {
+            info!(
+                chat_id = %feishu_room_id,
+                sender = ?sender_mxid,
+                "Not creating a portal for a p2p chat with an unlinked Feishu user"
+            );
+            return Ok(None);
+        }
+        let name = chat_profile
+            .as_ref()
+            .and_then(|chat| chat.name.as_deref())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(|| format!("Feishu {}", feishu_room_id));
+        let topic = chat_profile
+            .as_ref()
+            .and_then(|chat| chat.description.as_deref())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToOwned::to_owned);
+        let feishu_avatar = chat_profile
+            .as_ref()
+            .and_then(|chat| chat.avatar.as_deref())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToOwned::to_owned);
+        let avatar_url = match feishu_avatar.as_deref() {
+            Some(feishu_avatar) => match self.import_feishu_avatar(feishu_avatar).await {
+                Ok(mxc) => Some(mxc),
+                Err(err) => {
+                    warn!(
+                        chat_id = %feishu_room_id,
+                        error = %err,
+                        "Failed to import Feishu chat avatar; creating portal without avatar"
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let (mxid, invitees) = match &direct_owner_mxid {
+            Some(owner_mxid) => {
+                let mxid = self
+                    .create_direct_portal_room(feishu_room_id, &bot_mxid, owner_mxid, &bot_mxid)
+                    .await?;
+                (mxid, vec![owner_mxid.clone()])
+            }
+            None => {
+                // The Matrix accounts linked to the chat's Feishu members are the ones who
+                // belong in the room; puppets follow through the member sync.
+                let mut invitees = match self.linked_matrix_users_in_chat(feishu_room_id).await {
+                    Ok(linked) => linked,
+                    Err(err) => {
+                        warn!(
+                            chat_id = %feishu_room_id,
+                            error = %err,
+                            "Failed to list Feishu chat members; creating portal without invites"
+                        );
+                        Vec::new()
+                    }
+                };
+                if let Some(sender_mxid) = sender_mxid
+                    && sender_mxid != bot_mxid
+                    && !invitees.iter().any(|id| id == sender_mxid)
+                {
+                    invitees.push(sender_mxid.to_string());
+                }
+
+                let mut payload = json!({
+                    "name": name,
+                    "preset": "private_chat",
+                    "visibility": "private",
+                    "invite": invitees,
+                    "creation_content": {
+                        "m.federate": true
+                    }
+                });
+                if let Some(topic) = &topic {
+                    payload["topic"] = Value::String(topic.clone());
+                }
+                if let Some(avatar_url) = &avatar_url {
+                    payload["initial_state"] = json!([{
+                        "type": "m.room.avatar",
+                        "state_key": "",
+                        "content": { "url": avatar_url }
+                    }]);
+                }
+
+                let mxid = self.create_matrix_room_as_user(&bot_mxid, payload).await?;
+                info!(
+                    feishu_chat_id = %feishu_room_id,
+                    matrix_room_id = %mxid,
+                    invite_count = invitees.len(),
+                    "Created Matrix portal room for Feishu chat"
                 );
+                (mxid, invitees)
             }
+        };
 
-            global_metrics().record_trace_event("feishu_to_matrix", "success");
-            info!(
-                trace_id = %trace_id,
-                matrix_event_id = %link.matrix_event_id,
-                feishu_message_id = %link.feishu_message_id,
-                chat_id = %message.room_id,
-                "Bridged Feishu message to Matrix"
-            );
-            return Ok(());
+        let mut mapping =
+            RoomMapping::new(mxid.clone(), feishu_room_id.to_string(), Some(name.clone()));
+        if let Some(chat_type) = chat_profile.as_ref().and_then(|chat| {
+            chat.chat_mode
+                .clone()
+                .or_else(|| chat.chat_type.clone())
+                .filter(|value| !value.trim().is_empty())
+        }) {
+            mapping.feishu_chat_type = chat_type;
+        }
+        self.stores
+            .room_store()
+            .create_room_mapping(&mapping)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to persist room mapping for new portal {} <-> {}",
+                    mxid, feishu_room_id
+                )
+            })?;
+        if let (Some(feishu_user_id), Some(owner_mxid)) =
+            (&direct_feishu_user_id, direct_owner_mxid)
+        {
+            self.direct_chat_store()
+                .create_direct_chat(&DirectChat::new(
+                    feishu_user_id.clone(),
+                    feishu_room_id.to_string(),
+                    mxid.clone(),
+                    owner_mxid,
+                ))
+                .await?;
         }
 
-        global_metrics().record_trace_event("feishu_to_matrix", "attachments_only_or_empty");
-
-        Ok(())
+        let mut portal = BridgePortal::new(feishu_room_id.to_string(), mxid, name, bot_mxid);
+        portal.topic = topic;
+        if let Some(feishu_avatar) = feishu_avatar.filter(|_| avatar_url.is_some()) {
+            portal
+                .bridge_info
+                .channel
+                .insert("feishu_avatar".to_string(), Value::String(feishu_avatar));
+        }
+        portal.avatar_url = avatar_url;
+        portal.room_type = if is_direct {
+            RoomType::Direct
+        } else {
+            RoomType::Group
+        };
+        if let Some(feishu_user_id) = direct_feishu_user_id {
+            portal
+                .bridge_info
+                .channel
+                .insert("feishu_user_id".to_string(), Value::String(feishu_user_id));
+        }
+        if let Some(chat) = &chat_profile {
+            if let Some(mode) = &chat.chat_mode {
+                portal
+                    .bridge_info
+                    .channel
+                    .insert("chat_mode".to_string(), Value::String(mode.clone()));
+            }
+            if let Some(kind) = &chat.chat_type {
+                portal
+                    .bridge_info
+                    .channel
+                    .insert("chat_type".to_string(), Value::String(kind.clone()));
+            }
+            if let Some(tenant_key) = &chat.tenant_key {
+                portal
+                    .bridge_info
+                    .channel
+                    .insert("tenant_key".to_string(), Value::String(tenant_key.clone()));
+            }
+        }
+        self.send_portal_bridge_info(&mut portal).await;
+        let portal = self.save_portal(&portal).await?;
+        self.add_portal_to_spaces(&portal, &invitees).await;
+        if !is_direct {
+            self.spawn_portal_member_sync(feishu_room_id, &portal.mxid);
+        }
+        Ok(Some(portal))
     }
 
-    pub async fn handle_feishu_message_recalled(
-        &self,
-        feishu_chat_id: &str,
-        feishu_message_id: &str,
-    ) -> anyhow::Result<()> {
-        info!(
-            chat_id = %feishu_chat_id,
-            feishu_message_id = %feishu_message_id,
-            "Handling Feishu recalled event"
-        );
+    /// Creates and binds the portal for a `#feishu_<chat_id>` alias. Returns `None` when
+    /// the alias does not name a Feishu group the bridge bot belongs to.
+    pub async fn create_alias_portal(&self, room_alias: &str) -> anyhow::Result<Option<String>> {
+        let Some(feishu_chat_id) = room_alias
+            .strip_prefix('#')
+            .and_then(|value| value.split(':').next())
+            .and_then(|localpart| localpart.strip_prefix("feishu_"))
+            .filter(|chat_id| !chat_id.is_empty())
+        else {
+            return Ok(None);
+        };
 
-        let mapping = self
+        if self
             .stores
-            .message_store()
-            .get_message_by_feishu_id(feishu_message_id)
-            .await?;
-
-        let Some(mapping) = mapping else {
+            .room_store()
+            .get_room_by_feishu_id(feishu_chat_id)
+            .await?
+            .is_some()
+        {
             debug!(
-                feishu_message_id = %feishu_message_id,
+                room_alias = %room_alias,
                 chat_id = %feishu_chat_id,
-                "No message mapping found for recalled Feishu message"
+                "Feishu chat is already bridged; not creating a room for alias"
             );
-            return Ok(());
-        };
-
-        if let Err(err) = self
-            .bot_intent
-            .redact_event(
-                &mapping.room_id,
-                &mapping.matrix_event_id,
-                Some("Message recalled in Feishu"),
-            )
-            .await
-        {
-            warn!(
-                "Failed to redact Matrix event {} for Feishu message {}: {}",
-                mapping.matrix_event_id, feishu_message_id, err
+            return Ok(None);
+        }
+        if !self.feishu_service.is_bot_in_chat(feishu_chat_id).await? {
+            info!(
+                room_alias = %room_alias,
+                chat_id = %feishu_chat_id,
+                "Bridge bot is not a member of Feishu chat; rejecting alias query"
             );
+            return Ok(None);
+        }
+        let chat = self.feishu_service.get_chat(feishu_chat_id).await?;
+        if is_feishu_p2p_chat(chat.chat_mode.as_deref(), chat.chat_type.as_deref()) {
+            return Ok(None);
         }
 
-        if let Err(err) = self
-            .stores
-            .message_store()
-            .delete_message_mapping(mapping.id)
+        if !self
+            .alias_portal_reservations
+            .write()
             .await
+            .insert(feishu_chat_id.to_string())
         {
-            warn!(
-                "Failed to delete recalled message mapping {} (Feishu {}): {}",
-                mapping.id, feishu_message_id, err
+            debug!(
+                room_alias = %room_alias,
+                chat_id = %feishu_chat_id,
+                "Alias portal for Feishu chat is already being created"
             );
+            return Ok(None);
         }
-
-        Ok(())
+        let result = self
+            .create_and_bind_alias_portal(room_alias, feishu_chat_id, &chat)
+            .await;
+        self.release_alias_portal_reservation(feishu_chat_id).await;
+        result.map(Some)
     }
 
-    pub async fn handle_feishu_chat_member_added(
+    async fn create_and_bind_alias_portal(
         &self,
+        room_alias: &str,
         feishu_chat_id: &str,
-        user_ids: &[String],
-    ) -> anyhow::Result<()> {
+        chat: &FeishuChatProfile,
+    ) -> anyhow::Result<String> {
+        let bot_mxid = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
+        );
+        let name = chat
+            .name
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(|| format!("Feishu {}", feishu_chat_id));
+        let topic = chat
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToOwned::to_owned);
+
+        // The alias query does not say who asked, so the room is joinable by anyone who
+        // knows the alias; linked Matrix accounts of the chat's members are also invited.
+        let invitees = self.linked_matrix_users_in_chat(feishu_chat_id).await?;
+        let alias_localpart = room_alias
+            .strip_prefix('#')
+            .and_then(|value| value.split(':').next())
+            .unwrap_or_default();
+        let mut payload = json!({
+            "name": name,
+            "preset": "public_chat",
+            "visibility": "private",
+            "room_alias_name": alias_localpart,
+            "invite": invitees,
+            "creation_content": {
+                "m.federate": true
+            }
+        });
+        if let Some(topic) = &topic {
+            payload["topic"] = Value::String(topic.clone());
+        }
+        let mxid = self.create_matrix_room_as_user(&bot_mxid, payload).await?;
+
+        let mut mapping =
+            RoomMapping::new(mxid.clone(), feishu_chat_id.to_string(), Some(name.clone()));
+        if let Some(chat_type) = chat
+            .chat_mode
+            .clone()
+            .or_else(|| chat.chat_type.clone())
+            .filter(|value| !value.trim().is_empty())
+        {
+            mapping.feishu_chat_type = chat_type;
+        }
+        self.stores
+            .room_store()
+            .create_room_mapping(&mapping)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to persist room mapping for alias portal {} <-> {}",
+                    mxid, feishu_chat_id
+                )
+            })?;
+
+        let mut portal =
+            BridgePortal::new(feishu_chat_id.to_string(), mxid.clone(), name, bot_mxid);
+        portal.topic = topic;
+        portal.room_type = RoomType::Group;
+        for (key, value) in [
+            ("chat_mode", &chat.chat_mode),
+            ("chat_type", &chat.chat_type),
+            ("tenant_key", &chat.tenant_key),
+        ] {
+            if let Some(value) = value {
+                portal
+                    .bridge_info
+                    .channel
+                    .insert(key.to_string(), Value::String(value.clone()));
+            }
+        }
+        self.refresh_portal_avatar(&mut portal).await;
+        self.send_portal_bridge_info(&mut portal).await;
+        let portal = self.save_portal(&portal).await?;
+        self.add_portal_to_spaces(&portal, &invitees).await;
+        self.spawn_portal_member_sync(feishu_chat_id, &mxid);
         info!(
-            "Handling Feishu member added event: chat={} users={}",
-            feishu_chat_id,
-            user_ids.join(",")
+            room_alias = %room_alias,
+            chat_id = %feishu_chat_id,
+            matrix_room_id = %mxid,
+            "Created Matrix portal for Feishu chat alias"
         );
+        Ok(mxid)
+    }
+
+    async fn release_alias_portal_reservation(&self, feishu_chat_id: &str) {
+        self.alias_portal_reservations
+            .write()
+            .await
+            .remove(feishu_chat_id);
+    }
+
+    async fn wait_for_alias_portal_binding(&self, feishu_chat_id: &str) {
+        for _ in 0..ALIAS_PORTAL_WAIT_ATTEMPTS {
+            if !self
+                .alias_portal_reservations
+                .read()
+                .await
+                .contains(feishu_chat_id)
+            {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(ALIAS_PORTAL_WAIT_INTERVAL_MILLIS)).await;
+        }
+    }
+
+    async fn linked_matrix_users_in_chat(
+        &self,
+        feishu_chat_id: &str,
+    ) -> anyhow::Result<Vec<String>> {
+        let mut linked = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let page = self
+                .feishu_service
+                .list_chat_members(feishu_chat_id, page_token.as_deref())
+                .await?;
+            for member in page.items {
+                if let Some(matrix_user_id) = self
+                    .user_store()
+                    .get_linked_matrix_user(&member.member_id)
+                    .await?
+                    && !linked.contains(&matrix_user_id)
+                {
+                    linked.push(matrix_user_id);
+                }
+            }
+            page_token = page.page_token.filter(|token| !token.is_empty());
+            if !page.has_more || page_token.is_none() {
+                break;
+            }
+        }
+        Ok(linked)
+    }
 
+    /// Adds a room bridged with `!feishu bridge` to the tenant space and the
+    /// requester's space, creating its portal record if needed.
+    pub async fn add_bridged_room_to_spaces(
+        &self,
+        matrix_room_id: &str,
+        requester: &str,
+    ) -> anyhow::Result<()> {
         let Some(mapping) = self
             .stores
             .room_store()
-            .get_room_by_feishu_id(feishu_chat_id)
+            .get_room_by_matrix_id(matrix_room_id)
             .await?
         else {
-            debug!(
-                "No room mapping found for member added event in chat {}",
-                feishu_chat_id
-            );
             return Ok(());
         };
+        let portal = self.portal_for_room_mapping(&mapping).await?;
+        self.add_portal_to_spaces(&portal, &[requester.to_string()])
+            .await;
+        Ok(())
+    }
 
-        if !user_ids.is_empty() {
-            for user_id in user_ids {
-                if let Err(err) = self.sync_feishu_user_mapping(user_id).await {
-                    warn!(
-                        feishu_user_id = %user_id,
-                        chat_id = %feishu_chat_id,
-                        error = %err,
-                        "Failed to sync Feishu user mapping on member-added event"
-                    );
+    /// Puts every bridged room that predates tenant spaces into its tenant space.
+    pub async fn backfill_portal_spaces(&self) -> anyhow::Result<usize> {
+        if !self.config.bridge.enable_tenant_spaces {
+            return Ok(0);
+        }
+        let mut added = 0;
+        for mapping in self.list_all_room_mappings().await? {
+            match self.add_room_mapping_to_tenant_space(&mapping).await {
+                Ok(true) => added += 1,
+                Ok(false) => {}
+                Err(err) => warn!(
+                    matrix_room_id = %mapping.matrix_room_id,
+                    chat_id = %mapping.feishu_chat_id,
+                    error = %err,
+                    "Failed to backfill bridged room into tenant space"
+                ),
+            }
+        }
+        Ok(added)
+    }
+
+    /// Sends `m.bridge` / `uk.half-shot.bridge` to bridged rooms missing the current content.
+    pub async fn backfill_portal_bridge_info(&self) -> anyhow::Result<usize> {
+        let mut updated = 0;
+        for mapping in self.list_all_room_mappings().await? {
+            let result = async {
+                let mut portal = self.portal_for_room_mapping(&mapping).await?;
+                self.set_portal_bridgebot(&mut portal);
+                let current = self
+                    .get_matrix_room_state_key(&portal.mxid, "m.bridge", &portal.bridge_state_key())
+                    .await?;
+                if current.as_ref() == Some(&portal.bridge_state_content()) {
+                    return Ok::<_, anyhow::Error>(false);
                 }
+                self.send_portal_bridge_info(&mut portal).await;
+                Ok(true)
             }
-            let labels = self.resolve_feishu_user_labels(user_ids).await;
-            let notice = format!("Feishu members joined: {}", labels.join(", "));
-            if let Err(err) = self
-                .bot_intent
-                .send_notice(&mapping.matrix_room_id, &notice)
-                .await
-            {
-                warn!(
-                    "Failed to send Matrix join notice for Feishu chat {}: {}",
-                    feishu_chat_id, err
-                );
+            .await;
+            match result {
+                Ok(true) => updated += 1,
+                Ok(false) => {}
+                Err(err) => warn!(
+                    matrix_room_id = %mapping.matrix_room_id,
+                    chat_id = %mapping.feishu_chat_id,
+                    error = %err,
+                    "Failed to backfill bridge info into bridged room"
+                ),
             }
         }
+        Ok(updated)
+    }
 
-        Ok(())
+    async fn list_all_room_mappings(&self) -> anyhow::Result<Vec<RoomMapping>> {
+        let room_store = self.stores.room_store();
+        let mut mappings = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = room_store
+                .list_room_mappings(Some(USER_LOOKUP_PAGE_SIZE), Some(offset))
+                .await?;
+            let page_len = page.len() as i64;
+            mappings.extend(page);
+            if page_len < USER_LOOKUP_PAGE_SIZE {
+                return Ok(mappings);
+            }
+            offset += USER_LOOKUP_PAGE_SIZE;
+        }
     }
 
-    pub async fn handle_feishu_chat_member_deleted(
+    async fn add_room_mapping_to_tenant_space(
         &self,
-        feishu_chat_id: &str,
-        user_ids: &[String],
-    ) -> anyhow::Result<()> {
-        info!(
-            "Handling Feishu member deleted event: chat={} users={}",
-            feishu_chat_id,
-            user_ids.join(",")
+        mapping: &RoomMapping,
+    ) -> anyhow::Result<bool> {
+        let portal = self.portal_for_room_mapping(mapping).await?;
+        let space_room = self.ensure_tenant_space(portal_tenant_key(&portal)).await?;
+        if self.space_has_child(&space_room, &portal.mxid).await? {
+            return Ok(false);
+        }
+        self.set_space_child(&space_room, &portal.mxid, true)
+            .await?;
+        Ok(true)
+    }
+
+    async fn portal_for_room_mapping(&self, mapping: &RoomMapping) -> anyhow::Result<BridgePortal> {
+        if let Some(portal) = self.load_portal_by_mxid(&mapping.matrix_room_id).await? {
+            return Ok(portal);
+        }
+        let bot_mxid = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
         );
+        let mut portal = BridgePortal::new(
+            mapping.feishu_chat_id.clone(),
+            mapping.matrix_room_id.clone(),
+            mapping
+                .feishu_chat_name
+                .clone()
+                .unwrap_or_else(|| format!("Feishu {}", mapping.feishu_chat_id)),
+            bot_mxid,
+        );
+        portal.room_type = room_type_from_chat_type(Some(&mapping.feishu_chat_type));
+        self.save_portal(&portal).await
+    }
 
-        let Some(mapping) = self
-            .stores
-            .room_store()
-            .get_room_by_feishu_id(feishu_chat_id)
-            .await?
-        else {
-            debug!(
-                "No room mapping found for member deleted event in chat {}",
-                feishu_chat_id
-            );
-            return Ok(());
-        };
+    async fn add_portal_to_spaces(&self, portal: &BridgePortal, matrix_user_ids: &[String]) {
+        if self.config.bridge.enable_tenant_spaces {
+            let tenant_key = portal_tenant_key(portal);
+            let result = match self.ensure_tenant_space(tenant_key).await {
+                Ok(space_room) => {
+                    for matrix_user_id in matrix_user_ids {
+                        if !self.is_bridged_matrix_user(matrix_user_id) {
+                            continue;
+                        }
+                        if let Err(err) = self.invite_matrix_user(&space_room, matrix_user_id).await
+                        {
+                            warn!(
+                                space_room = %space_room,
+                                user_id = %matrix_user_id,
+                                error = %err,
+                                "Failed to invite Matrix user to tenant space"
+                            );
+                        }
+                    }
+                    self.set_space_child(&space_room, &portal.mxid, true).await
+                }
+                Err(err) => Err(err),
+            };
+            if let Err(err) = result {
+                warn!(
+                    matrix_room_id = %portal.mxid,
+                    tenant_key = %tenant_key,
+                    error = %err,
+                    "Failed to add portal to tenant space"
+                );
+            }
+        }
 
-        if !user_ids.is_empty() {
-            for user_id in user_ids {
-                if let Err(err) = self.sync_feishu_user_mapping(user_id).await {
+        for matrix_user_id in matrix_user_ids {
+            if let Err(err) = self
+                .add_portal_to_user_space(matrix_user_id, &portal.mxid)
+                .await
+            {
+                warn!(
+                    matrix_room_id = %portal.mxid,
+                    user_id = %matrix_user_id,
+                    error = %err,
+                    "Failed to add portal to user space"
+                );
+            }
+        }
+    }
+
+    async fn remove_portal_from_spaces(&self, portal: &BridgePortal) {
+        let tenant_key = portal_tenant_key(portal);
+        match self.space_store().get_tenant_space(tenant_key).await {
+            Ok(Some(space_room)) => {
+                if let Err(err) = self.set_space_child(&space_room, &portal.mxid, false).await {
                     warn!(
-                        feishu_user_id = %user_id,
-                        chat_id = %feishu_chat_id,
+                        matrix_room_id = %portal.mxid,
+                        space_room = %space_room,
                         error = %err,
-                        "Failed to sync Feishu user mapping on member-deleted event"
+                        "Failed to remove portal from tenant space"
                     );
                 }
             }
-            let labels = self.resolve_feishu_user_labels(user_ids).await;
-            let notice = format!("Feishu members left: {}", labels.join(", "));
+            Ok(None) => {}
+            Err(err) => warn!(
+                tenant_key = %tenant_key,
+                error = %err,
+                "Failed to load tenant space"
+            ),
+        }
+
+        let members = match self
+            .appservice
+            .client
+            .get_joined_room_members(&portal.mxid)
+            .await
+        {
+            Ok(members) => members,
+            Err(err) => {
+                warn!(
+                    matrix_room_id = %portal.mxid,
+                    error = %err,
+                    "Failed to list portal members while removing it from user spaces"
+                );
+                return;
+            }
+        };
+        for member in members {
             if let Err(err) = self
-                .bot_intent
-                .send_notice(&mapping.matrix_room_id, &notice)
+                .remove_portal_from_user_space(&member, &portal.mxid)
                 .await
             {
                 warn!(
-                    "Failed to send Matrix leave notice for Feishu chat {}: {}",
-                    feishu_chat_id, err
+                    matrix_room_id = %portal.mxid,
+                    user_id = %member,
+                    error = %err,
+                    "Failed to remove portal from user space"
                 );
             }
         }
-
-        Ok(())
     }
 
-    async fn resolve_feishu_user_labels(&self, user_ids: &[String]) -> Vec<String> {
-        let mut labels = Vec::with_capacity(user_ids.len());
-        for user_id in user_ids {
-            match self.feishu_service.get_user(user_id).await {
-                Ok(user) => {
-                    if user.name.trim().is_empty() {
-                        labels.push(user_id.clone());
-                    } else {
-                        labels.push(format!("{}({})", user.name, user_id));
-                    }
-                }
-                Err(_) => labels.push(user_id.clone()),
-            }
+    pub async fn add_portal_to_user_space(
+        &self,
+        matrix_user_id: &str,
+        matrix_room_id: &str,
+    ) -> anyhow::Result<()> {
+        if !self.config.bridge.enable_user_spaces
+            || !self.is_bridged_matrix_user(matrix_user_id)
+            || self.load_portal_by_mxid(matrix_room_id).await?.is_none()
+        {
+            return Ok(());
         }
-        labels
+        let space_room = self.ensure_user_space(matrix_user_id).await?;
+        if self.space_has_child(&space_room, matrix_room_id).await? {
+            return Ok(());
+        }
+        self.set_space_child(&space_room, matrix_room_id, true)
+            .await
     }
 
-    pub async fn handle_feishu_chat_updated(
+    pub async fn remove_portal_from_user_space(
         &self,
-        feishu_chat_id: &str,
-        chat_name: Option<String>,
-        chat_mode: Option<String>,
-        chat_type: Option<String>,
+        matrix_user_id: &str,
+        matrix_room_id: &str,
     ) -> anyhow::Result<()> {
-        info!(
-            "Handling Feishu chat updated event: chat={} name={:?} mode={:?} type={:?}",
-            feishu_chat_id, chat_name, chat_mode, chat_type
-        );
-
-        let Some(mut mapping) = self
-            .stores
-            .room_store()
-            .get_room_by_feishu_id(feishu_chat_id)
-            .await?
-        else {
-            debug!(
-                "No room mapping found for chat updated event in chat {}",
-                feishu_chat_id
-            );
+        let Some(space_room) = self.space_store().get_user_space(matrix_user_id).await? else {
             return Ok(());
         };
+        self.set_space_child(&space_room, matrix_room_id, false)
+            .await
+    }
 
-        let normalized_name = chat_name.and_then(|value| {
-            let trimmed = value.trim();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed.to_string())
-            }
-        });
-        let normalized_mode = chat_mode.and_then(|value| {
-            let trimmed = value.trim();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed.to_string())
-            }
-        });
-        let normalized_type = chat_type.and_then(|value| {
-            let trimmed = value.trim();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed.to_string())
-            }
-        });
-
-        let mut changed = false;
-        if let Some(name) = &normalized_name {
-            if mapping.feishu_chat_name.as_deref() != Some(name.as_str()) {
-                mapping.feishu_chat_name = Some(name.clone());
-                changed = true;
-            }
+    async fn ensure_tenant_space(&self, tenant_key: &str) -> anyhow::Result<String> {
+        if let Some(space_room) = self.space_store().get_tenant_space(tenant_key).await? {
+            return Ok(space_room);
         }
 
-        if let Some(mode) = &normalized_mode {
-            if mapping.feishu_chat_type != *mode {
-                mapping.feishu_chat_type = mode.clone();
-                changed = true;
-            }
-        } else if let Some(chat_type) = &normalized_type {
-            if mapping.feishu_chat_type != *chat_type {
-                mapping.feishu_chat_type = chat_type.clone();
-                changed = true;
-            }
+        let name = if tenant_key == DEFAULT_TENANT_KEY {
+            "Feishu".to_string()
+        } else {
+            format!("Feishu ({})", tenant_key)
+        };
+        let space_room = self
+            .create_matrix_space(&name, &self.portal_admin_invitees())
+            .await?;
+        self.space_store()
+            .set_tenant_space(tenant_key, &space_room)
+            .await?;
+        info!(
+            tenant_key = %tenant_key,
+            space_room = %space_room,
+            "Created Matrix space for Feishu tenant"
+        );
+        Ok(space_room)
+    }
+
+    async fn ensure_user_space(&self, matrix_user_id: &str) -> anyhow::Result<String> {
+        if let Some(space_room) = self.space_store().get_user_space(matrix_user_id).await? {
+            return Ok(space_room);
         }
 
-        if changed {
-            mapping.updated_at = Utc::now();
-            self.stores
-                .room_store()
-                .update_room_mapping(&mapping)
-                .await?;
+        let space_room = self
+            .create_matrix_space("Feishu", &[matrix_user_id.to_string()])
+            .await?;
+        self.space_store()
+            .set_user_space(matrix_user_id, &space_room)
+            .await?;
+        {
+            let mut users = self._users_by_mxid.write().await;
+            let entry = users
+                .entry(matrix_user_id.to_string())
+                .or_insert_with(|| BridgeUser::new(matrix_user_id.to_string()));
+            entry.space_room = Some(space_room.clone());
         }
+        info!(
+            user_id = %matrix_user_id,
+            space_room = %space_room,
+            "Created personal Matrix space for Feishu portals"
+        );
+        Ok(space_room)
+    }
 
-        let mut portals = self.portals_by_mxid.write().await;
-        if let Some(portal) = portals.get_mut(&mapping.matrix_room_id) {
-            if let Some(name) = normalized_name {
-                portal.name = name;
-            }
-            if let Some(mode) = &normalized_mode {
-                portal
-                    .bridge_info
-                    .channel
-                    .insert("chat_mode".to_string(), Value::String(mode.clone()));
-                portal.room_type = room_type_from_chat_type(normalized_type.as_deref());
-            }
-            if let Some(kind) = normalized_type {
-                portal
-                    .bridge_info
-                    .channel
-                    .insert("chat_type".to_string(), Value::String(kind.clone()));
-                portal.room_type = room_type_from_chat_type(Some(&kind));
+    async fn create_matrix_space(&self, name: &str, invitees: &[String]) -> anyhow::Result<String> {
+        let bot_mxid = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
+        );
+        let invitees = invitees
+            .iter()
+            .filter(|user_id| **user_id != bot_mxid)
+            .cloned()
+            .collect::<Vec<_>>();
+        let payload = json!({
+            "name": name,
+            "preset": "private_chat",
+            "visibility": "private",
+            "invite": invitees,
+            "creation_content": {
+                "type": "m.space",
+                "m.federate": true
+            },
+            "power_level_content_override": {
+                "events_default": 100
             }
-            portal.last_event = Some("im.chat.updated_v1".to_string());
+        });
+        self.create_matrix_room_as_user(&bot_mxid, payload).await
+    }
+
+    async fn set_space_child(
+        &self,
+        space_room: &str,
+        child_room: &str,
+        present: bool,
+    ) -> anyhow::Result<()> {
+        let content = if present {
+            json!({ "via": [self.config.bridge.domain.clone()] })
+        } else {
+            json!({})
+        };
+        self.send_matrix_state_event(space_room, "m.space.child", child_room, content)
+            .await?;
+        Ok(())
+    }
+
+    async fn space_has_child(&self, space_room: &str, child_room: &str) -> anyhow::Result<bool> {
+        Ok(self
+            .get_matrix_room_state_key(space_room, "m.space.child", child_room)
+            .await?
+            .and_then(|content| content.get("via").and_then(Value::as_array).cloned())
+            .is_some_and(|via| !via.is_empty()))
+    }
+
+    async fn send_matrix_state_event(
+        &self,
+        matrix_room_id: &str,
+        event_type: &str,
+        state_key: &str,
+        content: Value,
+    ) -> anyhow::Result<String> {
+        let bot_mxid = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
+        );
+        let endpoint = format!(
+            "/_matrix/client/v3/rooms/{}/state/{}/{}?user_id={}",
+            urlencoding::encode(matrix_room_id),
+            urlencoding::encode(event_type),
+            urlencoding::encode(state_key),
+            urlencoding::encode(&bot_mxid)
+        );
+        let response = self
+            .appservice
+            .client
+            .raw_json(Method::PUT, &endpoint, Some(content))
+            .await
+            .with_context(|| format!("failed to send {} to {}", event_type, matrix_room_id))?;
+        if response.get("errcode").is_some() {
+            anyhow::bail!(
+                "Matrix {} state event failed in {}: {}",
+                event_type,
+                matrix_room_id,
+                response
+            );
         }
-        drop(portals);
+        Ok(response
+            .get("event_id")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string())
+    }
 
-        if let Some(mode) = &normalized_mode {
-            let notice = if mode.eq_ignore_ascii_case("thread") {
-                "Feishu chat mode changed to thread; bridge reply strategy is now thread mode."
-            } else {
-                "Feishu chat mode changed; bridge reply strategy switched to non-thread mode."
-            };
-            if let Err(err) = self
-                .bot_intent
-                .send_notice(&mapping.matrix_room_id, notice)
-                .await
-            {
-                warn!(
-                    "Failed to send chat mode update notice for chat {}: {}",
-                    feishu_chat_id, err
-                );
+    fn is_bridge_puppet_mxid(&self, matrix_user_id: &str) -> bool {
+        sender_matches_bridge_puppet(
+            matrix_user_id,
+            &self.config.bridge.username_template,
+            &self.config.bridge.domain,
+        )
+    }
+
+    fn is_bridged_matrix_user(&self, matrix_user_id: &str) -> bool {
+        let bot_mxid = format!(
+            "@{}:{}",
+            self.config.bridge.bot_username, self.config.bridge.domain
+        );
+        matrix_user_id != bot_mxid && !self.is_bridge_puppet_mxid(matrix_user_id)
+    }
+
+    async fn invite_matrix_user(
+        &self,
+        matrix_room_id: &str,
+        matrix_user_id: &str,
+    ) -> anyhow::Result<()> {
+        if let Err(err) = self
+            .bot_intent
+            .invite_user(matrix_user_id, matrix_room_id)
+            .await
+        {
+            let detail = err.to_string().to_ascii_lowercase();
+            let ignorable = detail.contains("already in room")
+                || detail.contains("is already joined")
+                || detail.contains("already invited");
+            if !ignorable {
+                return Err(err);
             }
         }
+        Ok(())
+    }
+
+    async fn load_portal_by_feishu_room(
+        &self,
+        feishu_room_id: &str,
+    ) -> anyhow::Result<Option<BridgePortal>> {
+        Ok(self
+            .portal_store()
+            .get_portal_by_feishu_room(feishu_room_id)
+            .await?
+            .map(BridgePortal::from_record))
+    }
+
+    async fn load_portal_by_mxid(&self, mxid: &str) -> anyhow::Result<Option<BridgePortal>> {
+        Ok(self
+            .portal_store()
+            .get_portal_by_mxid(mxid)
+            .await?
+            .map(BridgePortal::from_record))
+    }
+
+    async fn save_portal(&self, portal: &BridgePortal) -> anyhow::Result<BridgePortal> {
+        let saved = self
+            .portal_store()
+            .upsert_portal(&portal.to_record())
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to persist portal {} <-> {}",
+                    portal.mxid, portal.feishu_room_id
+                )
+            })?;
+        Ok(BridgePortal::from_record(saved))
+    }
 
+    async fn delete_portal_by_feishu_room(&self, feishu_room_id: &str) -> anyhow::Result<()> {
+        if let Some(portal) = self
+            .portal_store()
+            .get_portal_by_feishu_room(feishu_room_id)
+            .await?
+        {
+            self.portal_store().delete_portal(portal.id).await?;
+            self.direct_chat_store()
+                .delete_direct_chat_by_matrix_room(&portal.mxid)
+                .await?;
+        }
         Ok(())
     }
 
-    pub async fn handle_feishu_chat_disbanded(&self, feishu_chat_id: &str) -> anyhow::Result<()> {
-        info!(
-            "Handling Feishu chat disbanded event: chat={}",
-            feishu_chat_id
-        );
-
-        let mapping = self
-            .stores
-            .room_store()
-            .get_room_by_feishu_id(feishu_chat_id)
+    pub async fn delete_portal_by_mxid(&self, mxid: &str) -> anyhow::Result<()> {
+        if let Some(portal) = self.portal_store().get_portal_by_mxid(mxid).await? {
+            self.portal_store().delete_portal(portal.id).await?;
+        }
+        self.direct_chat_store()
+            .delete_direct_chat_by_matrix_room(mxid)
             .await?;
+        Ok(())
+    }
 
-        let Some(mapping) = mapping else {
-            debug!(
-                "No room mapping found for disbanded chat {}; cleaning memory cache only",
-                feishu_chat_id
-            );
-            if let Some(mxid) = self
-                .portals_by_feishu_room
-                .write()
-                .await
-                .remove(feishu_chat_id)
-            {
-                self.portals_by_mxid.write().await.remove(&mxid);
-            }
-            return Ok(());
-        };
+    fn portal_admin_invitees(&self) -> Vec<String> {
+        let mut invitees = self
+            .config
+            .bridge
+            .permissions
+            .iter()
+            .filter(|(entity, level)| {
+                entity.starts_with('@') && level.trim().eq_ignore_ascii_case("admin")
+            })
+            .map(|(entity, _)| entity.clone())
+            .collect::<Vec<_>>();
+        invitees.sort();
+        invitees
+    }
 
-        let historical_mappings = self
-            .stores
-            .message_store()
-            .get_messages_by_room(&mapping.matrix_room_id, Some(2000))
+    /// Creates a DM between `creator_mxid` (the other party, usually a Feishu
+    /// puppet) and `owner_mxid`, the single Matrix user the chat belongs to.
+    async fn create_direct_portal_room(
+        &self,
+        feishu_room_id: &str,
+        creator_mxid: &str,
+        owner_mxid: &str,
+        bot_mxid: &str,
+    ) -> anyhow::Result<String> {
+        let mut invitees = vec![owner_mxid.to_string()];
+        if creator_mxid != bot_mxid {
+            self.ensure_matrix_user_registered(creator_mxid).await?;
+            if owner_mxid != bot_mxid {
+                invitees.push(bot_mxid.to_string());
+            }
+        }
+        let payload = json!({
+            "preset": "trusted_private_chat",
+            "visibility": "private",
+            "is_direct": true,
+            "invite": invitees,
+            "creation_content": {
+                "m.federate": true
+            }
+        });
+        let mxid = self
+            .create_matrix_room_as_user(creator_mxid, payload)
             .await?;
-        for message in historical_mappings {
-            if let Err(err) = self
-                .stores
-                .message_store()
-                .delete_message_mapping(message.id)
-                .await
-            {
+        if creator_mxid != bot_mxid {
+            self.join_matrix_room_as_user(&mxid, bot_mxid).await?;
+        }
+        info!(
+            feishu_chat_id = %feishu_room_id,
+            matrix_room_id = %mxid,
+            creator = %creator_mxid,
+            owner = %owner_mxid,
+            "Created Matrix direct room for Feishu p2p chat"
+        );
+
+        for (user_id, other_id) in [(creator_mxid, owner_mxid), (owner_mxid, creator_mxid)] {
+            if let Err(err) = self.add_matrix_direct_room(user_id, other_id, &mxid).await {
                 warn!(
-                    "Failed to delete message mapping {} while disbanding chat {}: {}",
-                    message.id, feishu_chat_id, err
+                    matrix_room_id = %mxid,
+                    user_id = %user_id,
+                    error = %err,
+                    "Failed to update m.direct for direct chat member"
                 );
             }
         }
+        Ok(mxid)
+    }
 
-        self.stores
-            .room_store()
-            .delete_room_mapping(mapping.id)
-            .await?;
-
-        self.portals_by_feishu_room
-            .write()
-            .await
-            .remove(feishu_chat_id);
-        self.portals_by_mxid
-            .write()
+    async fn add_matrix_direct_room(
+        &self,
+        owner_mxid: &str,
+        other_mxid: &str,
+        matrix_room_id: &str,
+    ) -> anyhow::Result<()> {
+        let endpoint = format!(
+            "/_matrix/client/v3/user/{}/account_data/m.direct?user_id={}",
+            urlencoding::encode(owner_mxid),
+            urlencoding::encode(owner_mxid)
+        );
+        let current = self
+            .appservice
+            .client
+            .raw_json(Method::GET, &endpoint, None)
             .await
-            .remove(&mapping.matrix_room_id);
+            .with_context(|| format!("failed to read m.direct for {}", owner_mxid))?;
+        let mut direct = match current.get("errcode").and_then(Value::as_str) {
+            Some("M_NOT_FOUND") => serde_json::Map::new(),
+            Some(_) => anyhow::bail!(
+                "Matrix m.direct read failed for {}: {}",
+                owner_mxid,
+                current
+            ),
+            None => current.as_object().cloned().unwrap_or_default(),
+        };
 
-        if let Err(err) = self
-            .bot_intent
-            .send_notice(
-                &mapping.matrix_room_id,
-                "Feishu chat has been disbanded; bridge mapping was removed automatically.",
-            )
-            .await
+        let mut rooms = direct
+            .get(other_mxid)
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        if rooms
+            .iter()
+            .any(|room| room.as_str() == Some(matrix_room_id))
         {
-            warn!(
-                "Failed to send disband notice to Matrix room {}: {}",
-                mapping.matrix_room_id, err
-            );
+            return Ok(());
         }
+        rooms.push(Value::String(matrix_room_id.to_string()));
+        direct.insert(other_mxid.to_string(), Value::Array(rooms));
 
+        let response = self
+            .appservice
+            .client
+            .raw_json(Method::PUT, &endpoint, Some(Value::Object(direct)))
+            .await
+            .with_context(|| format!("failed to write m.direct for {}", owner_mxid))?;
+        if response.get("errcode").is_some() {
+            anyhow::bail!(
+                "Matrix m.direct write failed for {}: {}",
+                owner_mxid,
+                response
+            );
+        }
         Ok(())
     }
 
-    pub async fn handle_matrix_message(&self, room_id: &str, event: Value) -> anyhow::Result<()> {
-        info!("Handling Matrix message in room {}", room_id);
-        let sender = event
-            .get("sender")
-            .and_then(Value::as_str)
-            .unwrap_or("unknown");
-        if self.is_bridge_bot_sender(sender) {
-            debug!(
-                room_id = %room_id,
-                sender = %sender,
-                "Ignoring Matrix message sent by bridge bot to prevent self-loop"
+    async fn create_matrix_room_as_user(
+        &self,
+        matrix_user_id: &str,
+        payload: Value,
+    ) -> anyhow::Result<String> {
+        let endpoint = format!(
+            "/_matrix/client/v3/createRoom?user_id={}",
+            urlencoding::encode(matrix_user_id)
+        );
+        let response = self
+            .appservice
+            .client
+            .raw_json(Method::POST, &endpoint, Some(payload))
+            .await
+            .with_context(|| format!("failed Matrix createRoom request for {}", matrix_user_id))?;
+        if response.get("errcode").is_some() {
+            anyhow::bail!(
+                "Matrix createRoom failed for {}: {}",
+                matrix_user_id,
+                response
             );
-            return Ok(());
         }
-
-        let body = event
-            .get("content")
-            .and_then(|c| c.get("body"))
+        response
+            .get("room_id")
             .and_then(Value::as_str)
-            .unwrap_or_default();
-
-        if self.command_handler.is_command(body) {
-            debug!("Matrix command detected: {}", body);
-            let room_mapping: Option<crate::database::RoomMapping> = self
-                .stores
-                .room_store()
-                .get_room_by_matrix_id(room_id)
-                .await?;
+            .map(ToOwned::to_owned)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "missing room_id in Matrix createRoom response for {}: {}",
+                    matrix_user_id,
+                    response
+                )
+            })
+    }
 
-            let outcome = self
-                .command_handler
-                .handle(body, room_mapping.is_some(), |_| true);
-            self.handle_command_outcome(outcome, room_id, sender).await?;
+    pub async fn sync_matrix_room_metadata(
+        &self,
+        matrix_room_id: &str,
+        event_type: &str,
+        content: &Value,
+    ) -> anyhow::Result<()> {
+        let Some(mut mapping) = self
+            .stores
+            .room_store()
+            .get_room_by_matrix_id(matrix_room_id)
+            .await?
+        else {
             return Ok(());
-        }
-
-        let message = self.matrix_event_to_bridge_message(room_id, event)?;
-        let portal = self.get_or_create_portal_by_matrix_room(room_id).await?;
-
-        if portal.feishu_room_id.starts_with("mx_") {
-            warn!(
-                "Skipping Matrix->Feishu forward for room {} because no Feishu room mapping exists yet",
-                room_id
-            );
+        };
+        if is_feishu_p2p_chat(Some(&mapping.feishu_chat_type), None) {
             return Ok(());
         }
+        let mut portal = self.load_portal_by_mxid(matrix_room_id).await?;
 
-        let feishu_content = formatter::format_matrix_to_feishu(message)?;
-        self.feishu_service
-            .send_text_message(&portal.feishu_room_id, &feishu_content)
-            .await?;
+        match event_type {
+            "m.room.name" => {
+                let name = content
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .map(str::trim)
+                    .unwrap_or_default();
+                if name.is_empty() || mapping.feishu_chat_name.as_deref() == Some(name) {
+                    return Ok(());
+                }
+                self.feishu_service
+                    .update_chat(&mapping.feishu_chat_id, Some(name), None)
+                    .await?;
+                mapping.feishu_chat_name = Some(name.to_string());
+                mapping.updated_at = Utc::now();
+                self.stores
+                    .room_store()
+                    .update_room_mapping(&mapping)
+                    .await?;
+                if let Some(portal) = portal.as_mut() {
+                    portal.name = name.to_string();
+                }
+            }
+            "m.room.topic" => {
+                let topic = content
+                    .get("topic")
+                    .and_then(Value::as_str)
+                    .map(str::trim)
+                    .unwrap_or_default();
+                if portal
+                    .as_ref()
+                    .is_some_and(|portal| portal.topic.as_deref().unwrap_or_default() == topic)
+                {
+                    return Ok(());
+                }
+                self.feishu_service
+                    .update_chat(&mapping.feishu_chat_id, None, Some(topic))
+                    .await?;
+                if let Some(portal) = portal.as_mut() {
+                    portal.topic = Some(topic.to_string()).filter(|value| !value.is_empty());
+                }
+            }
+            _ => return Ok(()),
+        }
 
+        info!(
+            matrix_room_id = %matrix_room_id,
+            feishu_chat_id = %mapping.feishu_chat_id,
+            event_type = %event_type,
+            "Pushed Matrix room metadata change to Feishu"
+        );
+        if let Some(portal) = portal {
+            self.save_portal(&portal).await?;
+        }
         Ok(())
     }
 
-    fn is_bridge_bot_sender(&self, sender: &str) -> bool {
-        let configured_bot = format!(
+    fn set_portal_bridgebot(&self, portal: &mut BridgePortal) {
+        portal.bridge_info.bridgebot = format!(
             "@{}:{}",
             self.config.bridge.bot_username, self.config.bridge.domain
         );
-        if sender.eq_ignore_ascii_case(&configured_bot) {
-            return true;
-        }
-
-        let sender_localpart = self.config.registration.sender_localpart.trim();
-        if sender_localpart.is_empty() {
-            return false;
-        }
-
-        let registration_bot = format!("@{}:{}", sender_localpart, self.config.bridge.domain);
-        sender.eq_ignore_ascii_case(&registration_bot)
     }
 
-    async fn handle_command_outcome(
-        &self,
-        outcome: MatrixCommandOutcome,
-        room_id: &str,
-        _sender: &str,
-    ) -> anyhow::Result<()> {
-        match outcome {
-            MatrixCommandOutcome::Ignored => {}
-            MatrixCommandOutcome::Reply(reply) => {
-                self.bot_intent.send_text(room_id, &reply).await?;
-            }
-            MatrixCommandOutcome::BridgeRequested { feishu_chat_id } => {
-                let mapping = crate::database::RoomMapping::new(
-                    room_id.to_string(),
-                    feishu_chat_id.clone(),
-                    Some(format!("Feishu {}", feishu_chat_id)),
+    async fn send_portal_bridge_info(&self, portal: &mut BridgePortal) {
+        self.set_portal_bridgebot(portal);
+        let state_key = portal.bridge_state_key();
+        let content = portal.bridge_state_content();
+        for event_type in ["m.bridge", "uk.half-shot.bridge"] {
+            if let Err(err) = self
+                .send_matrix_state_event(&portal.mxid, event_type, &state_key, content.clone())
+                .await
+            {
+                warn!(
+                    matrix_room_id = %portal.mxid,
+                    event_type = %event_type,
+                    error = %err,
+                    "Failed to send bridge info state event"
                 );
-                self.stores
-                    .room_store()
-                    .create_room_mapping(&mapping)
-                    .await?;
-                info!("Created bridge: {} <-> {}", room_id, feishu_chat_id);
-                self.bot_intent
-                    .send_text(
-                        room_id,
-                        &format!("Bridged to Feishu chat: {}", feishu_chat_id),
-                    )
-                    .await?;
             }
-            MatrixCommandOutcome::UnbridgeRequested => {
-                if let Some(mapping) = self
-                    .stores
-                    .room_store()
-                    .get_room_by_matrix_id(room_id)
-                    .await?
-                {
-                    self.stores
-                        .room_store()
-                        .delete_room_mapping(mapping.id)
-                        .await?;
-                    info!("Removed bridge for room {}", room_id);
-                    self.bot_intent.send_text(room_id, "Bridge removed").await?;
+        }
+    }
+
+    async fn refresh_portal_avatar(&self, portal: &mut BridgePortal) {
+        let avatar = match self.feishu_service.get_chat(&portal.feishu_room_id).await {
+            Ok(chat) => chat
+                .avatar
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToOwned::to_owned),
+            Err(err) => {
+                warn!(
+                    chat_id = %portal.feishu_room_id,
+                    error = %err,
+                    "Failed to load Feishu chat profile for avatar sync"
+                );
+                return;
+            }
+        };
+        let synced_avatar = portal
+            .bridge_info
+            .channel
+            .get("feishu_avatar")
+            .and_then(Value::as_str);
+        if avatar.as_deref() == synced_avatar && avatar.is_some() == portal.avatar_url.is_some() {
+            return;
+        }
+
+        let Some(avatar) = avatar else {
+            match self
+                .send_matrix_state_event(&portal.mxid, "m.room.avatar", "", json!({}))
+                .await
+            {
+                Ok(_) => {
+                    portal.avatar_url = None;
+                    portal.bridge_info.channel.remove("feishu_avatar");
                 }
+                Err(err) => warn!(
+                    matrix_room_id = %portal.mxid,
+                    error = %err,
+                    "Failed to remove Matrix room avatar"
+                ),
+            }
+            return;
+        };
+
+        let mxc = match self.import_feishu_avatar(&avatar).await {
+            Ok(mxc) => mxc,
+            Err(err) => {
+                warn!(
+                    chat_id = %portal.feishu_room_id,
+                    error = %err,
+                    "Failed to import Feishu chat avatar"
+                );
+                return;
             }
+        };
+        if portal.avatar_url.as_deref() != Some(mxc.as_str())
+            && let Err(err) = self
+                .send_matrix_state_event(&portal.mxid, "m.room.avatar", "", json!({ "url": mxc }))
+                .await
+        {
+            warn!(
+                matrix_room_id = %portal.mxid,
+                error = %err,
+                "Failed to update Matrix room avatar"
+            );
+            return;
         }
-        Ok(())
+        portal.avatar_url = Some(mxc);
+        portal
+            .bridge_info
+            .channel
+            .insert("feishu_avatar".to_string(), Value::String(avatar));
     }
 
-    async fn get_or_create_portal_by_feishu_room(
-        &self,
-        feishu_room_id: &str,
-    ) -> anyhow::Result<BridgePortal> {
-        if let Some(mxid) = self
-            .portals_by_feishu_room
-            .read()
+    async fn import_feishu_avatar(&self, avatar_url: &str) -> anyhow::Result<String> {
+        let response = reqwest::Client::new()
+            .get(avatar_url)
+            .send()
             .await
-            .get(feishu_room_id)
-            .cloned()
+            .with_context(|| format!("failed to download Feishu avatar {}", avatar_url))?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!(
+                "Feishu avatar download failed: status={} url={}",
+                status,
+                avatar_url
+            );
+        }
+        let declared_mime = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let bytes = response
+            .bytes()
+            .await
+            .context("failed to read Feishu avatar body")?
+            .to_vec();
+        if self.config.bridge.max_media_size > 0 && bytes.len() > self.config.bridge.max_media_size
         {
-            if let Some(portal) = self.portals_by_mxid.read().await.get(&mxid).cloned() {
-                return Ok(portal);
-            }
+            anyhow::bail!(
+                "Feishu avatar exceeds configured max_media_size: {} > {}",
+                bytes.len(),
+                self.config.bridge.max_media_size
+            );
         }
 
-        let mxid = format!(
-            "!feishu_{}:{}",
-            sanitize_identifier(feishu_room_id),
-            self.config.bridge.domain
-        );
-        let name = format!("Feishu {}", feishu_room_id);
-        let portal = BridgePortal::new(
-            feishu_room_id.to_string(),
-            mxid.clone(),
-            name,
-            format!(
-                "@{}:{}",
-                self.config.bridge.bot_username, self.config.bridge.domain
-            ),
-        );
-
-        self.portals_by_mxid
-            .write()
-            .await
-            .insert(mxid.clone(), portal.clone());
-        self.portals_by_feishu_room
-            .write()
-            .await
-            .insert(feishu_room_id.to_string(), mxid);
+        let content_hash = sha256_hex(&bytes);
+        let media_store = self.stores.media_store();
+        if let Some(cached) = media_store
+            .get_media_cache(&content_hash, "matrix_avatar")
+            .await?
+        {
+            return Ok(cached.resource_key);
+        }
 
-        Ok(portal)
+        let mime_type = resolve_attachment_mime_type("image", &declared_mime, &bytes);
+        let file_name = format!("avatar.{}", extension_for_mime(&mime_type));
+        let mxc = self
+            .upload_matrix_media(bytes, &mime_type, &file_name)
+            .await?;
+        let now = Utc::now();
+        media_store
+            .upsert_media_cache(&MediaCacheEntry {
+                id: 0,
+                content_hash,
+                media_kind: "matrix_avatar".to_string(),
+                resource_key: mxc.clone(),
+                created_at: now,
+                updated_at: now,
+            })
+            .await?;
+        Ok(mxc)
     }
 
     async fn get_or_create_portal_by_matrix_room(
         &self,
         room_id: &str,
     ) -> anyhow::Result<BridgePortal> {
-        if let Some(portal) = self.portals_by_mxid.read().await.get(room_id).cloned() {
+        if let Some(portal) = self.load_portal_by_mxid(room_id).await? {
             return Ok(portal);
         }
 
+        if let Some(mapping) = self
+            .stores
+            .room_store()
+            .get_room_by_matrix_id(room_id)
+            .await?
+        {
+            let portal = BridgePortal::new(
+                mapping.feishu_chat_id.clone(),
+                room_id.to_string(),
+                mapping
+                    .feishu_chat_name
+                    .unwrap_or_else(|| format!("Feishu {}", mapping.feishu_chat_id)),
+                format!(
+                    "@{}:{}",
+                    self.config.bridge.bot_username, self.config.bridge.domain
+                ),
+            );
+            return self.save_portal(&portal).await;
+        }
+
         let generated_feishu_room_id = format!("mx_{}", sanitize_identifier(room_id));
-        let portal = BridgePortal::new(
+        Ok(BridgePortal::new(
             generated_feishu_room_id,
             room_id.to_string(),
             format!("Matrix {}", room_id),
@@ -1538,13 +5105,7 @@ impl FeishuBridge {
                 "@{}:{}",
                 self.config.bridge.bot_username, self.config.bridge.domain
             ),
-        );
-
-        self.portals_by_mxid
-            .write()
-            .await
-            .insert(room_id.to_string(), portal.clone());
-        Ok(portal)
+        ))
     }
 
     fn matrix_event_to_bridge_message(
@@ -1604,6 +5165,8 @@ impl FeishuBridge {
             thread_id: None,
             root_id: None,
             parent_id: None,
+            rich_content: None,
+            mentions: Vec::new(),
         })
     }
 
@@ -1765,12 +5328,28 @@ impl FeishuBridge {
         matrix_user_id: &str,
         matrix_room_id: &str,
         content: Value,
+    ) -> anyhow::Result<String> {
+        self.send_matrix_room_event_as_user(
+            matrix_user_id,
+            matrix_room_id,
+            "m.room.message",
+            content,
+        )
+        .await
+    }
+
+    async fn send_matrix_room_event_as_user(
+        &self,
+        matrix_user_id: &str,
+        matrix_room_id: &str,
+        event_type: &str,
+        content: Value,
     ) -> anyhow::Result<String> {
         let txn_id = Uuid::new_v4().to_string();
         let endpoint = format!(
             "/_matrix/client/v3/rooms/{}/send/{}/{}?user_id={}",
             urlencoding::encode(matrix_room_id),
-            urlencoding::encode("m.room.message"),
+            urlencoding::encode(event_type),
             txn_id,
             urlencoding::encode(matrix_user_id)
         );
@@ -1821,32 +5400,116 @@ impl FeishuBridge {
         matrix_sender_mxid: &str,
         matrix_room_id: &str,
         body: &str,
-        reply_to_matrix_event_id: Option<&str>,
+        formatted: Option<&formatter::MatrixFormattedText>,
+        relates_to: Option<&Value>,
     ) -> anyhow::Result<String> {
-        if let Some(reply_event_id) = reply_to_matrix_event_id {
-            let content = json!({
-                "msgtype": "m.text",
-                "body": body,
-                "m.relates_to": {
-                    "m.in_reply_to": {
-                        "event_id": reply_event_id
-                    }
+        let mut content = json!({
+            "msgtype": "m.text",
+            "body": body
+        });
+        if let Some(formatted) = formatted {
+            content["format"] = json!("org.matrix.custom.html");
+            content["formatted_body"] = json!(formatted.formatted_body);
+            if let Some(mentions) = formatted.m_mentions() {
+                content["m.mentions"] = mentions;
+            }
+        }
+        if let Some(relates_to) = relates_to {
+            content["m.relates_to"] = relates_to.clone();
+        }
+
+        self.send_matrix_room_message_as_user(matrix_sender_mxid, matrix_room_id, content)
+            .await
+    }
+
+    async fn render_feishu_message_for_matrix(
+        &self,
+        message: &BridgeMessage,
+    ) -> Option<formatter::MatrixFormattedText> {
+        if message.rich_content.is_none() && message.mentions.is_empty() {
+            return None;
+        }
+
+        let mut context = formatter::FeishuRenderContext::default();
+        for mention in &message.mentions {
+            let pill = self.resolve_feishu_mention(mention).await;
+            context.mentions.insert(mention.key.clone(), pill);
+        }
+
+        let Some(post) = message.rich_content.as_ref() else {
+            return Some(formatter::feishu_text_to_matrix(&message.content, &context));
+        };
+        for image_key in formatter::extract_post_image_keys(post) {
+            match self.upload_feishu_post_image(&message.id, &image_key).await {
+                Ok(mxc) => {
+                    context.image_urls.insert(image_key, mxc);
                 }
-            });
-            return self
-                .send_matrix_room_message_as_user(matrix_sender_mxid, matrix_room_id, content)
-                .await;
+                Err(err) => warn!(
+                    "Failed to bridge inline image {} of Feishu post {}: {}",
+                    image_key, message.id, err
+                ),
+            }
         }
 
-        self.send_matrix_room_message_as_user(
-            matrix_sender_mxid,
-            matrix_room_id,
-            json!({
-                "msgtype": "m.text",
-                "body": body
-            }),
-        )
-        .await
+        Some(formatter::feishu_post_to_matrix(post, &context))
+    }
+
+    async fn resolve_feishu_mention(&self, mention: &MessageMention) -> formatter::MatrixPill {
+        let mut pill = formatter::MatrixPill {
+            user_id: None,
+            display_name: if mention.name.trim().is_empty() {
+                mention.key.trim_start_matches('@').to_string()
+            } else {
+                mention.name.clone()
+            },
+        };
+        if mention.feishu_user_id.is_empty() {
+            return pill;
+        }
+
+        match self.sync_feishu_user_mapping(&mention.feishu_user_id).await {
+            Ok(Some(canonical_user_id)) => {
+                if let Ok(Some(mapping)) = self
+                    .user_store()
+                    .get_user_by_feishu_id(&canonical_user_id)
+                    .await
+                {
+                    pill.user_id = Some(mapping.matrix_user_id);
+                }
+            }
+            Ok(None) => {}
+            Err(err) => debug!(
+                feishu_user_id = %mention.feishu_user_id,
+                error = %err,
+                "Failed to resolve Feishu mention to a Matrix user"
+            ),
+        }
+        pill
+    }
+
+    async fn upload_feishu_post_image(
+        &self,
+        feishu_message_id: &str,
+        image_key: &str,
+    ) -> anyhow::Result<String> {
+        let bytes = self
+            .feishu_service
+            .get_message_resource(feishu_message_id, image_key, "image")
+            .await?;
+
+        if self.config.bridge.max_media_size > 0 && bytes.len() > self.config.bridge.max_media_size
+        {
+            anyhow::bail!(
+                "feishu post image exceeds configured max_media_size: {} > {}",
+                bytes.len(),
+                self.config.bridge.max_media_size
+            );
+        }
+
+        let mime_type = resolve_attachment_mime_type("image", "image/*", &bytes);
+        let file_name = normalize_attachment_filename("", "image", image_key, &mime_type);
+        self.upload_matrix_media(bytes, &mime_type, &file_name)
+            .await
     }
 
     async fn forward_feishu_attachments_to_matrix(
@@ -1855,13 +5518,12 @@ impl FeishuBridge {
         matrix_sender_mxid: &str,
         matrix_room_id: &str,
         message: &BridgeMessage,
-        reply_to_matrix_event_id: Option<&str>,
+        relates_to: Option<Value>,
     ) -> anyhow::Result<Vec<String>> {
         let mut event_ids = Vec::new();
-        let mut pending_reply_target = reply_to_matrix_event_id.map(ToOwned::to_owned);
+        let mut pending_relation = relates_to;
 
         for attachment in &message.attachments {
-            let current_reply_target = pending_reply_target.as_deref();
             match self
                 .forward_single_feishu_attachment(
                     intent,
@@ -1869,13 +5531,13 @@ impl FeishuBridge {
                     matrix_room_id,
                     &message.id,
                     attachment,
-                    current_reply_target,
+                    pending_relation.as_ref(),
                 )
                 .await
             {
                 Ok(event_id) => {
+                    pending_relation = next_thread_relation(pending_relation.as_ref(), &event_id);
                     event_ids.push(event_id);
-                    pending_reply_target = None;
                 }
                 Err(err) => warn!(
                     "Failed to forward Feishu attachment {} for message {}: {}",
@@ -1894,7 +5556,7 @@ impl FeishuBridge {
         matrix_room_id: &str,
         feishu_message_id: &str,
         attachment: &super::message::Attachment,
-        reply_to_matrix_event_id: Option<&str>,
+        relates_to: Option<&Value>,
     ) -> anyhow::Result<String> {
         let (kind, key) = parse_feishu_attachment_url(&attachment.url)
             .ok_or_else(|| anyhow::anyhow!("invalid feishu attachment url: {}", attachment.url))?;
@@ -1944,12 +5606,8 @@ impl FeishuBridge {
                 "size": bytes.len() as u64
             }
         });
-        if let Some(reply_event_id) = reply_to_matrix_event_id {
-            content["m.relates_to"] = json!({
-                "m.in_reply_to": {
-                    "event_id": reply_event_id
-                }
-            });
+        if let Some(relates_to) = relates_to {
+            content["m.relates_to"] = relates_to.clone();
         }
 
         self.send_matrix_room_message_as_user(matrix_sender_mxid, matrix_room_id, content)
@@ -2018,6 +5676,52 @@ impl FeishuBridge {
     }
 }
 
+/// Matrix users whose power level crossed the Feishu manager threshold (50) between two
+/// `m.room.power_levels` contents, as (promoted, demoted).
+fn is_feishu_role_power_level(level: i64) -> bool {
+    level == FEISHU_MANAGER_POWER_LEVEL || level == FEISHU_OWNER_POWER_LEVEL
+}
+
+fn manager_level_changes(
+    prev_content: Option<&Value>,
+    content: &Value,
+) -> (Vec<String>, Vec<String>) {
+    fn level_of(content: &Value, user_id: &str) -> i64 {
+        content
+            .get("users")
+            .and_then(|users| users.get(user_id))
+            .or_else(|| content.get("users_default"))
+            .and_then(Value::as_i64)
+            .unwrap_or_default()
+    }
+
+    let mut user_ids: Vec<&String> = Vec::new();
+    for users in [Some(content), prev_content]
+        .into_iter()
+        .flatten()
+        .filter_map(|content| content.get("users").and_then(Value::as_object))
+    {
+        for user_id in users.keys() {
+            if !user_ids.contains(&user_id) {
+                user_ids.push(user_id);
+            }
+        }
+    }
+
+    let mut promoted = Vec::new();
+    let mut demoted = Vec::new();
+    for user_id in user_ids {
+        let was_manager = prev_content.is_some_and(|prev| level_of(prev, user_id) >= 50);
+        let is_manager = level_of(content, user_id) >= 50;
+        if is_manager && !was_manager {
+            promoted.push(user_id.clone());
+        } else if was_manager && !is_manager {
+            demoted.push(user_id.clone());
+        }
+    }
+    (promoted, demoted)
+}
+
 fn sanitize_identifier(input: &str) -> String {
     let sanitized = input
         .chars()
@@ -2041,6 +5745,67 @@ fn summarize_for_log(text: &str, max_chars: usize) -> String {
     summary.replace('\n', "\\n")
 }
 
+// Feishu reaction events carry no reaction_id, so inbound reactions are keyed
+// by message, reacting user and emoji type.
+fn feishu_reaction_key(feishu_message_id: &str, feishu_user_id: &str, emoji_type: &str) -> String {
+    format!("{}:{}:{}", feishu_message_id, feishu_user_id, emoji_type)
+}
+
+fn keep_latest_read_receipt(
+    readers: &mut HashMap<String, MessageMapping>,
+    reader_mxid: &str,
+    target: MessageMapping,
+) {
+    match readers.get(reader_mxid) {
+        Some(current) if current.created_at >= target.created_at => {}
+        _ => {
+            readers.insert(reader_mxid.to_string(), target);
+        }
+    }
+}
+
+fn matrix_relates_to(
+    thread_root_event_id: Option<&str>,
+    reply_to_event_id: Option<&str>,
+) -> Option<Value> {
+    match (thread_root_event_id, reply_to_event_id) {
+        (Some(root_event_id), reply_to) => {
+            // Clients without thread support still render the reply fallback.
+            let in_reply_to = reply_to.unwrap_or(root_event_id);
+            Some(json!({
+                "rel_type": "m.thread",
+                "event_id": root_event_id,
+                "is_falling_back": in_reply_to == root_event_id,
+                "m.in_reply_to": {
+                    "event_id": in_reply_to
+                }
+            }))
+        }
+        (None, Some(reply_to)) => Some(json!({
+            "m.in_reply_to": {
+                "event_id": reply_to
+            }
+        })),
+        (None, None) => None,
+    }
+}
+
+fn next_thread_relation(relates_to: Option<&Value>, last_event_id: &str) -> Option<Value> {
+    let relates_to = relates_to?;
+    if relates_to.get("rel_type").and_then(Value::as_str) != Some("m.thread") {
+        return None;
+    }
+    let root_event_id = relates_to.get("event_id").and_then(Value::as_str)?;
+    Some(json!({
+        "rel_type": "m.thread",
+        "event_id": root_event_id,
+        "is_falling_back": true,
+        "m.in_reply_to": {
+            "event_id": last_event_id
+        }
+    }))
+}
+
 fn parse_feishu_attachment_url(url: &str) -> Option<(&str, &str)> {
     let stripped = url.strip_prefix("feishu://")?;
     let mut parts = stripped.splitn(2, '/');
@@ -2206,6 +5971,60 @@ fn extension_for_mime(mime_type: &str) -> &'static str {
     }
 }
 
+fn is_matrix_user_id(value: &str) -> bool {
+    value
+        .strip_prefix('@')
+        .and_then(|rest| rest.split_once(':'))
+        .is_some_and(|(localpart, server)| !localpart.is_empty() && !server.is_empty())
+}
+
+fn looks_like_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.contains(['@', ':'])
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !value.contains(char::is_whitespace)
+}
+
+fn thirdparty_field<'a>(fields: &'a HashMap<String, String>, key: &str) -> Option<&'a str> {
+    fields
+        .get(key)
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+}
+
+fn thirdparty_user_entry(mapping: &UserMapping) -> Value {
+    json!({
+        "userid": mapping.matrix_user_id,
+        "protocol": THIRDPARTY_PROTOCOL,
+        "fields": {
+            "id": mapping.feishu_user_id,
+            "name": mapping
+                .feishu_username
+                .as_deref()
+                .unwrap_or(&mapping.feishu_user_id)
+        }
+    })
+}
+
+fn portal_tenant_key(portal: &BridgePortal) -> &str {
+    portal
+        .bridge_info
+        .channel
+        .get("tenant_key")
+        .and_then(Value::as_str)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(DEFAULT_TENANT_KEY)
+}
+
+fn is_feishu_p2p_chat(chat_mode: Option<&str>, chat_type: Option<&str>) -> bool {
+    matches!(chat_mode, Some("p2p")) || matches!(chat_type, Some("p2p") | Some("single"))
+}
+
 fn room_type_from_chat_type(chat_type: Option<&str>) -> RoomType {
     match chat_type {
         Some("p2p") | Some("private") | Some("single") => RoomType::Direct,
@@ -2250,6 +6069,53 @@ async fn ready_handler(res: &mut Response) {
     res.render(Json(serde_json::json!({ "ready": true })));
 }
 
+#[derive(Clone)]
+struct AliasQueryState {
+    bridge: FeishuBridge,
+    hs_token: String,
+}
+
+#[handler]
+async fn alias_query_handler(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let state: &AliasQueryState = depot.obtain().unwrap();
+    let token = req
+        .header::<String>("authorization")
+        .and_then(|value| value.strip_prefix("Bearer ").map(ToOwned::to_owned))
+        .or_else(|| req.query::<String>("access_token"));
+    if token.as_deref() != Some(state.hs_token.as_str()) {
+        res.status_code(StatusCode::FORBIDDEN);
+        res.render(Json(serde_json::json!({
+            "errcode": "M_FORBIDDEN",
+            "error": "Bad token supplied",
+        })));
+        return;
+    }
+
+    let room_alias = req.param::<String>("room_alias").unwrap_or_default();
+    info!("Query room alias: {}", room_alias);
+    match state.bridge.create_alias_portal(&room_alias).await {
+        Ok(Some(_)) => {
+            res.status_code(StatusCode::OK);
+            res.render(Json(serde_json::json!({})));
+        }
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND);
+            res.render(Json(serde_json::json!({
+                "errcode": "M_NOT_FOUND",
+                "error": "Room not created",
+            })));
+        }
+        Err(err) => {
+            warn!(room_alias = %room_alias, error = %err, "Failed to create alias portal");
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
+            res.render(Json(serde_json::json!({
+                "errcode": "M_UNKNOWN",
+                "error": "Failed to create room",
+            })));
+        }
+    }
+}
+
 #[derive(Clone)]
 struct BridgeStatusState {
     room_store: Arc<dyn RoomStore>,
@@ -2418,6 +6284,10 @@ impl AppserviceHandler for BridgeHandler {
                     .and_then(Value::as_str)
                     .map(ToOwned::to_owned),
                 content: event.get("content").cloned(),
+                prev_content: event
+                    .get("unsigned")
+                    .and_then(|unsigned| unsigned.get("prev_content"))
+                    .cloned(),
                 timestamp: event.get("origin_server_ts").map(|v| v.to_string()),
             };
 
@@ -2453,26 +6323,6 @@ impl AppserviceHandler for BridgeHandler {
         Ok(None)
     }
 
-    async fn query_room_alias(&self, room_alias: &str) -> anyhow::Result<Option<Value>> {
-        info!("Query room alias: {}", room_alias);
-
-        let localpart = room_alias
-            .strip_prefix('#')
-            .and_then(|s| s.split(':').next())
-            .unwrap_or(room_alias);
-
-        if localpart.starts_with("feishu_") {
-            return Ok(Some(json!({
-                "name": format!("Feishu {}", localpart),
-                "topic": "Bridged from Feishu",
-                "preset": "private_chat",
-                "visibility": "private",
-            })));
-        }
-
-        Ok(None)
-    }
-
     async fn thirdparty_protocol(&self, _protocol: &str) -> anyhow::Result<Option<Value>> {
         Ok(Some(json!({
             "user_fields": ["id", "name"],
@@ -2501,24 +6351,92 @@ impl AppserviceHandler for BridgeHandler {
     async fn thirdparty_user_remote(
         &self,
         _protocol: &str,
-        _fields: &HashMap<String, String>,
+        fields: &HashMap<String, String>,
     ) -> anyhow::Result<Vec<Value>> {
-        Ok(Vec::new())
+        self.bridge.thirdparty_users(fields).await
     }
 
-    async fn thirdparty_user_matrix(&self, _user_id: &str) -> anyhow::Result<Vec<Value>> {
-        Ok(Vec::new())
+    async fn thirdparty_user_matrix(&self, user_id: &str) -> anyhow::Result<Vec<Value>> {
+        self.bridge.thirdparty_users_for_matrix_id(user_id).await
     }
 
     async fn thirdparty_location_remote(
         &self,
         _protocol: &str,
-        _fields: &HashMap<String, String>,
+        fields: &HashMap<String, String>,
     ) -> anyhow::Result<Vec<Value>> {
-        Ok(Vec::new())
+        self.bridge.thirdparty_locations(fields).await
+    }
+
+    async fn thirdparty_location_matrix(&self, alias: &str) -> anyhow::Result<Vec<Value>> {
+        self.bridge.thirdparty_locations_for_alias(alias).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use std::collections::HashMap;
+
+    use chrono::{Duration, Utc};
+
+    use super::{keep_latest_read_receipt, matrix_relates_to};
+    use crate::database::MessageMapping;
+
+    fn read_message(event_id: &str, age_secs: i64) -> MessageMapping {
+        let mut mapping = MessageMapping::new(
+            event_id.to_string(),
+            format!("om_{}", event_id.trim_start_matches('$')),
+            "!room:localhost".to_string(),
+            "@feishu_u_bob:localhost".to_string(),
+            "u_bob".to_string(),
+        );
+        mapping.created_at = Utc::now() - Duration::seconds(age_secs);
+        mapping
+    }
+
+    #[test]
+    fn deferred_read_receipts_keep_the_newest_message_per_reader() {
+        let mut readers = HashMap::new();
+        keep_latest_read_receipt(&mut readers, "@alice:localhost", read_message("$older", 20));
+        keep_latest_read_receipt(&mut readers, "@alice:localhost", read_message("$newest", 5));
+        keep_latest_read_receipt(&mut readers, "@alice:localhost", read_message("$late", 10));
+        keep_latest_read_receipt(&mut readers, "@sam:localhost", read_message("$older", 20));
+
+        assert_eq!(readers.len(), 2);
+        assert_eq!(readers["@alice:localhost"].matrix_event_id, "$newest");
+        assert_eq!(readers["@sam:localhost"].matrix_event_id, "$older");
+    }
+
+    #[test]
+    fn matrix_relates_to_marks_only_root_replies_as_fallback() {
+        assert_eq!(
+            matrix_relates_to(Some("$root"), None),
+            Some(json!({
+                "rel_type": "m.thread",
+                "event_id": "$root",
+                "is_falling_back": true,
+                "m.in_reply_to": { "event_id": "$root" }
+            }))
+        );
+        assert_eq!(
+            matrix_relates_to(Some("$root"), Some("$middle")),
+            Some(json!({
+                "rel_type": "m.thread",
+                "event_id": "$root",
+                "is_falling_back": false,
+                "m.in_reply_to": { "event_id": "$middle" }
+            }))
+        );
     }
 
-    async fn thirdparty_location_matrix(&self, _alias: &str) -> anyhow::Result<Vec<Value>> {
-        Ok(Vec::new())
+    #[test]
+    fn matrix_relates_to_without_thread_is_a_plain_reply() {
+        assert_eq!(
+            matrix_relates_to(None, Some("$parent")),
+            Some(json!({ "m.in_reply_to": { "event_id": "$parent" } }))
+        );
+        assert_eq!(matrix_relates_to(None, None), None);
     }
 }
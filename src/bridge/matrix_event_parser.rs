@@ -23,9 +23,15 @@ pub fn outbound_content_hash(event: &MatrixEvent, outbound: &OutboundFeishuMessa
     hasher.update(event.sender.as_bytes());
     hasher.update(outbound.msg_type.as_bytes());
     hasher.update(outbound.content.as_bytes());
+    if let Some(formatted_body) = &outbound.formatted_body {
+        hasher.update(formatted_body.as_bytes());
+    }
     if let Some(reply_to) = &outbound.reply_to {
         hasher.update(reply_to.as_bytes());
     }
+    if let Some(thread_root) = &outbound.thread_root {
+        hasher.update(thread_root.as_bytes());
+    }
     if let Some(edit_of) = &outbound.edit_of {
         hasher.update(edit_of.as_bytes());
     }
@@ -46,6 +52,8 @@ pub fn outbound_delivery_uuid(event_id: Option<&str>, content_hash: &str) -> Str
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use chrono::Utc;
     use serde_json::json;
 
@@ -65,6 +73,7 @@ mod tests {
                 "msgtype": "m.text",
                 "body": "hello"
             })),
+            prev_content: None,
             timestamp: Some(Utc::now().timestamp_millis().to_string()),
         };
 
@@ -84,13 +93,17 @@ mod tests {
             sender: "@alice:example.com".to_string(),
             state_key: None,
             content: None,
+            prev_content: None,
             timestamp: None,
         };
         let outbound_a = OutboundFeishuMessage {
             content: "hello".to_string(),
+            formatted_body: None,
             msg_type: "text".to_string(),
             reply_to: None,
+            thread_root: None,
             edit_of: None,
+            mentions: HashMap::new(),
             attachments: vec![MessageAttachment {
                 name: "a.txt".to_string(),
                 url: "mxc://example/a".to_string(),
@@ -10,6 +10,9 @@ use crate::bridge::message_flow::{MessageAttachment, OutboundFeishuMessage};
 use crate::config::Config;
 use crate::database::{MediaCacheEntry, MediaStore, MessageStore, RoomMapping};
 use crate::feishu::{FeishuMessageSendData, FeishuService};
+use crate::formatter::{
+    MatrixHtmlContext, convert_matrix_html_to_feishu, extract_matrix_html_image_sources,
+};
 
 pub struct MatrixToFeishuDispatcher {
     config: Arc<Config>,
@@ -45,11 +48,43 @@ impl MatrixToFeishuDispatcher {
             return Ok(None);
         }
 
-        let (msg_type, content) =
-            build_feishu_content_payload(&outbound.msg_type, &outbound.content)?;
+        let (msg_type, content) = self.build_outbound_payload(outbound).await?;
         let reply_in_thread = mapping.feishu_chat_type.eq_ignore_ascii_case("thread");
 
-        if self.config.bridge.bridge_matrix_reply {
+        if let Some(thread_root) = &outbound.thread_root {
+            // Prefer an explicit reply inside the thread, otherwise answer the root.
+            let mut target = None;
+            if let Some(reply_to) = &outbound.reply_to {
+                target = self
+                    .message_store
+                    .get_message_by_matrix_id(reply_to)
+                    .await?;
+            }
+            if target.is_none() {
+                target = self
+                    .message_store
+                    .get_message_by_matrix_id(thread_root)
+                    .await?;
+            }
+            if let Some(target) = target {
+                let response = self
+                    .feishu_service
+                    .reply_message(
+                        &target.feishu_message_id,
+                        &msg_type,
+                        content,
+                        true,
+                        delivery_uuid,
+                    )
+                    .await?;
+                return Ok(Some(response));
+            }
+            warn!(
+                matrix_thread_root = %thread_root,
+                feishu_chat_id = %mapping.feishu_chat_id,
+                "Matrix thread root has no Feishu mapping; sending as a regular message"
+            );
+        } else if self.config.bridge.bridge_matrix_reply {
             if let Some(reply_to) = &outbound.reply_to {
                 if let Some(reply_mapping) = self
                     .message_store
@@ -74,7 +109,7 @@ impl MatrixToFeishuDispatcher {
         let response = self
             .feishu_service
             .send_message(
-                "chat_id",
+                feishu_receive_id_type(mapping),
                 &mapping.feishu_chat_id,
                 &msg_type,
                 content,
@@ -107,8 +142,7 @@ impl MatrixToFeishuDispatcher {
             return Ok(());
         };
 
-        let (mut msg_type, content) =
-            build_feishu_content_payload(&outbound.msg_type, &outbound.content)?;
+        let (mut msg_type, content) = self.build_outbound_payload(outbound).await?;
 
         if msg_type != "text" && msg_type != "post" {
             msg_type = "text".to_string();
@@ -121,6 +155,64 @@ impl MatrixToFeishuDispatcher {
         Ok(())
     }
 
+    async fn build_outbound_payload(
+        &self,
+        outbound: &OutboundFeishuMessage,
+    ) -> anyhow::Result<(String, Value)> {
+        let Some(html) = outbound
+            .formatted_body
+            .as_deref()
+            .filter(|_| outbound.msg_type == "post")
+        else {
+            return build_feishu_content_payload(&outbound.msg_type, &outbound.content);
+        };
+
+        let mut context = MatrixHtmlContext {
+            feishu_user_ids: outbound.mentions.clone(),
+            ..Default::default()
+        };
+        for source in extract_matrix_html_image_sources(html) {
+            match self.upload_inline_image(&source).await {
+                Ok(image_key) => {
+                    context.image_keys.insert(source, image_key);
+                }
+                Err(err) => warn!(
+                    "Failed to upload inline Matrix image {} to Feishu: {}",
+                    source, err
+                ),
+            }
+        }
+
+        Ok((
+            "post".to_string(),
+            convert_matrix_html_to_feishu(html, &context),
+        ))
+    }
+
+    async fn upload_inline_image(&self, source_url: &str) -> anyhow::Result<String> {
+        if !self.config.bridge.allow_images {
+            anyhow::bail!("image bridging disabled");
+        }
+
+        let bytes = self.download_matrix_media(source_url).await?;
+        let media_hash = sha256_hex(&bytes);
+        if let Some(cached) = self
+            .media_store
+            .get_media_cache(&media_hash, "image")
+            .await?
+        {
+            return Ok(cached.resource_key);
+        }
+
+        let image_key = self
+            .feishu_service
+            .upload_image(bytes, guess_image_mime(source_url))
+            .await?;
+        self.upsert_media_cache(&media_hash, "image", &image_key)
+            .await?;
+        Ok(image_key)
+    }
+
     pub async fn forward_attachments_to_feishu(
         &self,
         mapping: &RoomMapping,
@@ -129,10 +221,7 @@ impl MatrixToFeishuDispatcher {
         let mut message_ids = Vec::new();
 
         for attachment in attachments {
-            match self
-                .forward_single_attachment(&mapping.feishu_chat_id, attachment)
-                .await
-            {
+            match self.forward_single_attachment(mapping, attachment).await {
                 Ok(message_id) => message_ids.push(message_id),
                 Err(err) => warn!(
                     "Failed to forward Matrix attachment {} to Feishu chat {}: {}",
@@ -146,7 +235,7 @@ impl MatrixToFeishuDispatcher {
 
     async fn forward_single_attachment(
         &self,
-        feishu_chat_id: &str,
+        mapping: &RoomMapping,
         attachment: &MessageAttachment,
     ) -> anyhow::Result<String> {
         let bytes = self.download_matrix_media(&attachment.url).await?;
@@ -163,19 +252,14 @@ impl MatrixToFeishuDispatcher {
                     .await?
                 {
                     return self
-                        .send_cached_resource(
-                            feishu_chat_id,
-                            "image",
-                            "image_key",
-                            &cached.resource_key,
-                        )
+                        .send_cached_resource(mapping, "image", "image_key", &cached.resource_key)
                         .await;
                 }
                 let mime = guess_image_mime(&attachment.name);
                 let image_key = self.feishu_service.upload_image(bytes, mime).await?;
                 self.upsert_media_cache(&media_hash, "image", &image_key)
                     .await?;
-                self.send_cached_resource(feishu_chat_id, "image", "image_key", &image_key)
+                self.send_cached_resource(mapping, "image", "image_key", &image_key)
                     .await
             }
             "m.audio" => {
@@ -188,12 +272,7 @@ impl MatrixToFeishuDispatcher {
                     .await?
                 {
                     return self
-                        .send_cached_resource(
-                            feishu_chat_id,
-                            "audio",
-                            "file_key",
-                            &cached.resource_key,
-                        )
+                        .send_cached_resource(mapping, "audio", "file_key", &cached.resource_key)
                         .await;
                 }
                 let file_type = guess_file_type(&attachment.name, "m.audio");
@@ -203,7 +282,7 @@ impl MatrixToFeishuDispatcher {
                     .await?;
                 self.upsert_media_cache(&media_hash, "audio", &file_key)
                     .await?;
-                self.send_cached_resource(feishu_chat_id, "audio", "file_key", &file_key)
+                self.send_cached_resource(mapping, "audio", "file_key", &file_key)
                     .await
             }
             "m.video" => {
@@ -216,12 +295,7 @@ impl MatrixToFeishuDispatcher {
                     .await?
                 {
                     return self
-                        .send_cached_resource(
-                            feishu_chat_id,
-                            "media",
-                            "file_key",
-                            &cached.resource_key,
-                        )
+                        .send_cached_resource(mapping, "media", "file_key", &cached.resource_key)
                         .await;
                 }
                 let file_type = guess_file_type(&attachment.name, "m.video");
@@ -231,7 +305,7 @@ impl MatrixToFeishuDispatcher {
                     .await?;
                 self.upsert_media_cache(&media_hash, "media", &file_key)
                     .await?;
-                self.send_cached_resource(feishu_chat_id, "media", "file_key", &file_key)
+                self.send_cached_resource(mapping, "media", "file_key", &file_key)
                     .await
             }
             _ => {
@@ -244,12 +318,7 @@ impl MatrixToFeishuDispatcher {
                     .await?
                 {
                     return self
-                        .send_cached_resource(
-                            feishu_chat_id,
-                            "file",
-                            "file_key",
-                            &cached.resource_key,
-                        )
+                        .send_cached_resource(mapping, "file", "file_key", &cached.resource_key)
                         .await;
                 }
                 let file_type = guess_file_type(&attachment.name, "m.file");
@@ -259,7 +328,7 @@ impl MatrixToFeishuDispatcher {
                     .await?;
                 self.upsert_media_cache(&media_hash, "file", &file_key)
                     .await?;
-                self.send_cached_resource(feishu_chat_id, "file", "file_key", &file_key)
+                self.send_cached_resource(mapping, "file", "file_key", &file_key)
                     .await
             }
         }
@@ -267,7 +336,7 @@ impl MatrixToFeishuDispatcher {
 
     async fn send_cached_resource(
         &self,
-        feishu_chat_id: &str,
+        mapping: &RoomMapping,
         msg_type: &str,
         key_field: &str,
         resource_key: &str,
@@ -276,8 +345,8 @@ impl MatrixToFeishuDispatcher {
         let response = self
             .feishu_service
             .send_message(
-                "chat_id",
-                feishu_chat_id,
+                feishu_receive_id_type(mapping),
+                &mapping.feishu_chat_id,
                 msg_type,
                 payload,
                 Some(Uuid::new_v4().to_string()),
@@ -354,6 +423,25 @@ impl MatrixToFeishuDispatcher {
     }
 }
 
+pub fn feishu_receive_id_type(mapping: &RoomMapping) -> &'static str {
+    let receive_id = mapping.feishu_chat_id.as_str();
+    if !mapping.feishu_chat_type.eq_ignore_ascii_case("p2p") || receive_id.starts_with("oc_") {
+        "chat_id"
+    } else {
+        feishu_user_id_type(receive_id)
+    }
+}
+
+pub fn feishu_user_id_type(user_id: &str) -> &'static str {
+    if user_id.starts_with("ou_") {
+        "open_id"
+    } else if user_id.starts_with("on_") {
+        "union_id"
+    } else {
+        "user_id"
+    }
+}
+
 fn build_feishu_content_payload(msg_type: &str, body: &str) -> anyhow::Result<(String, Value)> {
     if msg_type == "post" {
         let post = crate::formatter::create_feishu_rich_text(body);
@@ -404,7 +492,7 @@ fn guess_file_type(name: &str, kind: &str) -> &'static str {
     }
 }
 
-fn sha256_hex(bytes: &[u8]) -> String {
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
     let mut hasher = Sha256::new();
     hasher.update(bytes);
     hex::encode(hasher.finalize())
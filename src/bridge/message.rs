@@ -12,6 +12,10 @@ pub struct BridgeMessage {
     pub thread_id: Option<String>,
     pub root_id: Option<String>,
     pub parent_id: Option<String>,
+    #[serde(default)]
+    pub rich_content: Option<serde_json::Value>,
+    #[serde(default)]
+    pub mentions: Vec<MessageMention>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +30,14 @@ pub enum MessageType {
     Card,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageMention {
+    // Placeholder used in the message content, e.g. `@_user_1`
+    pub key: String,
+    pub feishu_user_id: String,
+    pub name: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Attachment {
     pub id: String,
@@ -48,6 +60,8 @@ impl BridgeMessage {
             thread_id: None,
             root_id: None,
             parent_id: None,
+            rich_content: None,
+            mentions: Vec::new(),
         }
     }
 
@@ -63,6 +77,8 @@ impl BridgeMessage {
             thread_id: None,
             root_id: None,
             parent_id: None,
+            rich_content: None,
+            mentions: Vec::new(),
         }
     }
 
@@ -78,6 +94,8 @@ impl BridgeMessage {
             thread_id: None,
             root_id: None,
             parent_id: None,
+            rich_content: None,
+            mentions: Vec::new(),
         }
     }
 }
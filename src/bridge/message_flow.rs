@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use serde_json::Value;
@@ -10,8 +11,16 @@ const ATTACHMENT_TYPES: &[&str] = &["m.image", "m.audio", "m.video", "m.file", "
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MessageRelation {
-    Reply { event_id: String },
-    Replace { event_id: String },
+    Reply {
+        event_id: String,
+    },
+    Replace {
+        event_id: String,
+    },
+    Thread {
+        root_event_id: String,
+        reply_to: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -21,12 +30,20 @@ pub struct MessageAttachment {
     pub kind: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixMention {
+    pub user_id: String,
+    pub display_name: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct MatrixInboundMessage {
     pub event_id: Option<String>,
     pub room_id: String,
     pub sender: String,
     pub body: String,
+    pub formatted_body: Option<String>,
+    pub mentions: Vec<MatrixMention>,
     pub relation: Option<MessageRelation>,
     pub attachments: Vec<MessageAttachment>,
 }
@@ -45,10 +62,14 @@ pub struct FeishuInboundMessage {
 #[derive(Debug, Clone)]
 pub struct OutboundFeishuMessage {
     pub content: String,
+    pub formatted_body: Option<String>,
     pub msg_type: String,
     pub reply_to: Option<String>,
+    pub thread_root: Option<String>,
     pub edit_of: Option<String>,
     pub attachments: Vec<MessageAttachment>,
+    // Matrix user ID -> Feishu user ID for users mentioned in the message
+    pub mentions: HashMap<String, String>,
 }
 
 impl OutboundFeishuMessage {
@@ -148,6 +169,16 @@ impl MessageFlow {
             })
             .to_string();
 
+        let formatted_body = content_for_body
+            .get("formatted_body")
+            .and_then(Value::as_str)
+            .filter(|_| {
+                content_for_body.get("format").and_then(Value::as_str)
+                    == Some("org.matrix.custom.html")
+            })
+            .map(ToOwned::to_owned);
+
+        let mentions = parse_mentions(content_for_body, formatted_body.as_deref());
         let relation = parse_relation(content);
         let attachments = parse_attachments(content_for_body, &msgtype);
 
@@ -160,6 +191,8 @@ impl MessageFlow {
             room_id: String::new(),
             sender: String::new(),
             body,
+            formatted_body,
+            mentions,
             relation,
             attachments,
         })
@@ -168,6 +201,11 @@ impl MessageFlow {
     pub fn matrix_to_feishu(&self, message: &MatrixInboundMessage) -> OutboundFeishuMessage {
         let reply_to = match &message.relation {
             Some(MessageRelation::Reply { event_id }) => Some(event_id.clone()),
+            Some(MessageRelation::Thread { reply_to, .. }) => reply_to.clone(),
+            _ => None,
+        };
+        let thread_root = match &message.relation {
+            Some(MessageRelation::Thread { root_event_id, .. }) => Some(root_event_id.clone()),
             _ => None,
         };
         let edit_of = match &message.relation {
@@ -182,13 +220,20 @@ impl MessageFlow {
         } else {
             "text".to_string()
         };
+        let formatted_body = message
+            .formatted_body
+            .clone()
+            .filter(|_| self.config.bridge.allow_html && msg_type == "post");
 
         OutboundFeishuMessage {
             content,
+            formatted_body,
             msg_type,
             reply_to,
+            thread_root,
             edit_of,
             attachments,
+            mentions: HashMap::new(),
         }
     }
 
@@ -216,7 +261,7 @@ impl MessageFlow {
         let mut result = content.to_string();
 
         if self.config.bridge.allow_html {
-            result = crate::formatter::convert_matrix_html_to_feishu(&result);
+            result = crate::formatter::matrix_html_to_plain_text(&result);
         }
 
         if self.config.bridge.allow_markdown {
@@ -262,8 +307,56 @@ impl MessageFlow {
     }
 }
 
+fn parse_mentions(content: &Value, formatted_body: Option<&str>) -> Vec<MatrixMention> {
+    let mut mentions: Vec<MatrixMention> = formatted_body
+        .map(crate::formatter::extract_matrix_html_mentions)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(user_id, label)| MatrixMention {
+            user_id,
+            display_name: Some(label).filter(|label| !label.is_empty()),
+        })
+        .collect();
+
+    let intentional = content
+        .pointer("/m.mentions/user_ids")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str);
+    for user_id in intentional {
+        if !mentions.iter().any(|mention| mention.user_id == user_id) {
+            mentions.push(MatrixMention {
+                user_id: user_id.to_string(),
+                display_name: None,
+            });
+        }
+    }
+    mentions
+}
+
 fn parse_relation(content: &Value) -> Option<MessageRelation> {
     let relates_to = content.get("m.relates_to")?;
+    let rel_type = relates_to.get("rel_type").and_then(Value::as_str);
+    if rel_type == Some("m.thread")
+        && let Some(root_event_id) = relates_to.get("event_id").and_then(Value::as_str)
+    {
+        // A falling-back reply only points at the latest thread event for old clients.
+        let is_falling_back = relates_to
+            .get("is_falling_back")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let reply_to = relates_to
+            .get("m.in_reply_to")
+            .and_then(|inner| inner.get("event_id"))
+            .and_then(Value::as_str)
+            .filter(|event_id| !is_falling_back && *event_id != root_event_id)
+            .map(ToOwned::to_owned);
+        return Some(MessageRelation::Thread {
+            root_event_id: root_event_id.to_string(),
+            reply_to,
+        });
+    }
     if let Some(reply_event_id) = relates_to
         .get("m.in_reply_to")
         .and_then(|inner| inner.get("event_id"))
@@ -273,7 +366,6 @@ fn parse_relation(content: &Value) -> Option<MessageRelation> {
             event_id: reply_event_id.to_string(),
         });
     }
-    let rel_type = relates_to.get("rel_type").and_then(Value::as_str);
     if rel_type == Some("m.replace") {
         if let Some(edit_event_id) = relates_to.get("event_id").and_then(Value::as_str) {
             return Some(MessageRelation::Replace {
@@ -308,7 +400,7 @@ fn parse_attachments(content: &Value, msgtype: &str) -> Vec<MessageAttachment> {
 mod tests {
     use serde_json::json;
 
-    use super::{MessageFlow, MessageRelation};
+    use super::{MatrixMention, MessageFlow, MessageRelation};
 
     #[test]
     fn parse_matrix_event_extracts_reply_and_attachment() {
@@ -335,6 +427,32 @@ mod tests {
         assert_eq!(parsed.attachments[0].url, "mxc://example.org/cat");
     }
 
+    #[test]
+    fn parse_matrix_event_extracts_thread_relation() {
+        let content = json!({
+            "msgtype": "m.text",
+            "body": "in thread",
+            "m.relates_to": {
+                "rel_type": "m.thread",
+                "event_id": "$root",
+                "is_falling_back": true,
+                "m.in_reply_to": {
+                    "event_id": "$latest"
+                }
+            }
+        });
+
+        let parsed = MessageFlow::parse_matrix_event("m.room.message", &content)
+            .expect("matrix message should parse");
+        assert_eq!(
+            parsed.relation,
+            Some(MessageRelation::Thread {
+                root_event_id: "$root".to_string(),
+                reply_to: None
+            })
+        );
+    }
+
     #[test]
     fn parse_matrix_event_extracts_edit() {
         let content = json!({
@@ -359,4 +477,62 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn parse_matrix_event_keeps_html_formatted_body_of_edits() {
+        let content = json!({
+            "msgtype": "m.text",
+            "body": "* *new*",
+            "format": "org.matrix.custom.html",
+            "formatted_body": "* <em>new</em>",
+            "m.relates_to": {
+                "rel_type": "m.replace",
+                "event_id": "$old"
+            },
+            "m.new_content": {
+                "msgtype": "m.text",
+                "body": "*new*",
+                "format": "org.matrix.custom.html",
+                "formatted_body": "<em>new</em>"
+            }
+        });
+
+        let parsed = MessageFlow::parse_matrix_event("m.room.message", &content)
+            .expect("matrix message should parse");
+        assert_eq!(parsed.formatted_body.as_deref(), Some("<em>new</em>"));
+
+        let plain = json!({ "msgtype": "m.text", "body": "hi", "formatted_body": "<b>hi</b>" });
+        let parsed = MessageFlow::parse_matrix_event("m.room.message", &plain)
+            .expect("matrix message should parse");
+        assert_eq!(parsed.formatted_body, None);
+    }
+
+    #[test]
+    fn parse_matrix_event_collects_pills_and_intentional_mentions() {
+        let content = json!({
+            "msgtype": "m.text",
+            "body": "Alice: hi",
+            "format": "org.matrix.custom.html",
+            "formatted_body": "<a href=\"https://matrix.to/#/@alice:example.com\">Alice</a>: hi",
+            "m.mentions": {
+                "user_ids": ["@alice:example.com", "@bob:example.com"]
+            }
+        });
+
+        let parsed = MessageFlow::parse_matrix_event("m.room.message", &content)
+            .expect("matrix message should parse");
+        assert_eq!(
+            parsed.mentions,
+            vec![
+                MatrixMention {
+                    user_id: "@alice:example.com".to_string(),
+                    display_name: Some("Alice".to_string()),
+                },
+                MatrixMention {
+                    user_id: "@bob:example.com".to_string(),
+                    display_name: None,
+                },
+            ]
+        );
+    }
 }
@@ -4,6 +4,8 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
+use crate::database::Portal;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BridgePortal {
     pub id: Option<i64>,
@@ -27,6 +29,23 @@ pub enum RoomType {
     Direct,
 }
 
+impl RoomType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RoomType::Group => "group",
+            RoomType::Direct => "direct",
+        }
+    }
+
+    pub fn from_name(value: &str) -> Self {
+        if value.eq_ignore_ascii_case("direct") {
+            RoomType::Direct
+        } else {
+            RoomType::Group
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BridgeInfo {
     pub bridgebot: String,
@@ -50,7 +69,7 @@ impl BridgePortal {
             relay_user_id: None,
             creator_mxid,
             bridge_info: BridgeInfo {
-                bridgebot: "@feishubot:localhost".to_string(),
+                bridgebot: creator.clone(),
                 creator,
                 protocol: "feishu".to_string(),
                 channel: HashMap::new(),
@@ -60,6 +79,107 @@ impl BridgePortal {
         }
     }
 
+    pub fn from_record(record: Portal) -> Self {
+        let bridge_info =
+            serde_json::from_str(&record.bridge_info).unwrap_or_else(|_| BridgeInfo {
+                bridgebot: record.creator_mxid.clone(),
+                creator: record.creator_mxid.clone(),
+                protocol: "feishu".to_string(),
+                channel: HashMap::new(),
+            });
+        Self {
+            id: Some(record.id),
+            feishu_room_id: record.feishu_room_id,
+            mxid: record.mxid,
+            name: record.name,
+            topic: record.topic,
+            avatar_url: record.avatar_url,
+            encrypted: record.encrypted,
+            room_type: RoomType::from_name(&record.room_type),
+            relay_user_id: record.relay_user_id,
+            creator_mxid: record.creator_mxid,
+            bridge_info,
+            creation_content: record
+                .creation_content
+                .and_then(|value| serde_json::from_str(&value).ok()),
+            last_event: record.last_event,
+        }
+    }
+
+    pub fn to_record(&self) -> Portal {
+        Portal {
+            id: self.id.unwrap_or_default(),
+            feishu_room_id: self.feishu_room_id.clone(),
+            mxid: self.mxid.clone(),
+            name: self.name.clone(),
+            topic: self.topic.clone(),
+            avatar_url: self.avatar_url.clone(),
+            encrypted: self.encrypted,
+            room_type: self.room_type.as_str().to_string(),
+            relay_user_id: self.relay_user_id.clone(),
+            creator_mxid: self.creator_mxid.clone(),
+            bridge_info: serde_json::to_string(&self.bridge_info)
+                .unwrap_or_else(|_| "{}".to_string()),
+            creation_content: self
+                .creation_content
+                .as_ref()
+                .map(|value| value.to_string()),
+            last_event: self.last_event.clone(),
+        }
+    }
+
+    pub fn bridge_state_key(&self) -> String {
+        match self.tenant_key() {
+            Some(tenant_key) => format!(
+                "{}://{}/{}",
+                self.bridge_info.protocol, tenant_key, self.feishu_room_id
+            ),
+            None => format!("{}://{}", self.bridge_info.protocol, self.feishu_room_id),
+        }
+    }
+
+    /// Content for `m.bridge` / `uk.half-shot.bridge` (MSC2346).
+    pub fn bridge_state_content(&self) -> serde_json::Value {
+        let mut channel = serde_json::json!({
+            "id": self.feishu_room_id,
+            "displayname": self.name,
+        });
+        if let Some(avatar_url) = &self.avatar_url {
+            channel["avatar_url"] = serde_json::Value::String(avatar_url.clone());
+        }
+        for key in ["chat_mode", "chat_type"] {
+            if let Some(value) = self.bridge_info.channel.get(key) {
+                channel[key] = value.clone();
+            }
+        }
+
+        let mut content = serde_json::json!({
+            "bridgebot": self.bridge_info.bridgebot,
+            "creator": self.bridge_info.creator,
+            "protocol": {
+                "id": self.bridge_info.protocol,
+                "displayname": "Feishu",
+                "external_url": "https://www.feishu.cn"
+            },
+            "channel": channel,
+        });
+        if let Some(tenant_key) = self.tenant_key() {
+            content["network"] = serde_json::json!({
+                "id": tenant_key,
+                "displayname": tenant_key,
+            });
+        }
+        content
+    }
+
+    fn tenant_key(&self) -> Option<&str> {
+        self.bridge_info
+            .channel
+            .get("tenant_key")
+            .and_then(serde_json::Value::as_str)
+            .filter(|value| !value.is_empty())
+    }
+
     pub fn handle_feishu_message(&self, message: super::message::BridgeMessage) -> Result<()> {
         let matrix_text = crate::formatter::convert_feishu_content_to_matrix_html(&message.content);
         info!(
@@ -78,3 +198,83 @@ impl BridgePortal {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::BridgePortal;
+
+    fn portal() -> BridgePortal {
+        BridgePortal::new(
+            "oc_chat".to_string(),
+            "!room:example.com".to_string(),
+            "Team Chat".to_string(),
+            "@feishubot:example.com".to_string(),
+        )
+    }
+
+    #[test]
+    fn bridge_state_key_includes_tenant_when_known() {
+        let mut portal = portal();
+        assert_eq!(portal.bridge_state_key(), "feishu://oc_chat");
+
+        portal
+            .bridge_info
+            .channel
+            .insert("tenant_key".to_string(), json!("tenant_a"));
+        assert_eq!(portal.bridge_state_key(), "feishu://tenant_a/oc_chat");
+
+        portal
+            .bridge_info
+            .channel
+            .insert("tenant_key".to_string(), json!(""));
+        assert_eq!(portal.bridge_state_key(), "feishu://oc_chat");
+    }
+
+    #[test]
+    fn bridge_state_content_describes_channel_and_network() {
+        let mut portal = portal();
+        portal.avatar_url = Some("mxc://example.com/avatar".to_string());
+        portal
+            .bridge_info
+            .channel
+            .insert("tenant_key".to_string(), json!("tenant_a"));
+        portal
+            .bridge_info
+            .channel
+            .insert("chat_mode".to_string(), json!("group"));
+
+        assert_eq!(
+            portal.bridge_state_content(),
+            json!({
+                "bridgebot": "@feishubot:example.com",
+                "creator": "@feishubot:example.com",
+                "protocol": {
+                    "id": "feishu",
+                    "displayname": "Feishu",
+                    "external_url": "https://www.feishu.cn"
+                },
+                "channel": {
+                    "id": "oc_chat",
+                    "displayname": "Team Chat",
+                    "avatar_url": "mxc://example.com/avatar",
+                    "chat_mode": "group"
+                },
+                "network": {
+                    "id": "tenant_a",
+                    "displayname": "tenant_a"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn bridge_state_content_omits_unknown_avatar_and_network() {
+        let content = portal().bridge_state_content();
+
+        assert!(content.get("network").is_none());
+        assert!(content["channel"].get("avatar_url").is_none());
+        assert!(content["channel"].get("chat_mode").is_none());
+    }
+}
@@ -37,6 +37,7 @@ pub enum ProvisioningError {
     Declined,
     AlreadyExists,
     NotFound,
+    Upstream(String),
     Other(String),
 }
 
@@ -47,6 +48,7 @@ impl std::fmt::Display for ProvisioningError {
             ProvisioningError::Declined => write!(f, "bridge request was declined"),
             ProvisioningError::AlreadyExists => write!(f, "bridge already exists"),
             ProvisioningError::NotFound => write!(f, "bridge request not found"),
+            ProvisioningError::Upstream(msg) => write!(f, "upstream request failed: {}", msg),
             ProvisioningError::Other(msg) => write!(f, "{}", msg),
         }
     }
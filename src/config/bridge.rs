@@ -83,10 +83,19 @@ pub struct BridgeConfig {
     pub bridge_matrix_redactions: bool,
     #[serde(default)]
     pub bridge_matrix_leave: bool,
+    /// Feishu action when a linked Matrix user leaves a portal: notify (default), ignore or remove
+    #[serde(default = "default_matrix_leave_action")]
+    pub matrix_leave_action: String,
     #[serde(default)]
     pub bridge_feishu_join: bool,
     #[serde(default)]
     pub bridge_feishu_leave: bool,
+    #[serde(default)]
+    pub bridge_feishu_reactions: bool,
+    #[serde(default)]
+    pub bridge_feishu_read_receipts: bool,
+    #[serde(default)]
+    pub bridge_matrix_power_levels: bool,
 
     /// Message formatting
     #[serde(default)]
@@ -148,12 +157,58 @@ pub struct BridgeConfig {
     /// Convert Feishu cards to Matrix
     #[serde(default = "default_true")]
     pub convert_cards: bool,
+
+    /// Group portals into a Matrix Space per Feishu tenant; existing bridged rooms are
+    /// backfilled on startup
+    #[serde(default = "default_true")]
+    pub enable_tenant_spaces: bool,
+    /// Additionally maintain a personal Matrix Space per Matrix user
+    #[serde(default)]
+    pub enable_user_spaces: bool,
+
+    /// What to do with the Matrix room when its Feishu chat is disbanded: archive, tombstone or kick
+    #[serde(default = "default_disband_policy")]
+    pub disband_policy: String,
+}
+
+impl BridgeConfig {
+    /// Resolve the permission level for a Matrix user: exact MXID, then homeserver, then `*`.
+    pub fn permission_level(&self, matrix_user_id: &str) -> Option<&str> {
+        let server = matrix_user_id
+            .split_once(':')
+            .map(|(_, server)| server)
+            .unwrap_or_default();
+        self.permissions
+            .get(matrix_user_id)
+            .or_else(|| self.permissions.get(server))
+            .or_else(|| self.permissions.get("*"))
+            .map(|level| level.trim())
+    }
+
+    pub fn is_admin(&self, matrix_user_id: &str) -> bool {
+        self.permission_level(matrix_user_id)
+            .is_some_and(|level| level.eq_ignore_ascii_case("admin"))
+    }
+
+    pub fn has_full_access(&self, matrix_user_id: &str) -> bool {
+        self.permission_level(matrix_user_id).is_some_and(|level| {
+            level.eq_ignore_ascii_case("full") || level.eq_ignore_ascii_case("admin")
+        })
+    }
 }
 
 fn default_username_template() -> String {
     "feishu_{{.}}".to_string()
 }
 
+fn default_matrix_leave_action() -> String {
+    "notify".to_string()
+}
+
+fn default_disband_policy() -> String {
+    "archive".to_string()
+}
+
 fn default_event_mode() -> String {
     "long_connection".to_string()
 }
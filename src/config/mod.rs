@@ -259,6 +259,26 @@ impl Config {
             validate_not_placeholder("bridge.listen_secret", &self.bridge.listen_secret)?;
         }
 
+        if !matches!(
+            self.bridge.disband_policy.as_str(),
+            "archive" | "tombstone" | "kick"
+        ) {
+            return Err(ConfigError::InvalidConfig(format!(
+                "bridge.disband_policy='{}' is invalid; expected 'archive', 'tombstone' or 'kick'",
+                self.bridge.disband_policy
+            )));
+        }
+
+        if !matches!(
+            self.bridge.matrix_leave_action.as_str(),
+            "remove" | "notify" | "ignore"
+        ) {
+            return Err(ConfigError::InvalidConfig(format!(
+                "bridge.matrix_leave_action='{}' is invalid; expected 'remove', 'notify' or 'ignore'",
+                self.bridge.matrix_leave_action
+            )));
+        }
+
         if self.bridge.enable_rich_text == false && self.bridge.allow_plain_text == false {
             return Err(ConfigError::InvalidConfig(
                 "bridge.enable_rich_text and bridge.allow_plain_text cannot both be false"
@@ -368,6 +388,9 @@ impl Config {
         self.bridge.app_id = self.bridge.app_id.trim().to_string();
         self.bridge.app_secret = self.bridge.app_secret.trim().to_string();
         self.bridge.event_mode = self.bridge.event_mode.trim().to_ascii_lowercase();
+        self.bridge.disband_policy = self.bridge.disband_policy.trim().to_ascii_lowercase();
+        self.bridge.matrix_leave_action =
+            self.bridge.matrix_leave_action.trim().to_ascii_lowercase();
         self.bridge.listen_address = self.bridge.listen_address.trim().to_string();
         self.bridge.listen_secret = self.bridge.listen_secret.trim().to_string();
         self.bridge.long_connection_domain = self
@@ -11,9 +11,13 @@ use diesel::r2d2::{ConnectionManager, Pool};
 use diesel::sqlite::SqliteConnection;
 pub use error::{DatabaseError, DatabaseResult};
 pub use models::{
-    DeadLetterEvent, MediaCacheEntry, MessageMapping, ProcessedEvent, RoomMapping, UserMapping,
+    DeadLetterEvent, DirectChat, MediaCacheEntry, MessageMapping, Portal, ProcessedEvent,
+    ReactionMapping, RoomMapping, UserMapping,
+};
+pub use stores::{
+    DeadLetterStore, DirectChatStore, EventStore, MediaStore, MessageStore, PortalStore,
+    ReactionStore, RoomStore, SpaceStore, UserStore,
 };
-pub use stores::{DeadLetterStore, EventStore, MediaStore, MessageStore, RoomStore, UserStore};
 use tracing::info;
 
 pub type SqlitePool = Pool<ConnectionManager<SqliteConnection>>;
@@ -141,6 +145,14 @@ CREATE TABLE IF NOT EXISTS users (
     next_batch TEXT
 );
 
+CREATE TABLE IF NOT EXISTS tenant_spaces (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tenant_key TEXT NOT NULL UNIQUE,
+    space_room TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
 CREATE TABLE IF NOT EXISTS puppets (
     id INTEGER PRIMARY KEY,
     feishu_id TEXT NOT NULL UNIQUE,
@@ -218,6 +230,18 @@ CREATE TABLE IF NOT EXISTS message_mappings (
     created_at TEXT NOT NULL DEFAULT (datetime('now'))
 );
 
+CREATE TABLE IF NOT EXISTS reaction_mappings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    matrix_event_id TEXT NOT NULL UNIQUE,
+    feishu_message_id TEXT NOT NULL,
+    feishu_reaction_id TEXT NOT NULL,
+    room_id TEXT NOT NULL,
+    sender_mxid TEXT NOT NULL,
+    emoji TEXT NOT NULL,
+    origin TEXT NOT NULL DEFAULT 'matrix',
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
 CREATE TABLE IF NOT EXISTS processed_events (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     event_id TEXT NOT NULL UNIQUE,
@@ -251,6 +275,16 @@ CREATE TABLE IF NOT EXISTS media_cache (
     UNIQUE(content_hash, media_kind)
 );
 
+CREATE TABLE IF NOT EXISTS direct_chats (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    feishu_user_id TEXT NOT NULL UNIQUE,
+    feishu_chat_id TEXT NOT NULL,
+    matrix_room_id TEXT NOT NULL UNIQUE,
+    owner_mxid TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
 CREATE INDEX IF NOT EXISTS idx_room_mappings_matrix_id ON room_mappings(matrix_room_id);
 CREATE INDEX IF NOT EXISTS idx_room_mappings_feishu_id ON room_mappings(feishu_chat_id);
 CREATE INDEX IF NOT EXISTS idx_user_mappings_matrix_id ON user_mappings(matrix_user_id);
@@ -260,6 +294,10 @@ CREATE INDEX IF NOT EXISTS idx_message_mappings_feishu_id ON message_mappings(fe
 CREATE INDEX IF NOT EXISTS idx_message_mappings_room ON message_mappings(room_id);
 CREATE INDEX IF NOT EXISTS idx_message_mappings_content_hash
     ON message_mappings(content_hash);
+CREATE INDEX IF NOT EXISTS idx_reaction_mappings_feishu_message
+    ON reaction_mappings(feishu_message_id);
+CREATE INDEX IF NOT EXISTS idx_reaction_mappings_feishu_reaction
+    ON reaction_mappings(feishu_reaction_id);
 CREATE INDEX IF NOT EXISTS idx_processed_events_event_id ON processed_events(event_id);
 CREATE INDEX IF NOT EXISTS idx_dead_letters_status ON dead_letters(status);
 CREATE INDEX IF NOT EXISTS idx_dead_letters_created_at ON dead_letters(created_at);
@@ -12,6 +12,23 @@ pub struct RoomMapping {
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Portal {
+    pub id: i64,
+    pub feishu_room_id: String,
+    pub mxid: String,
+    pub name: String,
+    pub topic: Option<String>,
+    pub avatar_url: Option<String>,
+    pub encrypted: bool,
+    pub room_type: String,
+    pub relay_user_id: Option<String>,
+    pub creator_mxid: String,
+    pub bridge_info: String,
+    pub creation_content: Option<String>,
+    pub last_event: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserMapping {
     pub id: i64,
@@ -38,6 +55,20 @@ pub struct MessageMapping {
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReactionMapping {
+    pub id: i64,
+    pub matrix_event_id: String,
+    pub feishu_message_id: String,
+    pub feishu_reaction_id: String,
+    pub room_id: String,
+    pub sender_mxid: String,
+    pub emoji: String,
+    /// Side the reaction was first made on: `matrix` or `feishu`.
+    pub origin: String,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessedEvent {
     pub id: i64,
@@ -73,6 +104,19 @@ pub struct MediaCacheEntry {
     pub updated_at: DateTime<Utc>,
 }
 
+/// A Feishu p2p chat bridged to a Matrix DM. Feishu keeps one p2p chat per user
+/// with the bot, so each Feishu user has at most one owner on the Matrix side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectChat {
+    pub id: i64,
+    pub feishu_user_id: String,
+    pub feishu_chat_id: String,
+    pub matrix_room_id: String,
+    pub owner_mxid: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 impl RoomMapping {
     pub fn new(
         matrix_room_id: String,
@@ -92,6 +136,26 @@ impl RoomMapping {
     }
 }
 
+impl DirectChat {
+    pub fn new(
+        feishu_user_id: String,
+        feishu_chat_id: String,
+        matrix_room_id: String,
+        owner_mxid: String,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: 0,
+            feishu_user_id,
+            feishu_chat_id,
+            matrix_room_id,
+            owner_mxid,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
 impl UserMapping {
     pub fn new(
         matrix_user_id: String,
@@ -151,3 +215,38 @@ impl MessageMapping {
         self
     }
 }
+
+impl ReactionMapping {
+    pub const ORIGIN_MATRIX: &'static str = "matrix";
+    pub const ORIGIN_FEISHU: &'static str = "feishu";
+
+    pub fn new(
+        matrix_event_id: String,
+        feishu_message_id: String,
+        feishu_reaction_id: String,
+        room_id: String,
+        sender_mxid: String,
+        emoji: String,
+    ) -> Self {
+        Self {
+            id: 0,
+            matrix_event_id,
+            feishu_message_id,
+            feishu_reaction_id,
+            room_id,
+            sender_mxid,
+            emoji,
+            origin: Self::ORIGIN_MATRIX.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_origin(mut self, origin: &str) -> Self {
+        self.origin = origin.to_string();
+        self
+    }
+
+    pub fn is_from_feishu(&self) -> bool {
+        self.origin == Self::ORIGIN_FEISHU
+    }
+}
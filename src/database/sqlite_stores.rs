@@ -9,9 +9,13 @@ use parking_lot::Mutex;
 
 use super::error::{DatabaseError, DatabaseResult};
 use super::models::{
-    DeadLetterEvent, MediaCacheEntry, MessageMapping, ProcessedEvent, RoomMapping, UserMapping,
+    DeadLetterEvent, DirectChat, MediaCacheEntry, MessageMapping, Portal, ProcessedEvent,
+    ReactionMapping, RoomMapping, UserMapping,
+};
+use super::stores::{
+    DeadLetterStore, DirectChatStore, EventStore, MediaStore, MessageStore, PortalStore,
+    ReactionStore, RoomStore, SpaceStore, UserStore,
 };
-use super::stores::{DeadLetterStore, EventStore, MediaStore, MessageStore, RoomStore, UserStore};
 
 type SqlitePool = Pool<ConnectionManager<SqliteConnection>>;
 
@@ -27,6 +31,55 @@ table! {
     }
 }
 
+table! {
+    portals (id) {
+        id -> BigInt,
+        feishu_room_id -> Text,
+        mxid -> Text,
+        name -> Text,
+        topic -> Nullable<Text>,
+        avatar_url -> Nullable<Text>,
+        encrypted -> Bool,
+        room_type -> Text,
+        relay_user_id -> Nullable<Text>,
+        creator_mxid -> Text,
+        bridge_info -> Text,
+        creation_content -> Nullable<Text>,
+        last_event -> Nullable<Text>,
+    }
+}
+
+table! {
+    tenant_spaces (id) {
+        id -> BigInt,
+        tenant_key -> Text,
+        space_room -> Text,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
+table! {
+    direct_chats (id) {
+        id -> BigInt,
+        feishu_user_id -> Text,
+        feishu_chat_id -> Text,
+        matrix_room_id -> Text,
+        owner_mxid -> Text,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
+table! {
+    users (id) {
+        id -> BigInt,
+        mxid -> Text,
+        feishu_user_id -> Nullable<Text>,
+        space_room -> Nullable<Text>,
+    }
+}
+
 table! {
     user_mappings (id) {
         id -> BigInt,
@@ -55,6 +108,20 @@ table! {
     }
 }
 
+table! {
+    reaction_mappings (id) {
+        id -> BigInt,
+        matrix_event_id -> Text,
+        feishu_message_id -> Text,
+        feishu_reaction_id -> Text,
+        room_id -> Text,
+        sender_mxid -> Text,
+        emoji -> Text,
+        origin -> Text,
+        created_at -> Text,
+    }
+}
+
 table! {
     processed_events (id) {
         id -> BigInt,
@@ -117,6 +184,18 @@ impl SqliteStores {
         Arc::new(self.clone())
     }
 
+    pub fn portal_store(&self) -> Arc<dyn PortalStore> {
+        Arc::new(self.clone())
+    }
+
+    pub fn space_store(&self) -> Arc<dyn SpaceStore> {
+        Arc::new(self.clone())
+    }
+
+    pub fn direct_chat_store(&self) -> Arc<dyn DirectChatStore> {
+        Arc::new(self.clone())
+    }
+
     pub fn user_store(&self) -> Arc<dyn UserStore> {
         Arc::new(self.clone())
     }
@@ -125,6 +204,10 @@ impl SqliteStores {
         Arc::new(self.clone())
     }
 
+    pub fn reaction_store(&self) -> Arc<dyn ReactionStore> {
+        Arc::new(self.clone())
+    }
+
     pub fn event_store(&self) -> Arc<dyn EventStore> {
         Arc::new(self.clone())
     }
@@ -240,7 +323,18 @@ impl RoomStore for SqliteStores {
             Ok::<_, DatabaseError>(())
         })
         .await
-        .map_err(|e| DatabaseError::Query(e.to_string()))?
+        .map_err(|e| DatabaseError::Query(e.to_string()))??;
+
+        let mut cache = self.room_cache.lock();
+        let stale_keys = cache
+            .iter()
+            .filter(|(_, mapping)| mapping.id == id)
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in stale_keys {
+            cache.pop(&key);
+        }
+        Ok(())
     }
 
     async fn list_room_mappings(
@@ -280,6 +374,275 @@ impl RoomStore for SqliteStores {
     }
 }
 
+#[async_trait]
+impl PortalStore for SqliteStores {
+    async fn get_portal_by_mxid(&self, mxid: &str) -> DatabaseResult<Option<Portal>> {
+        let pool = self.pool.clone();
+        let mxid = mxid.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let portal: Option<SqlitePortal> = portals::table
+                .filter(portals::mxid.eq(&mxid))
+                .first(&mut conn)
+                .optional()
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(portal.map(|p| p.into_model()))
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn get_portal_by_feishu_room(
+        &self,
+        feishu_room_id: &str,
+    ) -> DatabaseResult<Option<Portal>> {
+        let pool = self.pool.clone();
+        let room_id = feishu_room_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let portal: Option<SqlitePortal> = portals::table
+                .filter(portals::feishu_room_id.eq(&room_id))
+                .first(&mut conn)
+                .optional()
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(portal.map(|p| p.into_model()))
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn upsert_portal(&self, portal: &Portal) -> DatabaseResult<Portal> {
+        let pool = self.pool.clone();
+        let portal = portal.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let row = NewSqlitePortal::from_model(&portal);
+            conn.transaction::<_, DatabaseError, _>(|conn| {
+                // A Matrix room re-bridged to another Feishu chat keeps its mxid, so the
+                // stale row must go before the upsert hits the mxid UNIQUE constraint.
+                diesel::delete(
+                    portals::table
+                        .filter(portals::mxid.eq(&portal.mxid))
+                        .filter(portals::feishu_room_id.ne(&portal.feishu_room_id)),
+                )
+                .execute(conn)
+                .map_err(DatabaseError::from)?;
+
+                diesel::insert_into(portals::table)
+                    .values(&row)
+                    .on_conflict(portals::feishu_room_id)
+                    .do_update()
+                    .set(&row)
+                    .execute(conn)
+                    .map_err(DatabaseError::from)?;
+
+                let saved: SqlitePortal = portals::table
+                    .filter(portals::feishu_room_id.eq(&portal.feishu_room_id))
+                    .first(conn)
+                    .map_err(DatabaseError::from)?;
+                Ok(saved.into_model())
+            })
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn delete_portal(&self, id: i64) -> DatabaseResult<()> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            diesel::delete(portals::table.filter(portals::id.eq(id)))
+                .execute(&mut conn)
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(())
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn list_portals(
+        &self,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> DatabaseResult<Vec<Portal>> {
+        let pool = self.pool.clone();
+        let limit = limit.unwrap_or(100);
+        let offset = offset.unwrap_or(0);
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let rows: Vec<SqlitePortal> = portals::table
+                .order(portals::id.desc())
+                .limit(limit)
+                .offset(offset)
+                .load(&mut conn)
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(rows.into_iter().map(|p| p.into_model()).collect())
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+}
+
+#[async_trait]
+impl SpaceStore for SqliteStores {
+    async fn get_tenant_space(&self, tenant_key: &str) -> DatabaseResult<Option<String>> {
+        let pool = self.pool.clone();
+        let tenant_key = tenant_key.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let space_room: Option<String> = tenant_spaces::table
+                .filter(tenant_spaces::tenant_key.eq(&tenant_key))
+                .select(tenant_spaces::space_room)
+                .first(&mut conn)
+                .optional()
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(space_room)
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn set_tenant_space(&self, tenant_key: &str, space_room: &str) -> DatabaseResult<()> {
+        let pool = self.pool.clone();
+        let tenant_key = tenant_key.to_string();
+        let space_room = space_room.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let now = Utc::now().to_rfc3339();
+            diesel::insert_into(tenant_spaces::table)
+                .values((
+                    tenant_spaces::tenant_key.eq(&tenant_key),
+                    tenant_spaces::space_room.eq(&space_room),
+                    tenant_spaces::created_at.eq(&now),
+                    tenant_spaces::updated_at.eq(&now),
+                ))
+                .on_conflict(tenant_spaces::tenant_key)
+                .do_update()
+                .set((
+                    tenant_spaces::space_room.eq(&space_room),
+                    tenant_spaces::updated_at.eq(&now),
+                ))
+                .execute(&mut conn)
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(())
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn get_user_space(&self, mxid: &str) -> DatabaseResult<Option<String>> {
+        let pool = self.pool.clone();
+        let mxid = mxid.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let space_room: Option<Option<String>> = users::table
+                .filter(users::mxid.eq(&mxid))
+                .select(users::space_room)
+                .first(&mut conn)
+                .optional()
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(space_room.flatten())
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn set_user_space(&self, mxid: &str, space_room: &str) -> DatabaseResult<()> {
+        let pool = self.pool.clone();
+        let mxid = mxid.to_string();
+        let space_room = space_room.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            diesel::insert_into(users::table)
+                .values((users::mxid.eq(&mxid), users::space_room.eq(&space_room)))
+                .on_conflict(users::mxid)
+                .do_update()
+                .set(users::space_room.eq(&space_room))
+                .execute(&mut conn)
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(())
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+}
+
+#[async_trait]
+impl DirectChatStore for SqliteStores {
+    async fn get_direct_chat_by_feishu_user(
+        &self,
+        feishu_user_id: &str,
+    ) -> DatabaseResult<Option<DirectChat>> {
+        let pool = self.pool.clone();
+        let feishu_user_id = feishu_user_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let row: Option<SqliteDirectChat> = direct_chats::table
+                .filter(direct_chats::feishu_user_id.eq(&feishu_user_id))
+                .first(&mut conn)
+                .optional()
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(row.map(|chat| chat.into_model()))
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn get_direct_chat_by_matrix_room(
+        &self,
+        matrix_room_id: &str,
+    ) -> DatabaseResult<Option<DirectChat>> {
+        let pool = self.pool.clone();
+        let matrix_room_id = matrix_room_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let row: Option<SqliteDirectChat> = direct_chats::table
+                .filter(direct_chats::matrix_room_id.eq(&matrix_room_id))
+                .first(&mut conn)
+                .optional()
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(row.map(|chat| chat.into_model()))
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn create_direct_chat(&self, chat: &DirectChat) -> DatabaseResult<DirectChat> {
+        let pool = self.pool.clone();
+        let chat = chat.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            diesel::insert_into(direct_chats::table)
+                .values(&NewSqliteDirectChat::from_model(&chat))
+                .execute(&mut conn)
+                .map_err(DatabaseError::from)?;
+            let saved: SqliteDirectChat = direct_chats::table
+                .filter(direct_chats::feishu_user_id.eq(&chat.feishu_user_id))
+                .first(&mut conn)
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(saved.into_model())
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn delete_direct_chat_by_matrix_room(&self, matrix_room_id: &str) -> DatabaseResult<()> {
+        let pool = self.pool.clone();
+        let matrix_room_id = matrix_room_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            diesel::delete(
+                direct_chats::table.filter(direct_chats::matrix_room_id.eq(&matrix_room_id)),
+            )
+            .execute(&mut conn)
+            .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(())
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+}
+
 #[async_trait]
 impl UserStore for SqliteStores {
     async fn get_user_by_matrix_id(
@@ -435,6 +798,78 @@ impl UserStore for SqliteStores {
         .await
         .map_err(|e| DatabaseError::Query(e.to_string()))?
     }
+
+    async fn get_linked_feishu_user(&self, matrix_user_id: &str) -> DatabaseResult<Option<String>> {
+        let pool = self.pool.clone();
+        let mxid = matrix_user_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let feishu_user_id: Option<Option<String>> = users::table
+                .filter(users::mxid.eq(&mxid))
+                .select(users::feishu_user_id)
+                .first(&mut conn)
+                .optional()
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(feishu_user_id.flatten())
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn get_linked_matrix_user(&self, feishu_user_id: &str) -> DatabaseResult<Option<String>> {
+        let pool = self.pool.clone();
+        let feishu_user_id = feishu_user_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let mxid: Option<String> = users::table
+                .filter(users::feishu_user_id.eq(&feishu_user_id))
+                .select(users::mxid)
+                .first(&mut conn)
+                .optional()
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(mxid)
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn set_linked_feishu_user(
+        &self,
+        matrix_user_id: &str,
+        feishu_user_id: Option<&str>,
+    ) -> DatabaseResult<()> {
+        let pool = self.pool.clone();
+        let mxid = matrix_user_id.to_string();
+        let feishu_user_id = feishu_user_id.map(ToOwned::to_owned);
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            conn.transaction::<_, DatabaseError, _>(|conn| {
+                if let Some(feishu_user_id) = &feishu_user_id {
+                    diesel::update(
+                        users::table
+                            .filter(users::feishu_user_id.eq(feishu_user_id))
+                            .filter(users::mxid.ne(&mxid)),
+                    )
+                    .set(users::feishu_user_id.eq(None::<String>))
+                    .execute(conn)
+                    .map_err(DatabaseError::from)?;
+                }
+                diesel::insert_into(users::table)
+                    .values((
+                        users::mxid.eq(&mxid),
+                        users::feishu_user_id.eq(&feishu_user_id),
+                    ))
+                    .on_conflict(users::mxid)
+                    .do_update()
+                    .set(users::feishu_user_id.eq(&feishu_user_id))
+                    .execute(conn)
+                    .map_err(DatabaseError::from)?;
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
 }
 
 #[async_trait]
@@ -551,6 +986,98 @@ impl MessageStore for SqliteStores {
     }
 }
 
+#[async_trait]
+impl ReactionStore for SqliteStores {
+    async fn get_reaction_by_matrix_id(
+        &self,
+        matrix_event_id: &str,
+    ) -> DatabaseResult<Option<ReactionMapping>> {
+        let pool = self.pool.clone();
+        let event_id = matrix_event_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let reaction: Option<SqliteReactionMapping> = reaction_mappings::table
+                .filter(reaction_mappings::matrix_event_id.eq(&event_id))
+                .first(&mut conn)
+                .optional()
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(reaction.map(|r| r.into_model()))
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn get_reaction_by_feishu_id(
+        &self,
+        feishu_reaction_id: &str,
+    ) -> DatabaseResult<Option<ReactionMapping>> {
+        let pool = self.pool.clone();
+        let reaction_id = feishu_reaction_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let reaction: Option<SqliteReactionMapping> = reaction_mappings::table
+                .filter(reaction_mappings::feishu_reaction_id.eq(&reaction_id))
+                .first(&mut conn)
+                .optional()
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(reaction.map(|r| r.into_model()))
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn get_reactions_by_feishu_message(
+        &self,
+        feishu_message_id: &str,
+    ) -> DatabaseResult<Vec<ReactionMapping>> {
+        let pool = self.pool.clone();
+        let message_id = feishu_message_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let reactions: Vec<SqliteReactionMapping> = reaction_mappings::table
+                .filter(reaction_mappings::feishu_message_id.eq(&message_id))
+                .order(reaction_mappings::id.asc())
+                .load(&mut conn)
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(reactions.into_iter().map(|r| r.into_model()).collect())
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn create_reaction_mapping(
+        &self,
+        mapping: &ReactionMapping,
+    ) -> DatabaseResult<ReactionMapping> {
+        let pool = self.pool.clone();
+        let mapping = mapping.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            let sqlite_mapping = NewSqliteReactionMapping::from_model(&mapping);
+            diesel::insert_into(reaction_mappings::table)
+                .values(&sqlite_mapping)
+                .execute(&mut conn)
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(mapping)
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+
+    async fn delete_reaction_mapping(&self, id: i64) -> DatabaseResult<()> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| DatabaseError::Pool(e.to_string()))?;
+            diesel::delete(reaction_mappings::table.filter(reaction_mappings::id.eq(id)))
+                .execute(&mut conn)
+                .map_err(DatabaseError::from)?;
+            Ok::<_, DatabaseError>(())
+        })
+        .await
+        .map_err(|e| DatabaseError::Query(e.to_string()))?
+    }
+}
+
 #[async_trait]
 impl EventStore for SqliteStores {
     async fn is_event_processed(&self, event_id: &str) -> DatabaseResult<bool> {
@@ -900,6 +1427,81 @@ impl NewSqliteRoomMapping {
     }
 }
 
+#[derive(Queryable)]
+#[diesel(table_name = portals)]
+struct SqlitePortal {
+    id: i64,
+    feishu_room_id: String,
+    mxid: String,
+    name: String,
+    topic: Option<String>,
+    avatar_url: Option<String>,
+    encrypted: bool,
+    room_type: String,
+    relay_user_id: Option<String>,
+    creator_mxid: String,
+    bridge_info: String,
+    creation_content: Option<String>,
+    last_event: Option<String>,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = portals)]
+#[diesel(treat_none_as_null = true)]
+struct NewSqlitePortal {
+    feishu_room_id: String,
+    mxid: String,
+    name: String,
+    topic: Option<String>,
+    avatar_url: Option<String>,
+    encrypted: bool,
+    room_type: String,
+    relay_user_id: Option<String>,
+    creator_mxid: String,
+    bridge_info: String,
+    creation_content: Option<String>,
+    last_event: Option<String>,
+}
+
+impl SqlitePortal {
+    fn into_model(self) -> Portal {
+        Portal {
+            id: self.id,
+            feishu_room_id: self.feishu_room_id,
+            mxid: self.mxid,
+            name: self.name,
+            topic: self.topic,
+            avatar_url: self.avatar_url,
+            encrypted: self.encrypted,
+            room_type: self.room_type,
+            relay_user_id: self.relay_user_id,
+            creator_mxid: self.creator_mxid,
+            bridge_info: self.bridge_info,
+            creation_content: self.creation_content,
+            last_event: self.last_event,
+        }
+    }
+}
+
+impl NewSqlitePortal {
+    fn from_model(model: &Portal) -> Self {
+        Self {
+            feishu_room_id: model.feishu_room_id.clone(),
+            mxid: model.mxid.clone(),
+            name: model.name.clone(),
+            topic: model.topic.clone(),
+            avatar_url: model.avatar_url.clone(),
+            encrypted: model.encrypted,
+            room_type: model.room_type.clone(),
+            relay_user_id: model.relay_user_id.clone(),
+            creator_mxid: model.creator_mxid.clone(),
+            bridge_info: model.bridge_info.clone(),
+            creation_content: model.creation_content.clone(),
+            last_event: model.last_event.clone(),
+        }
+    }
+}
+
 #[derive(Queryable, Insertable, AsChangeset)]
 #[diesel(table_name = user_mappings)]
 struct SqliteUserMapping {
@@ -1034,6 +1636,66 @@ impl NewSqliteMessageMapping {
     }
 }
 
+#[derive(Queryable)]
+#[diesel(table_name = reaction_mappings)]
+struct SqliteReactionMapping {
+    id: i64,
+    matrix_event_id: String,
+    feishu_message_id: String,
+    feishu_reaction_id: String,
+    room_id: String,
+    sender_mxid: String,
+    emoji: String,
+    origin: String,
+    created_at: String,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = reaction_mappings)]
+struct NewSqliteReactionMapping {
+    matrix_event_id: String,
+    feishu_message_id: String,
+    feishu_reaction_id: String,
+    room_id: String,
+    sender_mxid: String,
+    emoji: String,
+    origin: String,
+    created_at: String,
+}
+
+impl SqliteReactionMapping {
+    fn into_model(self) -> ReactionMapping {
+        ReactionMapping {
+            id: self.id,
+            matrix_event_id: self.matrix_event_id,
+            feishu_message_id: self.feishu_message_id,
+            feishu_reaction_id: self.feishu_reaction_id,
+            room_id: self.room_id,
+            sender_mxid: self.sender_mxid,
+            emoji: self.emoji,
+            origin: self.origin,
+            created_at: DateTime::parse_from_rfc3339(&self.created_at)
+                .map(|d| d.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        }
+    }
+}
+
+impl NewSqliteReactionMapping {
+    fn from_model(model: &ReactionMapping) -> Self {
+        Self {
+            matrix_event_id: model.matrix_event_id.clone(),
+            feishu_message_id: model.feishu_message_id.clone(),
+            feishu_reaction_id: model.feishu_reaction_id.clone(),
+            room_id: model.room_id.clone(),
+            sender_mxid: model.sender_mxid.clone(),
+            emoji: model.emoji.clone(),
+            origin: model.origin.clone(),
+            created_at: model.created_at.to_rfc3339(),
+        }
+    }
+}
+
 #[derive(Queryable, Insertable)]
 #[diesel(table_name = processed_events)]
 struct SqliteProcessedEvent {
@@ -1192,3 +1854,56 @@ impl NewSqliteMediaCache {
         }
     }
 }
+
+#[derive(Queryable)]
+struct SqliteDirectChat {
+    id: i64,
+    feishu_user_id: String,
+    feishu_chat_id: String,
+    matrix_room_id: String,
+    owner_mxid: String,
+    created_at: String,
+    updated_at: String,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = direct_chats)]
+struct NewSqliteDirectChat {
+    feishu_user_id: String,
+    feishu_chat_id: String,
+    matrix_room_id: String,
+    owner_mxid: String,
+    created_at: String,
+    updated_at: String,
+}
+
+impl SqliteDirectChat {
+    fn into_model(self) -> DirectChat {
+        DirectChat {
+            id: self.id,
+            feishu_user_id: self.feishu_user_id,
+            feishu_chat_id: self.feishu_chat_id,
+            matrix_room_id: self.matrix_room_id,
+            owner_mxid: self.owner_mxid,
+            created_at: DateTime::parse_from_rfc3339(&self.created_at)
+                .map(|value| value.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+            updated_at: DateTime::parse_from_rfc3339(&self.updated_at)
+                .map(|value| value.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        }
+    }
+}
+
+impl NewSqliteDirectChat {
+    fn from_model(model: &DirectChat) -> Self {
+        Self {
+            feishu_user_id: model.feishu_user_id.clone(),
+            feishu_chat_id: model.feishu_chat_id.clone(),
+            matrix_room_id: model.matrix_room_id.clone(),
+            owner_mxid: model.owner_mxid.clone(),
+            created_at: model.created_at.to_rfc3339(),
+            updated_at: model.updated_at.to_rfc3339(),
+        }
+    }
+}
@@ -5,7 +5,8 @@ use chrono::{DateTime, Utc};
 
 use super::error::DatabaseResult;
 use super::models::{
-    DeadLetterEvent, MediaCacheEntry, MessageMapping, ProcessedEvent, RoomMapping, UserMapping,
+    DeadLetterEvent, DirectChat, MediaCacheEntry, MessageMapping, Portal, ProcessedEvent,
+    ReactionMapping, RoomMapping, UserMapping,
 };
 
 #[async_trait]
@@ -29,6 +30,44 @@ pub trait RoomStore: Send + Sync {
     async fn count_rooms(&self) -> DatabaseResult<i64>;
 }
 
+#[async_trait]
+pub trait PortalStore: Send + Sync {
+    async fn get_portal_by_mxid(&self, mxid: &str) -> DatabaseResult<Option<Portal>>;
+    async fn get_portal_by_feishu_room(
+        &self,
+        feishu_room_id: &str,
+    ) -> DatabaseResult<Option<Portal>>;
+    async fn upsert_portal(&self, portal: &Portal) -> DatabaseResult<Portal>;
+    async fn delete_portal(&self, id: i64) -> DatabaseResult<()>;
+    async fn list_portals(
+        &self,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> DatabaseResult<Vec<Portal>>;
+}
+
+#[async_trait]
+pub trait SpaceStore: Send + Sync {
+    async fn get_tenant_space(&self, tenant_key: &str) -> DatabaseResult<Option<String>>;
+    async fn set_tenant_space(&self, tenant_key: &str, space_room: &str) -> DatabaseResult<()>;
+    async fn get_user_space(&self, mxid: &str) -> DatabaseResult<Option<String>>;
+    async fn set_user_space(&self, mxid: &str, space_room: &str) -> DatabaseResult<()>;
+}
+
+#[async_trait]
+pub trait DirectChatStore: Send + Sync {
+    async fn get_direct_chat_by_feishu_user(
+        &self,
+        feishu_user_id: &str,
+    ) -> DatabaseResult<Option<DirectChat>>;
+    async fn get_direct_chat_by_matrix_room(
+        &self,
+        matrix_room_id: &str,
+    ) -> DatabaseResult<Option<DirectChat>>;
+    async fn create_direct_chat(&self, chat: &DirectChat) -> DatabaseResult<DirectChat>;
+    async fn delete_direct_chat_by_matrix_room(&self, matrix_room_id: &str) -> DatabaseResult<()>;
+}
+
 #[async_trait]
 pub trait UserStore: Send + Sync {
     async fn get_user_by_matrix_id(
@@ -49,6 +88,14 @@ pub trait UserStore: Send + Sync {
     ) -> DatabaseResult<Vec<UserMapping>>;
     async fn count_users(&self) -> DatabaseResult<i64>;
     async fn cleanup_stale_user_mappings(&self, before: DateTime<Utc>) -> DatabaseResult<u64>;
+    /// Real (non-puppet) Matrix accounts linked to a Feishu identity.
+    async fn get_linked_feishu_user(&self, matrix_user_id: &str) -> DatabaseResult<Option<String>>;
+    async fn get_linked_matrix_user(&self, feishu_user_id: &str) -> DatabaseResult<Option<String>>;
+    async fn set_linked_feishu_user(
+        &self,
+        matrix_user_id: &str,
+        feishu_user_id: Option<&str>,
+    ) -> DatabaseResult<()>;
 }
 
 #[async_trait]
@@ -77,6 +124,27 @@ pub trait MessageStore: Send + Sync {
     ) -> DatabaseResult<Vec<MessageMapping>>;
 }
 
+#[async_trait]
+pub trait ReactionStore: Send + Sync {
+    async fn get_reaction_by_matrix_id(
+        &self,
+        matrix_event_id: &str,
+    ) -> DatabaseResult<Option<ReactionMapping>>;
+    async fn get_reaction_by_feishu_id(
+        &self,
+        feishu_reaction_id: &str,
+    ) -> DatabaseResult<Option<ReactionMapping>>;
+    async fn get_reactions_by_feishu_message(
+        &self,
+        feishu_message_id: &str,
+    ) -> DatabaseResult<Vec<ReactionMapping>>;
+    async fn create_reaction_mapping(
+        &self,
+        mapping: &ReactionMapping,
+    ) -> DatabaseResult<ReactionMapping>;
+    async fn delete_reaction_mapping(&self, id: i64) -> DatabaseResult<()>;
+}
+
 #[async_trait]
 pub trait EventStore: Send + Sync {
     async fn is_event_processed(&self, event_id: &str) -> DatabaseResult<bool>;
@@ -116,8 +184,12 @@ pub trait MediaStore: Send + Sync {
 }
 
 pub type SharedRoomStore = Arc<dyn RoomStore>;
+pub type SharedPortalStore = Arc<dyn PortalStore>;
+pub type SharedSpaceStore = Arc<dyn SpaceStore>;
+pub type SharedDirectChatStore = Arc<dyn DirectChatStore>;
 pub type SharedUserStore = Arc<dyn UserStore>;
 pub type SharedMessageStore = Arc<dyn MessageStore>;
+pub type SharedReactionStore = Arc<dyn ReactionStore>;
 pub type SharedEventStore = Arc<dyn EventStore>;
 pub type SharedDeadLetterStore = Arc<dyn DeadLetterStore>;
 pub type SharedMediaStore = Arc<dyn MediaStore>;
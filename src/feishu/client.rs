@@ -240,11 +240,113 @@ impl FeishuClient {
         Ok(data.user)
     }
 
+    pub async fn find_user_id_by_email(&mut self, email: &str) -> Result<Option<String>> {
+        let payload = json!({ "emails": [email] });
+        let response = self
+            .sdk_client()?
+            .operation("contact.v3.user.batch_get_id")
+            .query_param("user_id_type", "open_id")
+            .body_json(&payload)
+            .map_err(|err| Self::map_sdk_error("contact/v3/users/batch_get_id", err))
+            .context("failed to build contact/v3/users/batch_get_id request")?
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("contact/v3/users/batch_get_id", err))
+            .context("failed to call contact/v3/users/batch_get_id")?;
+        Self::ensure_sdk_http_success("contact/v3/users/batch_get_id", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("contact/v3/users/batch_get_id", err))
+            .context("failed to parse contact/v3/users/batch_get_id response as JSON")?;
+
+        #[derive(serde::Deserialize)]
+        struct UserIdEntry {
+            user_id: Option<String>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct UserIdList {
+            #[serde(default)]
+            user_list: Vec<UserIdEntry>,
+        }
+
+        let data: UserIdList = Self::parse_data("contact/v3/users/batch_get_id", json)?;
+        Ok(data
+            .user_list
+            .into_iter()
+            .filter_map(|entry| entry.user_id)
+            .find(|user_id| !user_id.trim().is_empty()))
+    }
+
+    pub async fn list_contact_scope_users(
+        &mut self,
+        page_token: Option<&str>,
+    ) -> Result<FeishuContactScopePage> {
+        let mut request = self
+            .sdk_client()?
+            .operation("contact.v3.scope.list")
+            .query_param("user_id_type", "user_id")
+            .query_param("page_size", "100");
+        if let Some(page_token) = page_token {
+            request = request.query_param("page_token", page_token);
+        }
+        let response = request
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("contact/v3/scopes", err))
+            .context("failed to call contact/v3/scopes")?;
+        Self::ensure_sdk_http_success("contact/v3/scopes", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("contact/v3/scopes", err))
+            .context("failed to parse contact/v3/scopes response as JSON")?;
+
+        Self::parse_data("contact/v3/scopes", json)
+    }
+
+    pub async fn batch_get_users(&mut self, user_ids: &[String]) -> Result<Vec<FeishuUserSummary>> {
+        let mut request = self
+            .sdk_client()?
+            .operation("contact.v3.user.batch")
+            .query_param("user_id_type", "user_id");
+        for user_id in user_ids {
+            request = request.query_param("user_ids", user_id);
+        }
+        let response = request
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("contact/v3/users/batch", err))
+            .context("failed to call contact/v3/users/batch")?;
+        Self::ensure_sdk_http_success("contact/v3/users/batch", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("contact/v3/users/batch", err))
+            .context("failed to parse contact/v3/users/batch response as JSON")?;
+
+        #[derive(serde::Deserialize)]
+        struct UserItems {
+            #[serde(default)]
+            items: Vec<FeishuUserSummary>,
+        }
+
+        let data: UserItems = Self::parse_data("contact/v3/users/batch", json)?;
+        Ok(data.items)
+    }
+
     pub async fn get_chat(&mut self, chat_id: &str) -> Result<FeishuChatProfile> {
         let response = self
             .sdk_client()?
             .im_v1_chat()
-            .get(chat_id.to_string(), Vec::new(), self.sdk_request_options())
+            .get(
+                chat_id.to_string(),
+                // owner_id and user_manager_id_list default to open_id; ask for user_id so they
+                // match the user mappings and the member_id_type used by the chat member APIs.
+                vec![("user_id_type".to_string(), "user_id".to_string())],
+                self.sdk_request_options(),
+            )
             .await
             .map_err(|err| Self::map_sdk_error("im/v1/chats/get", err))
             .context("failed to call im/v1/chats/get")?;
@@ -254,13 +356,293 @@ impl FeishuClient {
             .map_err(|err| Self::map_sdk_error("im/v1/chats/get", err))
             .context("failed to parse im/v1/chats/get response as JSON")?;
 
+        let mut data: Value = Self::parse_data("im/v1/chats/get", json)?;
+        if let Some(chat) = data.get_mut("chat") {
+            data = chat.take();
+        }
+        let mut chat: FeishuChatProfile = serde_json::from_value(data)
+            .context("im/v1/chats/get: failed to parse chat profile")?;
+        if chat.chat_id.is_empty() {
+            chat.chat_id = chat_id.to_string();
+        }
+        Ok(chat)
+    }
+
+    pub async fn create_chat(
+        &mut self,
+        name: &str,
+        description: Option<&str>,
+        avatar_key: Option<&str>,
+        user_ids: &[String],
+    ) -> Result<String> {
+        let mut payload = json!({
+            "name": name,
+            "chat_mode": "group",
+            "chat_type": "private",
+            "user_id_list": user_ids,
+        });
+        if let Some(description) = description {
+            payload["description"] = Value::String(description.to_string());
+        }
+        if let Some(avatar_key) = avatar_key {
+            payload["avatar"] = Value::String(avatar_key.to_string());
+        }
+
+        let response = self
+            .sdk_client()?
+            .operation("im.v1.chat.create")
+            .query_param("user_id_type", "user_id")
+            .query_param("set_bot_manager", "true")
+            .body_json(&payload)
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/create", err))
+            .context("failed to build im/v1/chats/create request")?
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/create", err))
+            .context("failed to call im/v1/chats/create")?;
+        Self::ensure_sdk_http_success("im/v1/chats/create", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/create", err))
+            .context("failed to parse im/v1/chats/create response as JSON")?;
+
+        #[derive(serde::Deserialize)]
+        struct CreatedChat {
+            chat_id: String,
+        }
+
+        let data: CreatedChat = Self::parse_data("im/v1/chats/create", json)?;
+        Ok(data.chat_id)
+    }
+
+    pub async fn list_chats(&mut self, page_token: Option<&str>) -> Result<FeishuChatListPage> {
+        let mut request = self
+            .sdk_client()?
+            .operation("im.v1.chat.list")
+            .query_param("user_id_type", "user_id")
+            .query_param("page_size", "100");
+        if let Some(page_token) = page_token {
+            request = request.query_param("page_token", page_token);
+        }
+        let response = request
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/list", err))
+            .context("failed to call im/v1/chats/list")?;
+        Self::ensure_sdk_http_success("im/v1/chats/list", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/list", err))
+            .context("failed to parse im/v1/chats/list response as JSON")?;
+
+        Self::parse_data("im/v1/chats/list", json)
+    }
+
+    pub async fn is_bot_in_chat(&mut self, chat_id: &str) -> Result<bool> {
+        let response = self
+            .sdk_client()?
+            .operation("im.v1.chat_members.is_in_chat")
+            .path_param("chat_id", chat_id)
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/members/is_in_chat", err))
+            .context("failed to call im/v1/chats/members/is_in_chat")?;
+        Self::ensure_sdk_http_success("im/v1/chats/members/is_in_chat", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/members/is_in_chat", err))
+            .context("failed to parse im/v1/chats/members/is_in_chat response as JSON")?;
+
         #[derive(serde::Deserialize)]
-        struct ChatWrapper {
-            chat: FeishuChatProfile,
+        struct InChat {
+            #[serde(default)]
+            is_in_chat: bool,
         }
 
-        let data: ChatWrapper = Self::parse_data("im/v1/chats/get", json)?;
-        Ok(data.chat)
+        let data: InChat = Self::parse_data("im/v1/chats/members/is_in_chat", json)?;
+        Ok(data.is_in_chat)
+    }
+
+    pub async fn list_chat_members(
+        &mut self,
+        chat_id: &str,
+        page_token: Option<&str>,
+    ) -> Result<FeishuChatMemberPage> {
+        let mut request = self
+            .sdk_client()?
+            .operation("im.v1.chat_members.get")
+            .path_param("chat_id", chat_id)
+            .query_param("member_id_type", "user_id")
+            .query_param("page_size", "100");
+        if let Some(page_token) = page_token {
+            request = request.query_param("page_token", page_token);
+        }
+        let response = request
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/members/get", err))
+            .context("failed to call im/v1/chats/members/get")?;
+        Self::ensure_sdk_http_success("im/v1/chats/members/get", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/members/get", err))
+            .context("failed to parse im/v1/chats/members/get response as JSON")?;
+
+        Self::parse_data("im/v1/chats/members/get", json)
+    }
+
+    pub async fn add_chat_members(&mut self, chat_id: &str, user_ids: &[String]) -> Result<()> {
+        let payload = json!({ "id_list": user_ids });
+        let response = self
+            .sdk_client()?
+            .operation("im.v1.chat_members.create")
+            .path_param("chat_id", chat_id)
+            .query_param("member_id_type", "user_id")
+            .body_json(&payload)
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/members/create", err))
+            .context("failed to build im/v1/chats/members/create request")?
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/members/create", err))
+            .context("failed to call im/v1/chats/members/create")?;
+        Self::ensure_sdk_http_success("im/v1/chats/members/create", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/members/create", err))
+            .context("failed to parse im/v1/chats/members/create response as JSON")?;
+
+        Self::ensure_ok("im/v1/chats/members/create", json)
+    }
+
+    pub async fn delete_chat(&mut self, chat_id: &str) -> Result<()> {
+        let response = self
+            .sdk_client()?
+            .operation("im.v1.chat.delete")
+            .path_param("chat_id", chat_id)
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/delete", err))
+            .context("failed to call im/v1/chats/delete")?;
+        Self::ensure_sdk_http_success("im/v1/chats/delete", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/delete", err))
+            .context("failed to parse im/v1/chats/delete response as JSON")?;
+
+        Self::ensure_ok("im/v1/chats/delete", json)
+    }
+
+    pub async fn remove_chat_members(&mut self, chat_id: &str, user_ids: &[String]) -> Result<()> {
+        let payload = json!({ "id_list": user_ids });
+        let response = self
+            .sdk_client()?
+            .operation("im.v1.chat_members.delete")
+            .path_param("chat_id", chat_id)
+            .query_param("member_id_type", "user_id")
+            .body_json(&payload)
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/members/delete", err))
+            .context("failed to build im/v1/chats/members/delete request")?
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/members/delete", err))
+            .context("failed to call im/v1/chats/members/delete")?;
+        Self::ensure_sdk_http_success("im/v1/chats/members/delete", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/members/delete", err))
+            .context("failed to parse im/v1/chats/members/delete response as JSON")?;
+
+        Self::ensure_ok("im/v1/chats/members/delete", json)
+    }
+
+    pub async fn add_chat_managers(&mut self, chat_id: &str, user_ids: &[String]) -> Result<()> {
+        let payload = json!({ "manager_ids": user_ids });
+        let response = self
+            .sdk_client()?
+            .operation("im.v1.chat_managers.add_managers")
+            .path_param("chat_id", chat_id)
+            .query_param("member_id_type", "user_id")
+            .body_json(&payload)
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/managers/add_managers", err))
+            .context("failed to build im/v1/chats/managers/add_managers request")?
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/managers/add_managers", err))
+            .context("failed to call im/v1/chats/managers/add_managers")?;
+        Self::ensure_sdk_http_success("im/v1/chats/managers/add_managers", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/managers/add_managers", err))
+            .context("failed to parse im/v1/chats/managers/add_managers response as JSON")?;
+
+        Self::ensure_ok("im/v1/chats/managers/add_managers", json)
+    }
+
+    pub async fn remove_chat_managers(&mut self, chat_id: &str, user_ids: &[String]) -> Result<()> {
+        let payload = json!({ "manager_ids": user_ids });
+        let response = self
+            .sdk_client()?
+            .operation("im.v1.chat_managers.delete_managers")
+            .path_param("chat_id", chat_id)
+            .query_param("member_id_type", "user_id")
+            .body_json(&payload)
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/managers/delete_managers", err))
+            .context("failed to build im/v1/chats/managers/delete_managers request")?
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/managers/delete_managers", err))
+            .context("failed to call im/v1/chats/managers/delete_managers")?;
+        Self::ensure_sdk_http_success("im/v1/chats/managers/delete_managers", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/managers/delete_managers", err))
+            .context("failed to parse im/v1/chats/managers/delete_managers response as JSON")?;
+
+        Self::ensure_ok("im/v1/chats/managers/delete_managers", json)
+    }
+
+    pub async fn update_chat(
+        &mut self,
+        chat_id: &str,
+        name: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<()> {
+        let mut payload = json!({});
+        if let Some(name) = name {
+            payload["name"] = Value::String(name.to_string());
+        }
+        if let Some(description) = description {
+            payload["description"] = Value::String(description.to_string());
+        }
+
+        let response = self
+            .sdk_client()?
+            .operation("im.v1.chat.update")
+            .path_param("chat_id", chat_id)
+            .body_json(&payload)
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/update", err))
+            .context("failed to build im/v1/chats/update request")?
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/update", err))
+            .context("failed to call im/v1/chats/update")?;
+        Self::ensure_sdk_http_success("im/v1/chats/update", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("im/v1/chats/update", err))
+            .context("failed to parse im/v1/chats/update response as JSON")?;
+
+        Self::ensure_ok("im/v1/chats/update", json)
     }
 
     pub async fn send_message(
@@ -397,6 +779,61 @@ impl FeishuClient {
         Ok(())
     }
 
+    pub async fn add_message_reaction(
+        &mut self,
+        message_id: &str,
+        emoji_type: &str,
+    ) -> Result<FeishuMessageReaction> {
+        let payload = json!({
+            "reaction_type": {
+                "emoji_type": emoji_type
+            }
+        });
+        let response = self
+            .sdk_client()?
+            .operation("im.v1.message_reaction.create")
+            .path_param("message_id", message_id)
+            .body_json(&payload)
+            .map_err(|err| Self::map_sdk_error("im/v1/message/reactions/create", err))
+            .context("failed to build im/v1/message/reactions/create request")?
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("im/v1/message/reactions/create", err))
+            .context("failed to call im/v1/message/reactions/create")?;
+        Self::ensure_sdk_http_success("im/v1/message/reactions/create", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("im/v1/message/reactions/create", err))
+            .context("failed to parse im/v1/message/reactions/create response as JSON")?;
+
+        Self::parse_data("im/v1/message/reactions/create", json)
+    }
+
+    pub async fn delete_message_reaction(
+        &mut self,
+        message_id: &str,
+        reaction_id: &str,
+    ) -> Result<()> {
+        let response = self
+            .sdk_client()?
+            .operation("im.v1.message_reaction.delete")
+            .path_param("message_id", message_id)
+            .path_param("reaction_id", reaction_id)
+            .options(self.sdk_request_options())
+            .send()
+            .await
+            .map_err(|err| Self::map_sdk_error("im/v1/message/reactions/delete", err))
+            .context("failed to call im/v1/message/reactions/delete")?;
+        Self::ensure_sdk_http_success("im/v1/message/reactions/delete", &response)?;
+        let json = response
+            .json_value()
+            .map_err(|err| Self::map_sdk_error("im/v1/message/reactions/delete", err))
+            .context("failed to parse im/v1/message/reactions/delete response as JSON")?;
+
+        Self::ensure_ok("im/v1/message/reactions/delete", json)
+    }
+
     pub async fn get_message(&mut self, message_id: &str) -> Result<Option<FeishuMessageData>> {
         let response = self
             .sdk_client()?
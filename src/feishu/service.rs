@@ -16,11 +16,13 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use super::{
-    FeishuChatProfile, FeishuClient, FeishuMessageData, FeishuMessageSendData, FeishuRichText,
-    FeishuUser,
+    FeishuChatListPage, FeishuChatMemberPage, FeishuChatProfile, FeishuClient,
+    FeishuContactScopePage, FeishuMessageData, FeishuMessageReaction, FeishuMessageSendData,
+    FeishuRichText, FeishuUser, FeishuUserSummary,
 };
 use crate::bridge::FeishuBridge;
-use crate::bridge::message::{Attachment, BridgeMessage, MessageType};
+use crate::bridge::message::{Attachment, BridgeMessage, MessageMention, MessageType};
+use crate::formatter::{FeishuRenderContext, feishu_post_to_matrix};
 use crate::util::{TtlCache, build_trace_id, parse_feishu_api_error};
 use crate::web::{ScopedTimer, global_metrics};
 
@@ -66,6 +68,20 @@ struct RecalledMessage {
     chat_id: String,
 }
 
+#[derive(Debug, Clone)]
+struct MessageReadEvent {
+    reader_id: String,
+    message_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct MessageReactionEvent {
+    message_id: String,
+    emoji_type: String,
+    user_id: Option<String>,
+    operator_type: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 struct ChatMemberChangedEvent {
     chat_id: String,
@@ -76,6 +92,7 @@ struct ChatMemberChangedEvent {
 struct ChatUpdatedEvent {
     chat_id: String,
     chat_name: Option<String>,
+    description: Option<String>,
     chat_mode: Option<String>,
     chat_type: Option<String>,
 }
@@ -915,6 +932,7 @@ impl FeishuService {
                 let chat_id = event.chat_id.clone();
                 self.invalidate_chat_cache(&chat_id).await;
                 let chat_name = event.chat_name.clone();
+                let description = event.description.clone();
                 let chat_mode = event.chat_mode.clone();
                 let chat_type = event.chat_type.clone();
                 let event_type_for_task = event_type.clone();
@@ -922,6 +940,7 @@ impl FeishuService {
                 let dead_letter_payload = json!({
                     "chat_id": event.chat_id,
                     "chat_name": event.chat_name,
+                    "description": event.description,
                     "chat_mode": event.chat_mode,
                     "chat_type": event.chat_type
                 });
@@ -933,7 +952,13 @@ impl FeishuService {
                 global_metrics().record_trace_event(flow, "queued");
                 self.queue_chat_task(chat_id.clone(), async move {
                     if let Err(err) = bridge
-                        .handle_feishu_chat_updated(&chat_id, chat_name, chat_mode, chat_type)
+                        .handle_feishu_chat_updated(
+                            &chat_id,
+                            chat_name,
+                            description,
+                            chat_mode,
+                            chat_type,
+                        )
                         .await
                     {
                         global_metrics().record_trace_event(flow, "failed");
@@ -1044,6 +1069,236 @@ impl FeishuService {
                 .await;
                 Ok(EventDispatchResult::Accepted)
             }
+            "im.message.updated_v1" => {
+                let bridge_message = self
+                    .webhook_event_to_bridge_message(&payload)
+                    .context("failed to parse message updated event")?;
+                let chat_id = bridge_message.room_id.clone();
+                let feishu_message_id = bridge_message.id.clone();
+                let event_type_for_task = event_type.clone();
+                let event_id_for_task = header_event_id.clone();
+                let dead_letter_payload = json!({
+                    "message": bridge_message
+                });
+                let dedupe_key = format!(
+                    "{}:{}",
+                    event_type_for_task,
+                    event_id_for_task
+                        .clone()
+                        .unwrap_or_else(|| feishu_message_id.clone())
+                );
+                global_metrics().record_trace_event(flow, "queued");
+                self.queue_chat_task(chat_id.clone(), async move {
+                    if let Err(err) = bridge.handle_feishu_message_updated(bridge_message).await {
+                        global_metrics().record_trace_event(flow, "failed");
+                        error!(
+                            event_type = "im.message.updated_v1",
+                            chat_id = %chat_id,
+                            feishu_message_id = %feishu_message_id,
+                            error = %err,
+                            "Failed to process Feishu message updated event"
+                        );
+                        if let Err(store_err) = bridge
+                            .record_dead_letter(
+                                &event_type_for_task,
+                                &dedupe_key,
+                                Some(chat_id.clone()),
+                                dead_letter_payload.clone(),
+                                &err.to_string(),
+                            )
+                            .await
+                        {
+                            warn!(
+                                event_type = %event_type_for_task,
+                                chat_id = %chat_id,
+                                error = %store_err,
+                                "Failed to persist dead-letter event"
+                            );
+                        }
+                        return;
+                    }
+
+                    if let Some(event_id) = &event_id_for_task
+                        && let Err(err) = bridge
+                            .mark_feishu_event_processed(event_id, &event_type_for_task)
+                            .await
+                    {
+                        warn!(
+                            event_id = %event_id,
+                            event_type = %event_type_for_task,
+                            error = %err,
+                            "Failed to mark Feishu event as processed"
+                        );
+                    }
+                    global_metrics().record_trace_event(flow, "processed");
+                })
+                .await;
+                Ok(EventDispatchResult::Accepted)
+            }
+            "im.message.message_read_v1" => {
+                let event = self
+                    .webhook_event_to_message_read(&payload)
+                    .context("failed to parse message read event")?;
+                let Some(first_message_id) = event.message_ids.first().cloned() else {
+                    global_metrics().record_trace_event(flow, "ignored");
+                    return Ok(EventDispatchResult::Ignored);
+                };
+                let chat_id = bridge
+                    .resolve_feishu_chat_for_message(&first_message_id)
+                    .await?;
+                let queue_key = chat_id.clone().unwrap_or_else(|| first_message_id.clone());
+                let reader_id = event.reader_id.clone();
+                let message_ids = event.message_ids.clone();
+                let event_type_for_task = event_type.clone();
+                let event_id_for_task = header_event_id.clone();
+                let dead_letter_payload = json!({
+                    "reader_id": event.reader_id,
+                    "message_ids": event.message_ids
+                });
+                let dedupe_key = format!(
+                    "{}:{}",
+                    event_type_for_task,
+                    event_id_for_task
+                        .clone()
+                        .unwrap_or_else(|| format!("{}:{}", reader_id, first_message_id))
+                );
+                global_metrics().record_trace_event(flow, "queued");
+                self.queue_chat_task(queue_key, async move {
+                    if let Err(err) = bridge
+                        .handle_feishu_message_read(&reader_id, &message_ids)
+                        .await
+                    {
+                        global_metrics().record_trace_event(flow, "failed");
+                        error!(
+                            event_type = "im.message.message_read_v1",
+                            feishu_reader_id = %reader_id,
+                            error = %err,
+                            "Failed to process Feishu message read event"
+                        );
+                        if let Err(store_err) = bridge
+                            .record_dead_letter(
+                                &event_type_for_task,
+                                &dedupe_key,
+                                chat_id.clone(),
+                                dead_letter_payload.clone(),
+                                &err.to_string(),
+                            )
+                            .await
+                        {
+                            warn!(
+                                event_type = %event_type_for_task,
+                                feishu_reader_id = %reader_id,
+                                error = %store_err,
+                                "Failed to persist dead-letter event"
+                            );
+                        }
+                        return;
+                    }
+
+                    if let Some(event_id) = &event_id_for_task
+                        && let Err(err) = bridge
+                            .mark_feishu_event_processed(event_id, &event_type_for_task)
+                            .await
+                    {
+                        warn!(
+                            event_id = %event_id,
+                            event_type = %event_type_for_task,
+                            error = %err,
+                            "Failed to mark Feishu event as processed"
+                        );
+                    }
+                    global_metrics().record_trace_event(flow, "processed");
+                })
+                .await;
+                Ok(EventDispatchResult::Accepted)
+            }
+            "im.message.reaction.created_v1" | "im.message.reaction.deleted_v1" => {
+                let event = self
+                    .webhook_event_to_message_reaction(&payload)
+                    .context("failed to parse message reaction event")?;
+                // Reactions made by the app itself are echoes of Matrix reactions.
+                let Some(user_id) = event
+                    .user_id
+                    .clone()
+                    .filter(|_| event.operator_type.as_deref() != Some("app"))
+                else {
+                    global_metrics().record_trace_event(flow, "ignored");
+                    return Ok(EventDispatchResult::Ignored);
+                };
+                let message_id = event.message_id.clone();
+                let emoji_type = event.emoji_type.clone();
+                let chat_id = bridge.resolve_feishu_chat_for_message(&message_id).await?;
+                let queue_key = chat_id.clone().unwrap_or_else(|| message_id.clone());
+                let event_type_for_task = event_type.clone();
+                let event_id_for_task = header_event_id.clone();
+                let dead_letter_payload = json!({
+                    "message_id": event.message_id,
+                    "emoji_type": event.emoji_type,
+                    "user_id": user_id
+                });
+                let dedupe_key = format!(
+                    "{}:{}",
+                    event_type_for_task,
+                    event_id_for_task
+                        .clone()
+                        .unwrap_or_else(|| format!("{}:{}:{}", message_id, user_id, emoji_type))
+                );
+                global_metrics().record_trace_event(flow, "queued");
+                self.queue_chat_task(queue_key.clone(), async move {
+                    let result = if event_type_for_task == "im.message.reaction.created_v1" {
+                        bridge
+                            .handle_feishu_reaction_created(&message_id, &emoji_type, &user_id)
+                            .await
+                    } else {
+                        bridge
+                            .handle_feishu_reaction_deleted(&message_id, &emoji_type, &user_id)
+                            .await
+                    };
+                    if let Err(err) = result {
+                        global_metrics().record_trace_event(flow, "failed");
+                        error!(
+                            event_type = %event_type_for_task,
+                            feishu_message_id = %message_id,
+                            error = %err,
+                            "Failed to process Feishu reaction event"
+                        );
+                        if let Err(store_err) = bridge
+                            .record_dead_letter(
+                                &event_type_for_task,
+                                &dedupe_key,
+                                chat_id.clone(),
+                                dead_letter_payload.clone(),
+                                &err.to_string(),
+                            )
+                            .await
+                        {
+                            warn!(
+                                event_type = %event_type_for_task,
+                                feishu_message_id = %message_id,
+                                error = %store_err,
+                                "Failed to persist dead-letter event"
+                            );
+                        }
+                        return;
+                    }
+
+                    if let Some(event_id) = &event_id_for_task
+                        && let Err(err) = bridge
+                            .mark_feishu_event_processed(event_id, &event_type_for_task)
+                            .await
+                    {
+                        warn!(
+                            event_id = %event_id,
+                            event_type = %event_type_for_task,
+                            error = %err,
+                            "Failed to mark Feishu event as processed"
+                        );
+                    }
+                    global_metrics().record_trace_event(flow, "processed");
+                })
+                .await;
+                Ok(EventDispatchResult::Accepted)
+            }
             _ => {
                 global_metrics().record_trace_event(flow, "ignored");
                 debug!(event_type = %event_type, "Ignoring unsupported Feishu event type");
@@ -1269,6 +1524,7 @@ impl FeishuService {
         let parsed_content = parse_feishu_message_content(&raw_content);
 
         let mut attachments = Vec::new();
+        let mut rich_content = None;
         let (content, message_type) = match msg_type.as_str() {
             "text" => (
                 parsed_content
@@ -1278,10 +1534,12 @@ impl FeishuService {
                     .to_string(),
                 MessageType::Text,
             ),
-            "post" | "rich_text" => (
-                extract_text_from_post_content(&parsed_content),
-                MessageType::RichText,
-            ),
+            "post" | "rich_text" => {
+                let rendered =
+                    feishu_post_to_matrix(&parsed_content, &FeishuRenderContext::default());
+                rich_content = Some(parsed_content.clone());
+                (rendered.body, MessageType::RichText)
+            }
             "image" => {
                 if let Some(image_key) = parsed_content.get("image_key").and_then(Value::as_str) {
                     attachments.push(build_attachment("image", image_key, "image/*"));
@@ -1355,6 +1613,8 @@ impl FeishuService {
             thread_id,
             root_id,
             parent_id,
+            rich_content,
+            mentions: parse_message_mentions(message),
         })
     }
 
@@ -1393,6 +1653,53 @@ impl FeishuService {
         })
     }
 
+    fn webhook_event_to_message_read(&self, payload: &Value) -> Result<MessageReadEvent> {
+        let event = payload
+            .get("event")
+            .ok_or_else(|| anyhow::anyhow!("missing event object"))?;
+
+        let reader_id = pick_first_string(
+            event,
+            &["/reader/reader_id/user_id", "/reader/reader_id/open_id"],
+        )
+        .ok_or_else(|| anyhow::anyhow!("missing reader_id in message read event"))?;
+        let message_ids = event
+            .get("message_id_list")
+            .and_then(Value::as_array)
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(Value::as_str)
+                    .map(ToOwned::to_owned)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        Ok(MessageReadEvent {
+            reader_id,
+            message_ids,
+        })
+    }
+
+    fn webhook_event_to_message_reaction(&self, payload: &Value) -> Result<MessageReactionEvent> {
+        let event = payload
+            .get("event")
+            .ok_or_else(|| anyhow::anyhow!("missing event object"))?;
+
+        let message_id = pick_first_string(event, &["/message_id"])
+            .ok_or_else(|| anyhow::anyhow!("missing message_id in reaction event"))?;
+        let emoji_type = pick_first_string(event, &["/reaction_type/emoji_type"])
+            .ok_or_else(|| anyhow::anyhow!("missing emoji_type in reaction event"))?;
+        let user_id = pick_first_string(event, &["/user_id/user_id", "/user_id/open_id"]);
+        let operator_type = pick_first_string(event, &["/operator_type"]);
+
+        Ok(MessageReactionEvent {
+            message_id,
+            emoji_type,
+            user_id,
+            operator_type,
+        })
+    }
+
     fn webhook_event_to_chat_member_change(
         &self,
         payload: &Value,
@@ -1449,13 +1756,30 @@ impl FeishuService {
         )
         .ok_or_else(|| anyhow::anyhow!("missing chat_id in chat updated event"))?;
 
-        let chat_name = pick_first_string(event, &["/name", "/chat/name", "/chat_info/name"]);
+        let chat_name = pick_first_string(
+            event,
+            &[
+                "/after_change/name",
+                "/name",
+                "/chat/name",
+                "/chat_info/name",
+            ],
+        );
+        let description = pick_first_string(
+            event,
+            &[
+                "/after_change/description",
+                "/description",
+                "/chat/description",
+            ],
+        );
         let chat_mode = pick_first_string(event, &["/chat_mode", "/chat/chat_mode"]);
         let chat_type = pick_first_string(event, &["/chat_type", "/chat/chat_type"]);
 
         Ok(ChatUpdatedEvent {
             chat_id,
             chat_name,
+            description,
             chat_mode,
             chat_type,
         })
@@ -1506,6 +1830,45 @@ impl FeishuService {
         result
     }
 
+    pub async fn find_user_id_by_email(&self, email: &str) -> Result<Option<String>> {
+        let api = "contact.v3.users.batch_get_id";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client.find_user_id_by_email(email).await;
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        }
+        result
+    }
+
+    pub async fn list_contact_scope_users(
+        &self,
+        page_token: Option<&str>,
+    ) -> Result<FeishuContactScopePage> {
+        let api = "contact.v3.scopes.list";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client.list_contact_scope_users(page_token).await;
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        }
+        result
+    }
+
+    pub async fn batch_get_users(&self, user_ids: &[String]) -> Result<Vec<FeishuUserSummary>> {
+        let api = "contact.v3.users.batch";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client.batch_get_users(user_ids).await;
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        }
+        result
+    }
+
     pub async fn get_chat(&self, chat_id: &str) -> Result<FeishuChatProfile> {
         let cache_name = "feishu_chat_meta";
         {
@@ -1534,6 +1897,155 @@ impl FeishuService {
         result
     }
 
+    pub async fn create_chat(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        avatar_key: Option<&str>,
+        user_ids: &[String],
+    ) -> Result<String> {
+        let api = "im.v1.chats.create";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client
+            .create_chat(name, description, avatar_key, user_ids)
+            .await;
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        }
+        result
+    }
+
+    pub async fn delete_chat(&self, chat_id: &str) -> Result<()> {
+        let api = "im.v1.chats.delete";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client.delete_chat(chat_id).await;
+        drop(client);
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        } else {
+            self.invalidate_chat_cache(chat_id).await;
+        }
+        result
+    }
+
+    pub async fn list_chats(&self, page_token: Option<&str>) -> Result<FeishuChatListPage> {
+        let api = "im.v1.chats.list";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client.list_chats(page_token).await;
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        }
+        result
+    }
+
+    pub async fn is_bot_in_chat(&self, chat_id: &str) -> Result<bool> {
+        let api = "im.v1.chat_members.is_in_chat";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client.is_bot_in_chat(chat_id).await;
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        }
+        result
+    }
+
+    pub async fn list_chat_members(
+        &self,
+        chat_id: &str,
+        page_token: Option<&str>,
+    ) -> Result<FeishuChatMemberPage> {
+        let api = "im.v1.chat_members.get";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client.list_chat_members(chat_id, page_token).await;
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        }
+        result
+    }
+
+    pub async fn add_chat_members(&self, chat_id: &str, user_ids: &[String]) -> Result<()> {
+        let api = "im.v1.chat_members.create";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client.add_chat_members(chat_id, user_ids).await;
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        }
+        result
+    }
+
+    pub async fn remove_chat_members(&self, chat_id: &str, user_ids: &[String]) -> Result<()> {
+        let api = "im.v1.chat_members.delete";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client.remove_chat_members(chat_id, user_ids).await;
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        }
+        result
+    }
+
+    pub async fn add_chat_managers(&self, chat_id: &str, user_ids: &[String]) -> Result<()> {
+        let api = "im.v1.chat_managers.add_managers";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client.add_chat_managers(chat_id, user_ids).await;
+        drop(client);
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        } else {
+            self.invalidate_chat_cache(chat_id).await;
+        }
+        result
+    }
+
+    pub async fn remove_chat_managers(&self, chat_id: &str, user_ids: &[String]) -> Result<()> {
+        let api = "im.v1.chat_managers.delete_managers";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client.remove_chat_managers(chat_id, user_ids).await;
+        drop(client);
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        } else {
+            self.invalidate_chat_cache(chat_id).await;
+        }
+        result
+    }
+
+    pub async fn update_chat(
+        &self,
+        chat_id: &str,
+        name: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<()> {
+        let api = "im.v1.chats.update";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client.update_chat(chat_id, name, description).await;
+        drop(client);
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        } else {
+            self.invalidate_chat_cache(chat_id).await;
+        }
+        result
+    }
+
     pub async fn invalidate_user_cache(&self, user_id: &str) {
         self.user_cache
             .lock()
@@ -1647,6 +2159,36 @@ impl FeishuService {
         result
     }
 
+    pub async fn add_message_reaction(
+        &self,
+        message_id: &str,
+        emoji_type: &str,
+    ) -> Result<FeishuMessageReaction> {
+        let api = "im.v1.message_reaction.create";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client.add_message_reaction(message_id, emoji_type).await;
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        }
+        result
+    }
+
+    pub async fn delete_message_reaction(&self, message_id: &str, reaction_id: &str) -> Result<()> {
+        let api = "im.v1.message_reaction.delete";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client
+            .delete_message_reaction(message_id, reaction_id)
+            .await;
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        }
+        result
+    }
+
     pub async fn get_message(&self, message_id: &str) -> Result<Option<FeishuMessageData>> {
         let api = "im.v1.messages.get";
         global_metrics().record_outbound_call(api);
@@ -1690,6 +2232,18 @@ impl FeishuService {
         result
     }
 
+    pub async fn upload_avatar(&self, image_data: Vec<u8>, image_type: &str) -> Result<String> {
+        let api = "im.v1.images.create";
+        global_metrics().record_outbound_call(api);
+        let mut client = self.client.lock().await;
+        let result = client.upload_image(image_data, image_type, "avatar").await;
+        if let Err(err) = &result {
+            global_metrics().record_outbound_failure(api, &extract_error_code(err));
+            log_feishu_api_failure(api, err);
+        }
+        result
+    }
+
     pub async fn upload_file(
         &self,
         file_name: &str,
@@ -1929,64 +2483,6 @@ fn parse_feishu_message_content(raw_content: &Value) -> Value {
     }
 }
 
-fn extract_text_from_post_content(content: &Value) -> String {
-    let mut parts = Vec::new();
-
-    if let Some(locale_obj) = content.as_object() {
-        for value in locale_obj.values() {
-            if let Some(rows) = value.get("content").and_then(Value::as_array) {
-                for row in rows {
-                    if let Some(items) = row.as_array() {
-                        for item in items {
-                            let tag = item.get("tag").and_then(Value::as_str).unwrap_or("");
-                            match tag {
-                                "text" => {
-                                    if let Some(text) = item.get("text").and_then(Value::as_str) {
-                                        parts.push(text.to_string());
-                                    }
-                                }
-                                "a" => {
-                                    let text =
-                                        item.get("text").and_then(Value::as_str).unwrap_or("");
-                                    let href =
-                                        item.get("href").and_then(Value::as_str).unwrap_or("");
-                                    if text.is_empty() {
-                                        parts.push(href.to_string());
-                                    } else {
-                                        parts.push(format!("{} ({})", text, href));
-                                    }
-                                }
-                                "at" => {
-                                    let text = item
-                                        .get("user_name")
-                                        .and_then(Value::as_str)
-                                        .or_else(|| item.get("user_id").and_then(Value::as_str))
-                                        .unwrap_or("user");
-                                    parts.push(format!("@{}", text));
-                                }
-                                "img" => {
-                                    parts.push("[Image]".to_string());
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    if parts.is_empty() {
-        content
-            .get("text")
-            .and_then(Value::as_str)
-            .unwrap_or_default()
-            .to_string()
-    } else {
-        parts.join("")
-    }
-}
-
 fn extract_text_from_card_content(content: &Value) -> String {
     let mut parts = Vec::new();
 
@@ -2083,6 +2579,29 @@ fn log_feishu_api_failure(api: &str, err: &anyhow::Error) {
     );
 }
 
+fn parse_message_mentions(message: &Value) -> Vec<MessageMention> {
+    message
+        .get("mentions")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|mention| {
+            let key = pick_first_string(mention, &["/key"])?;
+            let feishu_user_id = pick_first_string(
+                mention,
+                &["/id/user_id", "/id/open_id", "/id/union_id", "/id"],
+            )
+            .unwrap_or_default();
+            let name = pick_first_string(mention, &["/name"]).unwrap_or_default();
+            Some(MessageMention {
+                key,
+                feishu_user_id,
+                name,
+            })
+        })
+        .collect()
+}
+
 fn pick_first_string(root: &Value, pointers: &[&str]) -> Option<String> {
     pointers.iter().find_map(|pointer| {
         root.pointer(pointer)
@@ -2253,6 +2772,30 @@ mod tests {
         assert_eq!(parsed.chat_type.as_deref(), Some("group"));
     }
 
+    #[test]
+    fn parse_chat_updated_event_reads_after_change() {
+        let service = build_service();
+        let payload = json!({
+            "event": {
+                "chat_id": "oc_chat",
+                "after_change": {
+                    "name": "Renamed",
+                    "description": "New topic"
+                },
+                "before_change": {
+                    "name": "Old name"
+                }
+            }
+        });
+
+        let parsed = service
+            .webhook_event_to_chat_updated(&payload)
+            .expect("chat updated should parse");
+        assert_eq!(parsed.chat_id, "oc_chat");
+        assert_eq!(parsed.chat_name.as_deref(), Some("Renamed"));
+        assert_eq!(parsed.description.as_deref(), Some("New topic"));
+    }
+
     #[test]
     fn parse_receive_event_image_extracts_attachment_and_thread_fields() {
         let service = build_service();
@@ -2288,6 +2831,46 @@ mod tests {
         assert_eq!(parsed.parent_id.as_deref(), Some("om_parent"));
     }
 
+    #[test]
+    fn parse_receive_event_collects_mentions() {
+        let service = build_service();
+        let payload = json!({
+            "event": {
+                "sender": {
+                    "sender_id": {
+                        "open_id": "ou_sender"
+                    }
+                },
+                "message": {
+                    "message_id": "om_mention",
+                    "chat_id": "oc_chat",
+                    "msg_type": "text",
+                    "create_time": "1700000000",
+                    "content": "{\"text\":\"@_user_1 hello\"}",
+                    "mentions": [
+                        {
+                            "key": "@_user_1",
+                            "id": {
+                                "open_id": "ou_alice",
+                                "user_id": "alice"
+                            },
+                            "name": "Alice"
+                        }
+                    ]
+                }
+            }
+        });
+
+        let parsed = service
+            .webhook_event_to_bridge_message(&payload)
+            .expect("receive event with mentions should parse");
+        assert_eq!(parsed.content, "@_user_1 hello");
+        assert_eq!(parsed.mentions.len(), 1);
+        assert_eq!(parsed.mentions[0].key, "@_user_1");
+        assert_eq!(parsed.mentions[0].feishu_user_id, "alice");
+        assert_eq!(parsed.mentions[0].name, "Alice");
+    }
+
     #[test]
     fn parse_receive_event_post_extracts_text_content() {
         let service = build_service();
@@ -2367,6 +2950,62 @@ mod tests {
         assert_eq!(parsed.content, "legacy hello");
     }
 
+    #[test]
+    fn parse_message_read_event() {
+        let service = build_service();
+        let payload = json!({
+            "header": {
+                "event_type": "im.message.message_read_v1"
+            },
+            "event": {
+                "reader": {
+                    "reader_id": {
+                        "open_id": "ou_reader",
+                        "user_id": "u_reader"
+                    },
+                    "read_time": "1700000040000"
+                },
+                "message_id_list": ["om_first", "om_second"]
+            }
+        });
+
+        let parsed = service
+            .webhook_event_to_message_read(&payload)
+            .expect("message read event should parse");
+        assert_eq!(parsed.reader_id, "u_reader");
+        assert_eq!(parsed.message_ids, vec!["om_first", "om_second"]);
+    }
+
+    #[test]
+    fn parse_message_reaction_event() {
+        let service = build_service();
+        let payload = json!({
+            "header": {
+                "event_type": "im.message.reaction.created_v1"
+            },
+            "event": {
+                "message_id": "om_reacted",
+                "reaction_type": {
+                    "emoji_type": "THUMBSUP"
+                },
+                "operator_type": "user",
+                "user_id": {
+                    "open_id": "ou_reactor",
+                    "user_id": "u_reactor"
+                },
+                "action_time": "1700000030000"
+            }
+        });
+
+        let parsed = service
+            .webhook_event_to_message_reaction(&payload)
+            .expect("reaction event should parse");
+        assert_eq!(parsed.message_id, "om_reacted");
+        assert_eq!(parsed.emoji_type, "THUMBSUP");
+        assert_eq!(parsed.user_id.as_deref(), Some("u_reactor"));
+        assert_eq!(parsed.operator_type.as_deref(), Some("user"));
+    }
+
     #[test]
     fn extract_event_type_accepts_legacy_event_path() {
         let payload = json!({
@@ -152,11 +152,61 @@ pub struct FeishuUserStatus {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeishuChatProfile {
+    #[serde(default)]
     pub chat_id: String,
     pub name: Option<String>,
+    pub avatar: Option<String>,
     pub description: Option<String>,
     pub chat_mode: Option<String>,
     pub chat_type: Option<String>,
+    pub tenant_key: Option<String>,
+    pub owner_id: Option<String>,
+    #[serde(default)]
+    pub user_manager_id_list: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeishuChatListPage {
+    #[serde(default)]
+    pub items: Vec<FeishuChatProfile>,
+    pub page_token: Option<String>,
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeishuContactScopePage {
+    #[serde(default)]
+    pub user_ids: Vec<String>,
+    pub page_token: Option<String>,
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeishuUserSummary {
+    pub user_id: String,
+    #[serde(default)]
+    pub name: String,
+    pub en_name: Option<String>,
+    pub avatar: Option<FeishuAvatar>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeishuChatMember {
+    pub member_id: String,
+    pub member_id_type: Option<String>,
+    pub name: Option<String>,
+    pub tenant_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeishuChatMemberPage {
+    #[serde(default)]
+    pub items: Vec<FeishuChatMember>,
+    pub page_token: Option<String>,
+    #[serde(default)]
+    pub has_more: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -267,11 +317,23 @@ pub struct FeishuApiEnvelope<T> {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeishuMessageSendData {
     pub message_id: String,
+    pub chat_id: Option<String>,
     pub root_id: Option<String>,
     pub parent_id: Option<String>,
     pub thread_id: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeishuReactionType {
+    pub emoji_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeishuMessageReaction {
+    pub reaction_id: String,
+    pub reaction_type: Option<FeishuReactionType>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeishuMessageListData {
     #[serde(default)]
@@ -347,8 +409,8 @@ pub const FEISHU_MESSAGE_CAPABILITIES: &[FeishuCapabilityMatrixRow] = &[
     FeishuCapabilityMatrixRow {
         capability: "post",
         status: CapabilityStatus::Supported,
-        degrade_strategy: "flatten post blocks to readable text",
-        code_entry: "src/feishu/service.rs:extract_text_from_post_content",
+        degrade_strategy: "render post blocks as Matrix HTML with plain-text body",
+        code_entry: "src/formatter/feishu_to_matrix.rs:feishu_post_to_matrix",
     },
     FeishuCapabilityMatrixRow {
         capability: "interactive/card",
@@ -395,4 +457,22 @@ pub const FEISHU_EVENT_CAPABILITIES: &[FeishuCapabilityMatrixRow] = &[
         degrade_strategy: "missing mapping clears in-memory cache only",
         code_entry: "src/bridge/feishu_bridge.rs:handle_feishu_chat_disbanded",
     },
+    FeishuCapabilityMatrixRow {
+        capability: "im.message.updated_v1",
+        status: CapabilityStatus::Supported,
+        degrade_strategy: "missing mapping or media-only edits log and skip",
+        code_entry: "src/bridge/feishu_bridge.rs:handle_feishu_message_updated",
+    },
+    FeishuCapabilityMatrixRow {
+        capability: "im.message.message_read_v1",
+        status: CapabilityStatus::Supported,
+        degrade_strategy: "rate-limited per room; excess receipts are dropped",
+        code_entry: "src/bridge/feishu_bridge.rs:handle_feishu_message_read",
+    },
+    FeishuCapabilityMatrixRow {
+        capability: "im.message.reaction.created_v1 / deleted_v1",
+        status: CapabilityStatus::Supported,
+        degrade_strategy: "unknown emoji_type is sent as its raw name",
+        code_entry: "src/bridge/feishu_bridge.rs:handle_feishu_reaction_created",
+    },
 ];
@@ -0,0 +1,116 @@
+// Feishu reaction `emoji_type` values and their closest Unicode emoji.
+// The first entry for a given emoji wins when mapping Unicode -> Feishu.
+const FEISHU_EMOJI_TYPES: &[(&str, &str)] = &[
+    ("THUMBSUP", "👍"),
+    ("ThumbsDown", "👎"),
+    ("OK", "👌"),
+    ("THANKS", "🙏"),
+    ("MUSCLE", "💪"),
+    ("FINGERHEART", "🫰"),
+    ("APPLAUSE", "👏"),
+    ("CLAP", "👏"),
+    ("FISTBUMP", "👊"),
+    ("DONE", "✅"),
+    ("CheckMark", "✔️"),
+    ("CrossMark", "❌"),
+    ("Yes", "🆗"),
+    ("No", "🙅"),
+    ("SMILE", "😊"),
+    ("BLUSH", "😳"),
+    ("LAUGH", "😄"),
+    ("LOL", "😂"),
+    ("SMIRK", "😏"),
+    ("WINK", "😉"),
+    ("LOVE", "😍"),
+    ("KISS", "😘"),
+    ("TONGUE", "😛"),
+    ("THINKING", "🤔"),
+    ("FACEPALM", "🤦"),
+    ("SOB", "😭"),
+    ("CRY", "😢"),
+    ("TEARS", "🥲"),
+    ("ANGRY", "😠"),
+    ("SHOCKED", "😱"),
+    ("WOW", "😮"),
+    ("SWEAT", "😓"),
+    ("SPEECHLESS", "😶"),
+    ("SLEEP", "😴"),
+    ("YAWN", "🥱"),
+    ("SICK", "🤢"),
+    ("PUKE", "🤮"),
+    ("DIZZY", "😵"),
+    ("SHHH", "🤫"),
+    ("SKULL", "💀"),
+    ("HUG", "🤗"),
+    ("SALUTE", "🫡"),
+    ("WAVE", "👋"),
+    ("HIGHFIVE", "🙌"),
+    ("SHAKE", "🤝"),
+    ("HEART", "❤️"),
+    ("HEARTBROKEN", "💔"),
+    ("ROSE", "🌹"),
+    ("PARTY", "🎉"),
+    ("FIREWORKS", "🎆"),
+    ("GIFT", "🎁"),
+    ("CAKE", "🎂"),
+    ("BEER", "🍺"),
+    ("Coffee", "☕"),
+    ("Fire", "🔥"),
+    ("Trophy", "🏆"),
+    ("Hundred", "💯"),
+    ("Pin", "📌"),
+    ("Alarm", "⏰"),
+    ("Loudspeaker", "📢"),
+    ("BOMB", "💣"),
+    ("POOP", "💩"),
+    ("Music", "🎵"),
+    ("Lemon", "🍋"),
+    ("REDPACKET", "🧧"),
+    ("XmasTree", "🎄"),
+    ("Snowman", "⛄"),
+    ("Soccer", "⚽"),
+    ("Basketball", "🏀"),
+    ("EYESCLOSED", "😌"),
+    ("PROUD", "😎"),
+    ("EMBARRASSED", "😅"),
+    ("WRONGED", "🥺"),
+    ("TERROR", "😨"),
+    ("MONEY", "🤑"),
+    ("CALF", "🐮"),
+    ("BEAR", "🐻"),
+];
+
+fn strip_variation_selectors(emoji: &str) -> String {
+    emoji.chars().filter(|ch| *ch != '\u{fe0f}').collect()
+}
+
+pub fn feishu_emoji_type_for_unicode(emoji: &str) -> Option<&'static str> {
+    let emoji = strip_variation_selectors(emoji.trim());
+    FEISHU_EMOJI_TYPES
+        .iter()
+        .find(|(_, unicode)| strip_variation_selectors(unicode) == emoji)
+        .map(|(emoji_type, _)| *emoji_type)
+}
+
+pub fn unicode_for_feishu_emoji_type(emoji_type: &str) -> Option<&'static str> {
+    FEISHU_EMOJI_TYPES
+        .iter()
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(emoji_type))
+        .map(|(_, unicode)| *unicode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{feishu_emoji_type_for_unicode, unicode_for_feishu_emoji_type};
+
+    #[test]
+    fn maps_unicode_and_feishu_emoji_types_both_ways() {
+        assert_eq!(feishu_emoji_type_for_unicode("👍"), Some("THUMBSUP"));
+        assert_eq!(feishu_emoji_type_for_unicode("❤"), Some("HEART"));
+        assert_eq!(feishu_emoji_type_for_unicode("❤️"), Some("HEART"));
+        assert_eq!(feishu_emoji_type_for_unicode("🦀"), None);
+        assert_eq!(unicode_for_feishu_emoji_type("THUMBSUP"), Some("👍"));
+        assert_eq!(unicode_for_feishu_emoji_type("thumbsdown"), Some("👎"));
+        assert_eq!(unicode_for_feishu_emoji_type("UNKNOWN_TYPE"), None);
+    }
+}
@@ -1,6 +1,105 @@
+use std::collections::HashMap;
+
+use serde_json::{Value, json};
+
+use super::emoji::unicode_for_feishu_emoji_type;
 use crate::bridge::message::{BridgeMessage, MessageType};
 use crate::feishu::types::FeishuMessage;
 
+const POST_LOCALE_PREFERENCE: &[&str] = &["zh_cn", "en_us", "ja_jp"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixFormattedText {
+    pub body: String,
+    pub formatted_body: String,
+    pub mentioned_user_ids: Vec<String>,
+    pub mentions_room: bool,
+}
+
+impl MatrixFormattedText {
+    pub fn m_mentions(&self) -> Option<Value> {
+        if self.mentioned_user_ids.is_empty() && !self.mentions_room {
+            return None;
+        }
+        let mut mentions = json!({ "user_ids": self.mentioned_user_ids });
+        if self.mentions_room {
+            mentions["room"] = Value::Bool(true);
+        }
+        Some(mentions)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixPill {
+    // None when the Feishu user has no Matrix counterpart yet.
+    pub user_id: Option<String>,
+    pub display_name: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FeishuRenderContext {
+    // Feishu image_key -> uploaded mxc:// URI
+    pub image_urls: HashMap<String, String>,
+    // Feishu mention key (`@_user_1`) or user ID -> Matrix user
+    pub mentions: HashMap<String, MatrixPill>,
+}
+
+#[derive(Default)]
+struct MentionCollector {
+    user_ids: Vec<String>,
+    room: bool,
+}
+
+impl MentionCollector {
+    fn render(
+        &mut self,
+        key: &str,
+        fallback_name: &str,
+        context: &FeishuRenderContext,
+    ) -> (String, String) {
+        if matches!(key, "all" | "@_all") {
+            self.room = true;
+            return ("@room".to_string(), "@room".to_string());
+        }
+
+        match context.mentions.get(key) {
+            Some(MatrixPill {
+                user_id: Some(user_id),
+                display_name,
+            }) => {
+                if !self.user_ids.contains(user_id) {
+                    self.user_ids.push(user_id.clone());
+                }
+                let html = format!(
+                    "<a href=\"https://matrix.to/#/{}\">{}</a>",
+                    escape_html(user_id),
+                    escape_html(display_name)
+                );
+                (display_name.clone(), html)
+            }
+            Some(MatrixPill { display_name, .. }) => {
+                let mention = format!("@{}", display_name);
+                let html = escape_html(&mention);
+                (mention, html)
+            }
+            None => {
+                let mention = format!("@{}", fallback_name);
+                let html = escape_html(&mention);
+                (mention, html)
+            }
+        }
+    }
+
+    fn finish(self, body: String, formatted_body: String) -> MatrixFormattedText {
+        MatrixFormattedText {
+            body,
+            formatted_body,
+            mentioned_user_ids: self.user_ids,
+            mentions_room: self.room,
+        }
+    }
+}
+
 pub fn format_feishu_to_matrix(message: FeishuMessage) -> BridgeMessage {
     let (content, msg_type, attachments) = match message.msg_type.as_str() {
         "text" => {
@@ -68,6 +167,8 @@ pub fn format_feishu_to_matrix(message: FeishuMessage) -> BridgeMessage {
         thread_id: message.thread_id,
         root_id: message.root_id,
         parent_id: message.parent_id,
+        rich_content: None,
+        mentions: Vec::new(),
     }
 }
 
@@ -145,6 +246,377 @@ fn extract_text_from_card(card: &Option<crate::feishu::types::FeishuCard>) -> St
     }
 }
 
+// Feishu delivers posts either wrapped in a locale (`{"zh_cn": {...}}`) or
+// already unwrapped (`{"title": ..., "content": [[...]]}`).
+fn feishu_post_body(content: &Value) -> Option<&Value> {
+    if content.get("content").is_some_and(Value::is_array) {
+        return Some(content);
+    }
+
+    let locales = content.as_object()?;
+    POST_LOCALE_PREFERENCE
+        .iter()
+        .filter_map(|locale| locales.get(*locale))
+        .chain(locales.values())
+        .find(|value| value.get("content").is_some_and(Value::is_array))
+}
+
+fn feishu_post_rows(post: &Value) -> impl Iterator<Item = &Value> {
+    post.get("content")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_array)
+        .flatten()
+}
+
+pub fn extract_post_image_keys(content: &Value) -> Vec<String> {
+    let Some(post) = feishu_post_body(content) else {
+        return Vec::new();
+    };
+
+    let mut keys: Vec<String> = Vec::new();
+    for element in feishu_post_rows(post) {
+        if element.get("tag").and_then(Value::as_str) == Some("img")
+            && let Some(key) = element.get("image_key").and_then(Value::as_str)
+            && !key.is_empty()
+            && !keys.iter().any(|existing| existing == key)
+        {
+            keys.push(key.to_string());
+        }
+    }
+    keys
+}
+
+struct PostSegment {
+    body: String,
+    html: String,
+    block: bool,
+}
+
+impl PostSegment {
+    fn inline(body: String, html: String) -> Self {
+        Self {
+            body,
+            html,
+            block: false,
+        }
+    }
+
+    fn block(body: String, html: String) -> Self {
+        Self {
+            body,
+            html,
+            block: true,
+        }
+    }
+}
+
+// Feishu text messages carry mentions as `@_user_N` placeholders.
+pub fn feishu_text_to_matrix(text: &str, context: &FeishuRenderContext) -> MatrixFormattedText {
+    let mut keys: Vec<&str> = context
+        .mentions
+        .keys()
+        .map(String::as_str)
+        .chain(["@_all"])
+        .filter(|key| key.starts_with('@'))
+        .collect();
+    keys.sort_by_key(|key| std::cmp::Reverse(key.len()));
+
+    let mut mentions = MentionCollector::default();
+    let mut body = String::with_capacity(text.len());
+    let mut html = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('@') {
+        let plain = &rest[..start];
+        body.push_str(plain);
+        html.push_str(&escape_html(plain).replace('\n', "<br/>"));
+
+        let candidate = &rest[start..];
+        match keys.iter().find(|key| candidate.starts_with(**key)) {
+            Some(key) => {
+                let (mention_body, mention_html) = mentions.render(key, key, context);
+                body.push_str(&mention_body);
+                html.push_str(&mention_html);
+                rest = &candidate[key.len()..];
+            }
+            None => {
+                body.push('@');
+                html.push('@');
+                rest = &candidate[1..];
+            }
+        }
+    }
+    body.push_str(rest);
+    html.push_str(&escape_html(rest).replace('\n', "<br/>"));
+
+    mentions.finish(body, html)
+}
+
+pub fn feishu_post_to_matrix(
+    content: &Value,
+    context: &FeishuRenderContext,
+) -> MatrixFormattedText {
+    let Some(post) = feishu_post_body(content) else {
+        let text = content
+            .get("text")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        return feishu_text_to_matrix(text, context);
+    };
+
+    let mut mentions = MentionCollector::default();
+    let mut rows = Vec::new();
+    if let Some(title) = post
+        .get("title")
+        .and_then(Value::as_str)
+        .filter(|title| !title.trim().is_empty())
+    {
+        rows.push(vec![PostSegment::inline(
+            title.to_string(),
+            format!("<strong>{}</strong>", escape_html(title)),
+        )]);
+    }
+
+    for row in post
+        .get("content")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let segments: Vec<PostSegment> = row
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|element| render_post_element(element, context, &mut mentions))
+            .collect();
+        rows.push(segments);
+    }
+
+    let mut body_lines = Vec::with_capacity(rows.len());
+    let mut html = String::new();
+    let mut previous_ends_block = true;
+    for row in &rows {
+        body_lines.push(
+            row.iter()
+                .map(|segment| segment.body.as_str())
+                .collect::<String>(),
+        );
+
+        let starts_block = row.first().is_some_and(|segment| segment.block);
+        if !previous_ends_block && !starts_block {
+            html.push_str("<br/>");
+        }
+        for segment in row {
+            html.push_str(&segment.html);
+        }
+        previous_ends_block = row.last().is_some_and(|segment| segment.block);
+    }
+
+    mentions.finish(body_lines.join("\n").trim_end().to_string(), html)
+}
+
+fn render_post_element(
+    element: &Value,
+    context: &FeishuRenderContext,
+    mentions: &mut MentionCollector,
+) -> Option<PostSegment> {
+    let text = element
+        .get("text")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+
+    match element.get("tag").and_then(Value::as_str)? {
+        "text" => Some(PostSegment::inline(
+            text.to_string(),
+            apply_post_styles(element, escape_html(text).replace('\n', "<br/>")),
+        )),
+        "a" => {
+            let href = element
+                .get("href")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let label = if text.is_empty() { href } else { text };
+            let body = if text.is_empty() || text == href {
+                href.to_string()
+            } else {
+                format!("{} ({})", text, href)
+            };
+            let html = format!(
+                "<a href=\"{}\">{}</a>",
+                escape_html(href),
+                escape_html(label)
+            );
+            Some(PostSegment::inline(body, apply_post_styles(element, html)))
+        }
+        "at" => {
+            let key = element
+                .get("user_id")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let name = element
+                .get("user_name")
+                .and_then(Value::as_str)
+                .filter(|name| !name.is_empty())
+                .or(Some(key).filter(|key| !key.is_empty()))
+                .unwrap_or("user");
+            let (body, html) = mentions.render(key, name, context);
+            Some(PostSegment::inline(body, html))
+        }
+        "img" => {
+            let key = element
+                .get("image_key")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let html = match context.image_urls.get(key) {
+                Some(mxc) => format!("<img src=\"{}\" alt=\"[Image]\"/>", escape_html(mxc)),
+                None => "[Image]".to_string(),
+            };
+            Some(PostSegment::inline("[Image]".to_string(), html))
+        }
+        "media" => Some(PostSegment::inline(
+            "[Video]".to_string(),
+            "[Video]".to_string(),
+        )),
+        "emotion" => {
+            let emoji_type = element
+                .get("emoji_type")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let emoji = unicode_for_feishu_emoji_type(emoji_type)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!(":{}:", emoji_type));
+            let html = escape_html(&emoji);
+            Some(PostSegment::inline(emoji, html))
+        }
+        "code_block" => {
+            let language = element
+                .get("language")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            let code = text.trim_end_matches('\n');
+            let html = if language.is_empty() || language == "plain_text" {
+                format!("<pre><code>{}</code></pre>", escape_html(code))
+            } else {
+                format!(
+                    "<pre><code class=\"language-{}\">{}</code></pre>",
+                    escape_html(&language),
+                    escape_html(code)
+                )
+            };
+            let fence_language = if language == "plain_text" {
+                ""
+            } else {
+                language.as_str()
+            };
+            Some(PostSegment::block(
+                format!("```{}\n{}\n```", fence_language, code),
+                html,
+            ))
+        }
+        "hr" => Some(PostSegment::block("---".to_string(), "<hr/>".to_string())),
+        "md" => Some(render_post_markdown(text)),
+        _ => None,
+    }
+}
+
+fn apply_post_styles(element: &Value, mut html: String) -> String {
+    let styles: Vec<&str> = element
+        .get("style")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+
+    for (style, tag) in [
+        ("bold", "strong"),
+        ("italic", "em"),
+        ("underline", "u"),
+        ("lineThrough", "del"),
+    ] {
+        if styles.contains(&style) {
+            html = format!("<{tag}>{html}</{tag}>");
+        }
+    }
+    html
+}
+
+// `md` elements carry raw markdown. Only list items get structure here; the
+// rest is kept as escaped text so nothing the sender typed is lost.
+fn render_post_markdown(text: &str) -> PostSegment {
+    let mut html = String::new();
+    let mut open_list: Option<&str> = None;
+    let mut pending_break = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let list_item = if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            Some(("ul", item))
+        } else {
+            trimmed
+                .split_once(". ")
+                .filter(|(number, _)| {
+                    !number.is_empty() && number.chars().all(|ch| ch.is_ascii_digit())
+                })
+                .map(|(_, item)| ("ol", item))
+        };
+
+        match list_item {
+            Some((list_tag, item)) => {
+                if open_list != Some(list_tag) {
+                    if let Some(open) = open_list.take() {
+                        html.push_str(&format!("</{}>", open));
+                    }
+                    html.push_str(&format!("<{}>", list_tag));
+                    open_list = Some(list_tag);
+                }
+                html.push_str(&format!("<li>{}</li>", escape_html(item)));
+                pending_break = false;
+            }
+            None => {
+                if let Some(open) = open_list.take() {
+                    html.push_str(&format!("</{}>", open));
+                } else if pending_break {
+                    html.push_str("<br/>");
+                }
+                html.push_str(&escape_html(line));
+                pending_break = true;
+            }
+        }
+    }
+
+    let ends_with_list = open_list.is_some();
+    if let Some(open) = open_list {
+        html.push_str(&format!("</{}>", open));
+    }
+
+    PostSegment {
+        body: text.to_string(),
+        html,
+        block: ends_with_list,
+    }
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
 pub fn convert_feishu_content_to_matrix_html(content: &str) -> String {
     // Convert Feishu specific formatting to Matrix HTML
     let html = content
@@ -205,11 +677,15 @@ pub fn convert_feishu_emoticons(content: &str) -> String {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use chrono::Utc;
+    use serde_json::json;
 
     use super::{
-        convert_feishu_emoticons, extract_links_from_rich_text, extract_mentions_from_rich_text,
-        format_feishu_to_matrix,
+        FeishuRenderContext, MatrixPill, convert_feishu_emoticons, extract_links_from_rich_text,
+        extract_mentions_from_rich_text, extract_post_image_keys, feishu_post_to_matrix,
+        feishu_text_to_matrix, format_feishu_to_matrix,
     };
     use crate::bridge::message::MessageType;
     use crate::feishu::types::{
@@ -292,4 +768,122 @@ mod tests {
         assert!(matches!(bridged.msg_type, MessageType::Text));
         assert!(bridged.content.contains("[Unsupported: unknown]"));
     }
+
+    #[test]
+    fn feishu_post_renders_styles_links_and_inline_images() {
+        let post = json!({
+            "zh_cn": {
+                "title": "Release <notes>",
+                "content": [
+                    [
+                        {"tag": "text", "text": "bold", "style": ["bold"]},
+                        {"tag": "text", "text": " and "},
+                        {"tag": "text", "text": "gone", "style": ["italic", "lineThrough"]},
+                        {"tag": "a", "text": " docs", "href": "https://example.com/?a=1&b=2"}
+                    ],
+                    [
+                        {"tag": "at", "user_id": "ou_1", "user_name": "alice"},
+                        {"tag": "emotion", "emoji_type": "THUMBSUP"},
+                        {"tag": "img", "image_key": "img_1"},
+                        {"tag": "img", "image_key": "img_2"}
+                    ]
+                ]
+            }
+        });
+        let context = FeishuRenderContext {
+            image_urls: HashMap::from([("img_1".to_string(), "mxc://example.org/abc".to_string())]),
+            ..Default::default()
+        };
+
+        assert_eq!(extract_post_image_keys(&post), vec!["img_1", "img_2"]);
+
+        let rendered = feishu_post_to_matrix(&post, &context);
+        assert_eq!(
+            rendered.body,
+            "Release <notes>\nbold and gone docs (https://example.com/?a=1&b=2)\n@alice👍[Image][Image]"
+        );
+        assert_eq!(
+            rendered.formatted_body,
+            "<strong>Release &lt;notes&gt;</strong><br/>\
+             <strong>bold</strong> and <del><em>gone</em></del>\
+             <a href=\"https://example.com/?a=1&amp;b=2\"> docs</a><br/>\
+             @alice👍<img src=\"mxc://example.org/abc\" alt=\"[Image]\"/>[Image]"
+        );
+    }
+
+    #[test]
+    fn feishu_post_renders_code_blocks_rules_and_markdown_lists() {
+        let post = json!({
+            "title": "",
+            "content": [
+                [{"tag": "text", "text": "before"}],
+                [{"tag": "code_block", "language": "Rust", "text": "let x = 1 < 2;\n"}],
+                [{"tag": "hr"}],
+                [{"tag": "md", "text": "steps:\n1. one\n2. two\n- dot"}],
+                [{"tag": "text", "text": "after"}]
+            ]
+        });
+
+        let rendered = feishu_post_to_matrix(&post, &FeishuRenderContext::default());
+        assert_eq!(
+            rendered.body,
+            "before\n```rust\nlet x = 1 < 2;\n```\n---\nsteps:\n1. one\n2. two\n- dot\nafter"
+        );
+        assert_eq!(
+            rendered.formatted_body,
+            "before<pre><code class=\"language-rust\">let x = 1 &lt; 2;</code></pre><hr/>\
+             steps:<ol><li>one</li><li>two</li></ol><ul><li>dot</li></ul>after"
+        );
+    }
+    #[test]
+    fn feishu_mentions_become_matrix_pills() {
+        let context = FeishuRenderContext {
+            mentions: HashMap::from([
+                (
+                    "@_user_1".to_string(),
+                    MatrixPill {
+                        user_id: Some("@feishu_alice:example.com".to_string()),
+                        display_name: "Alice".to_string(),
+                    },
+                ),
+                (
+                    "@_user_10".to_string(),
+                    MatrixPill {
+                        user_id: None,
+                        display_name: "Ghost".to_string(),
+                    },
+                ),
+            ]),
+            ..Default::default()
+        };
+
+        let text = feishu_text_to_matrix("@_user_1 @_user_10 & @_all: see a@b", &context);
+        assert_eq!(text.body, "Alice @Ghost & @room: see a@b");
+        assert_eq!(
+            text.formatted_body,
+            "<a href=\"https://matrix.to/#/@feishu_alice:example.com\">Alice</a> @Ghost &amp; @room: see a@b"
+        );
+        assert_eq!(
+            text.m_mentions(),
+            Some(json!({ "user_ids": ["@feishu_alice:example.com"], "room": true }))
+        );
+
+        let post = json!({
+            "content": [[
+                {"tag": "at", "user_id": "@_user_1", "user_name": "Alice"},
+                {"tag": "text", "text": " hi "},
+                {"tag": "at", "user_id": "@_user_2", "user_name": "Bob"}
+            ]]
+        });
+        let rendered = feishu_post_to_matrix(&post, &context);
+        assert_eq!(rendered.body, "Alice hi @Bob");
+        assert_eq!(
+            rendered.mentioned_user_ids,
+            vec!["@feishu_alice:example.com".to_string()]
+        );
+        assert!(!rendered.mentions_room);
+
+        let plain = feishu_text_to_matrix("no mentions", &FeishuRenderContext::default());
+        assert_eq!(plain.m_mentions(), None);
+    }
 }
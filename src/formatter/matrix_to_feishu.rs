@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+
 use serde_json::{Value, json};
 
+use super::feishu_to_matrix::{FeishuRenderContext, feishu_post_to_matrix};
 use crate::bridge::message::{BridgeMessage, MessageType};
 use crate::feishu::types::FeishuRichText;
 
@@ -121,46 +124,801 @@ pub fn extract_matrix_mentions(content: &str) -> String {
     result
 }
 
-pub fn convert_matrix_html_to_feishu(html: &str) -> String {
-    // Basic HTML to text conversion for Feishu
-    let mut result = html.to_string();
+#[derive(Debug, Clone, Default)]
+pub struct MatrixHtmlContext {
+    // mxc:// URI -> uploaded Feishu image_key
+    pub image_keys: HashMap<String, String>,
+    // Matrix user ID -> Feishu user ID used for `at` elements. Users that are
+    // not pilled in the HTML are still mentioned at the end of the post.
+    pub feishu_user_ids: HashMap<String, String>,
+}
+
+pub fn convert_matrix_html_to_feishu(html: &str, context: &MatrixHtmlContext) -> Value {
+    let mut builder = FeishuPostBuilder::new(context);
+    for token in tokenize_html(html) {
+        match token {
+            HtmlToken::Open {
+                name,
+                attrs,
+                self_closing,
+            } => {
+                builder.open(&name, &attrs);
+                if self_closing {
+                    builder.close(&name);
+                }
+            }
+            HtmlToken::Close(name) => builder.close(&name),
+            HtmlToken::Text(text) => builder.text(&text),
+        }
+    }
+
+    json!({
+        "zh_cn": {
+            "title": "",
+            "content": builder.finish()
+        }
+    })
+}
+
+pub fn matrix_html_to_plain_text(html: &str) -> String {
+    let post = convert_matrix_html_to_feishu(html, &MatrixHtmlContext::default());
+    feishu_post_to_matrix(&post, &FeishuRenderContext::default()).body
+}
+
+// Returns `(user_id, label)` for every matrix.to user pill in the HTML.
+pub fn extract_matrix_html_mentions(html: &str) -> Vec<(String, String)> {
+    let mut mentions: Vec<(String, String)> = Vec::new();
+    let mut current: Option<(String, String)> = None;
+    for token in tokenize_html(html) {
+        match token {
+            HtmlToken::Open { name, attrs, .. } if name == "a" => {
+                current = html_attr(&attrs, "href")
+                    .and_then(matrix_to_user_id)
+                    .map(|user_id| (user_id.to_string(), String::new()));
+            }
+            HtmlToken::Text(text) => {
+                if let Some((_, label)) = current.as_mut() {
+                    label.push_str(&text);
+                }
+            }
+            HtmlToken::Close(name) if name == "a" => {
+                if let Some((user_id, label)) = current.take()
+                    && !mentions.iter().any(|(existing, _)| *existing == user_id)
+                {
+                    mentions.push((user_id, label.trim().to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+    mentions
+}
+
+// Replaces the first standalone occurrence of the mention label outside existing
+// `<at>` tags with a Feishu text `<at>` tag, appending the tag when the label
+// isn't in the text.
+pub fn insert_feishu_at_tag(content: &str, label: &str, feishu_user_id: &str) -> String {
+    let label = label.trim();
+    let tag = format!(
+        "<at user_id=\"{}\">{}</at>",
+        feishu_user_id.replace(['<', '>', '"'], ""),
+        label.replace(['<', '>'], "")
+    );
+    if !label.is_empty() {
+        let at_tags = feishu_at_tag_ranges(content);
+        for needle in [format!("@{}", label), label.to_string()] {
+            let found = content.match_indices(&needle).find(|(start, _)| {
+                let end = start + needle.len();
+                !at_tags
+                    .iter()
+                    .any(|range| *start < range.end && end > range.start)
+                    && is_mention_boundary(
+                        content[..*start].chars().next_back(),
+                        needle.chars().next(),
+                    )
+                    && is_mention_boundary(
+                        content[end..].chars().next(),
+                        needle.chars().next_back(),
+                    )
+            });
+            if let Some((start, _)) = found {
+                return format!(
+                    "{}{}{}",
+                    &content[..start],
+                    tag,
+                    &content[start + needle.len()..]
+                );
+            }
+        }
+    }
+    format!("{} {}", content.trim_end(), tag)
+}
 
-    // Remove common HTML tags but preserve content
-    result = result.replace("<p>", "").replace("</p>", "\n");
-    result = result.replace("<br>", "\n").replace("<br/>", "\n");
-    result = result.replace("<div>", "").replace("</div>", "\n");
-    result = result.replace("<span>", "").replace("</span>", "");
-    result = result.replace("<strong>", "").replace("</strong>", "");
-    result = result.replace("<em>", "").replace("</em>", "");
-    result = result.replace("<b>", "").replace("</b>", "");
-    result = result.replace("<i>", "").replace("</i>", "");
-    result = result.replace("<u>", "").replace("</u>", "");
-    result = result.replace("<s>", "").replace("</s>", "");
-    result = result.replace("<del>", "").replace("</del>", "");
+fn feishu_at_tag_ranges(content: &str) -> Vec<std::ops::Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = content[offset..].find("<at ").map(|i| offset + i) {
+        let end = content[start..]
+            .find("</at>")
+            .map(|i| start + i + "</at>".len())
+            .unwrap_or(content.len());
+        ranges.push(start..end);
+        offset = end;
+    }
+    ranges
+}
 
-    // Handle links
-    let link_regex = regex::Regex::new(r#"<a[^>]+href="([^"]+)"[^>]*>([^<]+)</a>"#).unwrap();
-    result = link_regex.replace_all(&result, "$2 ($1)").to_string();
+// Only ASCII word characters glue together; CJK names are routinely written
+// without spaces around them.
+fn is_mention_boundary(outside: Option<char>, edge: Option<char>) -> bool {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    !matches!((outside, edge), (Some(outside), Some(edge)) if is_word(outside) && is_word(edge))
+}
 
-    // Handle images
-    let img_regex = regex::Regex::new(r#"<img[^>]+src="([^"]+)"[^>]+alt="([^"]*)"[^>]*>"#).unwrap();
-    result = img_regex.replace_all(&result, "[Image: $2]").to_string();
+pub fn extract_matrix_html_image_sources(html: &str) -> Vec<String> {
+    let mut sources: Vec<String> = Vec::new();
+    for token in tokenize_html(html) {
+        if let HtmlToken::Open { name, attrs, .. } = token
+            && name == "img"
+            && html_attr(&attrs, "data-mx-emoticon").is_none()
+            && let Some(src) = html_attr(&attrs, "src")
+            && src.starts_with("mxc://")
+            && !sources.iter().any(|existing| existing == src)
+        {
+            sources.push(src.to_string());
+        }
+    }
+    sources
+}
 
-    // Handle code blocks
-    let code_regex = regex::Regex::new(r#"<code[^>]*>([^<]+)</code>"#).unwrap();
-    result = code_regex.replace_all(&result, "`$1`").to_string();
+fn matrix_to_user_id(href: &str) -> Option<&str> {
+    let target = href
+        .strip_prefix("https://matrix.to/#/")
+        .or_else(|| href.strip_prefix("http://matrix.to/#/"))?;
+    let user_id = target.split(['?', '/']).next()?;
+    (user_id.starts_with('@') && user_id.contains(':')).then_some(user_id)
+}
 
-    let pre_regex = regex::Regex::new(r#"<pre[^>]*>([^<]+)</pre>"#).unwrap();
-    result = pre_regex.replace_all(&result, "```\n$1\n```").to_string();
+enum LinkTarget {
+    Href(String),
+    Mention(String),
+    Plain,
+}
 
-    // Clean up extra whitespace
-    result = result
-        .lines()
-        .map(|line| line.trim())
-        .collect::<Vec<_>>()
-        .join("\n");
+#[derive(Default)]
+struct CodeBlock {
+    language: String,
+    text: String,
+}
 
-    convert_matrix_text_to_feishu(&result)
+struct FeishuPostBuilder<'a> {
+    context: &'a MatrixHtmlContext,
+    rows: Vec<Vec<Value>>,
+    row: Vec<Value>,
+    bold: usize,
+    italic: usize,
+    underline: usize,
+    strike: usize,
+    inline_code: usize,
+    skip_depth: usize,
+    quote_depth: usize,
+    lists: Vec<Option<usize>>,
+    pending_bullet: Option<String>,
+    links: Vec<LinkTarget>,
+    mention_text: String,
+    mentioned: Vec<String>,
+    code_block: Option<CodeBlock>,
+}
+
+impl<'a> FeishuPostBuilder<'a> {
+    fn new(context: &'a MatrixHtmlContext) -> Self {
+        Self {
+            context,
+            rows: Vec::new(),
+            row: Vec::new(),
+            bold: 0,
+            italic: 0,
+            underline: 0,
+            strike: 0,
+            inline_code: 0,
+            skip_depth: 0,
+            quote_depth: 0,
+            lists: Vec::new(),
+            pending_bullet: None,
+            links: Vec::new(),
+            mention_text: String::new(),
+            mentioned: Vec::new(),
+            code_block: None,
+        }
+    }
+
+    fn open(&mut self, name: &str, attrs: &[(String, String)]) {
+        if self.skip_depth > 0 {
+            if is_skipped_element(name) {
+                self.skip_depth += 1;
+            }
+            return;
+        }
+        if let Some(code_block) = self.code_block.as_mut() {
+            if name == "code"
+                && let Some(language) = html_attr(attrs, "class").and_then(|class| {
+                    class
+                        .split_whitespace()
+                        .find_map(|c| c.strip_prefix("language-"))
+                })
+            {
+                code_block.language = language.to_ascii_uppercase();
+            }
+            return;
+        }
+
+        match name {
+            _ if is_skipped_element(name) => self.skip_depth += 1,
+            "b" | "strong" => self.bold += 1,
+            "i" | "em" => self.italic += 1,
+            "u" | "ins" => self.underline += 1,
+            "s" | "del" | "strike" => self.strike += 1,
+            "code" => {
+                self.push_text("`");
+                self.inline_code += 1;
+            }
+            "a" => {
+                let href = html_attr(attrs, "href").unwrap_or_default();
+                let target = match matrix_to_user_id(href) {
+                    Some(user_id) => LinkTarget::Mention(user_id.to_string()),
+                    None if href.is_empty() => LinkTarget::Plain,
+                    None => LinkTarget::Href(href.to_string()),
+                };
+                self.mention_text.clear();
+                self.links.push(target);
+            }
+            "br" => {
+                self.trim_row_end();
+                self.rows.push(std::mem::take(&mut self.row));
+            }
+            "hr" => {
+                self.break_row();
+                self.rows.push(vec![json!({ "tag": "hr" })]);
+            }
+            "pre" => {
+                self.break_row();
+                self.code_block = Some(CodeBlock::default());
+            }
+            "img" => self.image(attrs),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.break_row();
+                self.bold += 1;
+            }
+            "blockquote" => {
+                self.break_row();
+                self.quote_depth += 1;
+            }
+            "ul" => {
+                self.break_row();
+                self.lists.push(None);
+            }
+            "ol" => {
+                self.break_row();
+                let start = html_attr(attrs, "start")
+                    .and_then(|start| start.trim().parse().ok())
+                    .unwrap_or(1);
+                self.lists.push(Some(start));
+            }
+            "li" => {
+                self.break_row();
+                let depth = self.lists.len().saturating_sub(1);
+                let bullet = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                // Emitted with the item's first inline content, so block children
+                // like <p> don't leave the bullet on a row of its own.
+                self.pending_bullet = Some(format!("{}{}", "  ".repeat(depth), bullet));
+            }
+            "td" | "th" if !self.row.is_empty() => self.push_plain_text(" | "),
+            "p" | "div" | "tr" | "table" | "details" | "summary" => self.break_row(),
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        if self.skip_depth > 0 {
+            if is_skipped_element(name) {
+                self.skip_depth -= 1;
+            }
+            return;
+        }
+        if self.code_block.is_some() {
+            if name == "pre"
+                && let Some(code_block) = self.code_block.take()
+            {
+                let language = if code_block.language.is_empty() {
+                    "PLAIN_TEXT".to_string()
+                } else {
+                    code_block.language
+                };
+                self.rows.push(vec![json!({
+                    "tag": "code_block",
+                    "language": language,
+                    "text": code_block.text.trim_end_matches('\n'),
+                })]);
+            }
+            return;
+        }
+
+        match name {
+            "b" | "strong" => self.bold = self.bold.saturating_sub(1),
+            "i" | "em" => self.italic = self.italic.saturating_sub(1),
+            "u" | "ins" => self.underline = self.underline.saturating_sub(1),
+            "s" | "del" | "strike" => self.strike = self.strike.saturating_sub(1),
+            "code" if self.inline_code > 0 => {
+                self.inline_code -= 1;
+                self.push_text("`");
+            }
+            "a" => self.close_link(),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.bold = self.bold.saturating_sub(1);
+                self.break_row();
+            }
+            "blockquote" => {
+                self.break_row();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+            }
+            "ul" | "ol" => {
+                self.break_row();
+                self.lists.pop();
+                self.pending_bullet = None;
+            }
+            "li" => {
+                self.break_row();
+                self.pending_bullet = None;
+            }
+            "p" | "div" | "tr" | "table" | "details" | "summary" => self.break_row(),
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.skip_depth > 0 {
+            return;
+        }
+        if let Some(code_block) = self.code_block.as_mut() {
+            code_block.text.push_str(text);
+            return;
+        }
+
+        let mut collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.starts_with(char::is_whitespace) {
+            collapsed.insert(0, ' ');
+        }
+        if text.ends_with(char::is_whitespace) && !collapsed.ends_with(' ') {
+            collapsed.push(' ');
+        }
+        if self.row.is_empty() {
+            collapsed = collapsed.trim_start().to_string();
+        }
+        if collapsed.is_empty() {
+            return;
+        }
+
+        if matches!(self.links.last(), Some(LinkTarget::Mention(_))) {
+            self.mention_text.push_str(&collapsed);
+        } else {
+            self.push_text(&collapsed);
+        }
+    }
+
+    fn close_link(&mut self) {
+        let Some(target) = self.links.pop() else {
+            return;
+        };
+        let LinkTarget::Mention(user_id) = target else {
+            return;
+        };
+
+        let label = std::mem::take(&mut self.mention_text);
+        let label = label.trim();
+        let name = if label.is_empty() {
+            user_id.as_str()
+        } else {
+            label
+        };
+        let name = name.trim_start_matches('@');
+        match self.context.feishu_user_ids.get(&user_id) {
+            Some(feishu_user_id) => {
+                self.ensure_row_prefix();
+                self.row.push(json!({
+                    "tag": "at",
+                    "user_id": feishu_user_id,
+                    "user_name": name,
+                }));
+                self.mentioned.push(user_id.clone());
+            }
+            None => self.push_text(&format!("@{}", name)),
+        }
+    }
+
+    fn image(&mut self, attrs: &[(String, String)]) {
+        let alt = html_attr(attrs, "alt")
+            .or_else(|| html_attr(attrs, "title"))
+            .filter(|alt| !alt.trim().is_empty());
+        let image_key = html_attr(attrs, "src")
+            .filter(|_| html_attr(attrs, "data-mx-emoticon").is_none())
+            .and_then(|src| self.context.image_keys.get(src));
+
+        match image_key {
+            Some(image_key) => {
+                self.break_row();
+                self.rows
+                    .push(vec![json!({ "tag": "img", "image_key": image_key })]);
+            }
+            None => {
+                let fallback = match alt {
+                    Some(alt) if html_attr(attrs, "data-mx-emoticon").is_some() => alt.to_string(),
+                    Some(alt) => format!("[{}]", alt),
+                    None => "[Image]".to_string(),
+                };
+                self.push_text(&fallback);
+            }
+        }
+    }
+
+    fn styles(&self) -> Vec<&'static str> {
+        let mut styles = Vec::new();
+        if self.bold > 0 {
+            styles.push("bold");
+        }
+        if self.italic > 0 {
+            styles.push("italic");
+        }
+        if self.underline > 0 {
+            styles.push("underline");
+        }
+        if self.strike > 0 {
+            styles.push("lineThrough");
+        }
+        styles
+    }
+
+    fn ensure_row_prefix(&mut self) {
+        if self.row.is_empty() && self.quote_depth > 0 {
+            self.row.push(json!({
+                "tag": "text",
+                "text": "> ".repeat(self.quote_depth),
+            }));
+        }
+        if let Some(bullet) = self.pending_bullet.take() {
+            self.append_run(&bullet, Vec::new(), None);
+        }
+    }
+
+    fn push_plain_text(&mut self, text: &str) {
+        self.ensure_row_prefix();
+        self.append_run(text, Vec::new(), None);
+    }
+
+    fn push_text(&mut self, text: &str) {
+        self.ensure_row_prefix();
+        let styles = self.styles();
+        let href = match self.links.last() {
+            Some(LinkTarget::Href(href)) => Some(href.clone()),
+            _ => None,
+        };
+        self.append_run(text, styles, href);
+    }
+
+    fn append_run(&mut self, text: &str, styles: Vec<&'static str>, href: Option<String>) {
+        let tag = if href.is_some() { "a" } else { "text" };
+        if let Some(last) = self.row.last_mut()
+            && last.get("tag").and_then(Value::as_str) == Some(tag)
+            && last.get("href").and_then(Value::as_str) == href.as_deref()
+            && style_list(last) == styles
+            && let Some(existing) = last.get("text").and_then(Value::as_str)
+        {
+            last["text"] = Value::String(format!("{}{}", existing, text));
+            return;
+        }
+
+        let mut element = json!({ "tag": tag, "text": text });
+        if let Some(href) = href {
+            element["href"] = Value::String(href);
+        }
+        if !styles.is_empty() {
+            element["style"] = json!(styles);
+        }
+        self.row.push(element);
+    }
+
+    fn trim_row_end(&mut self) {
+        if let Some(last) = self.row.last_mut()
+            && last.get("tag").and_then(Value::as_str) == Some("text")
+            && let Some(text) = last.get("text").and_then(Value::as_str)
+        {
+            let trimmed = text.trim_end().to_string();
+            if trimmed.is_empty() {
+                self.row.pop();
+            } else {
+                last["text"] = Value::String(trimmed);
+            }
+        }
+    }
+
+    fn break_row(&mut self) {
+        self.trim_row_end();
+        if !self.row.is_empty() {
+            self.rows.push(std::mem::take(&mut self.row));
+        }
+    }
+
+    fn finish(mut self) -> Vec<Vec<Value>> {
+        if self.code_block.is_some() {
+            self.close("pre");
+        }
+        self.break_row();
+        while self.rows.last().is_some_and(Vec::is_empty) {
+            self.rows.pop();
+        }
+
+        let mut missing: Vec<(&String, &String)> = self
+            .context
+            .feishu_user_ids
+            .iter()
+            .filter(|(user_id, _)| !self.mentioned.contains(user_id))
+            .collect();
+        missing.sort();
+        if missing.is_empty() {
+            return self.rows;
+        }
+        let mut row = match self.rows.pop() {
+            Some(row) if !is_block_row(&row) => row,
+            Some(row) => {
+                self.rows.push(row);
+                Vec::new()
+            }
+            None => Vec::new(),
+        };
+        for (_, feishu_user_id) in missing {
+            if !row.is_empty() {
+                row.push(json!({ "tag": "text", "text": " " }));
+            }
+            row.push(json!({ "tag": "at", "user_id": feishu_user_id }));
+        }
+        self.rows.push(row);
+        self.rows
+    }
+}
+
+fn is_block_row(row: &[Value]) -> bool {
+    row.iter().any(|element| {
+        matches!(
+            element.get("tag").and_then(Value::as_str),
+            Some("code_block" | "hr" | "img")
+        )
+    })
+}
+
+fn style_list(element: &Value) -> Vec<&str> {
+    element
+        .get("style")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect()
+}
+
+fn is_skipped_element(name: &str) -> bool {
+    matches!(name, "mx-reply" | "script" | "style" | "head" | "title")
+}
+
+fn html_attr<'a>(attrs: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attrs
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HtmlToken {
+    Open {
+        name: String,
+        attrs: Vec<(String, String)>,
+        self_closing: bool,
+    },
+    Close(String),
+    Text(String),
+}
+
+// A small, forgiving tokenizer for the HTML subset Matrix clients send.
+// Anything that doesn't look like a tag is kept as text.
+fn tokenize_html(html: &str) -> Vec<HtmlToken> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment
+                .find("-->")
+                .map(|end| &comment[end + 3..])
+                .unwrap_or_default();
+            continue;
+        }
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest
+                .find('>')
+                .map(|end| &rest[end + 1..])
+                .unwrap_or_default();
+            continue;
+        }
+        if rest.starts_with('<')
+            && let Some((token, remaining)) = parse_html_tag(rest)
+        {
+            tokens.push(token);
+            rest = remaining;
+            continue;
+        }
+
+        let end = rest
+            .char_indices()
+            .skip(1)
+            .find(|(_, ch)| *ch == '<')
+            .map(|(index, _)| index)
+            .unwrap_or(rest.len());
+        tokens.push(HtmlToken::Text(decode_html_entities(&rest[..end])));
+        rest = &rest[end..];
+    }
+
+    tokens
+}
+
+fn parse_html_tag(input: &str) -> Option<(HtmlToken, &str)> {
+    let body = input.strip_prefix('<')?;
+    let mut quote = None;
+    let mut end = None;
+    for (index, ch) in body.char_indices() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => {}
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == '>' => {
+                end = Some(index);
+                break;
+            }
+            None => {}
+        }
+    }
+    let end = end?;
+    let inner = &body[..end];
+    let remaining = &body[end + 1..];
+
+    if let Some(name) = inner.strip_prefix('/') {
+        let name = name.trim().to_ascii_lowercase();
+        if !is_valid_tag_name(&name) {
+            return None;
+        }
+        return Some((HtmlToken::Close(name), remaining));
+    }
+
+    let inner = inner.trim_end();
+    let (inner, self_closing) = match inner.strip_suffix('/') {
+        Some(inner) => (inner, true),
+        None => (inner, false),
+    };
+    let name_end = inner
+        .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '-'))
+        .unwrap_or(inner.len());
+    let name = inner[..name_end].to_ascii_lowercase();
+    if !is_valid_tag_name(&name) {
+        return None;
+    }
+
+    Some((
+        HtmlToken::Open {
+            self_closing: self_closing || matches!(name.as_str(), "br" | "hr" | "img"),
+            name,
+            attrs: parse_html_attributes(&inner[name_end..]),
+        },
+        remaining,
+    ))
+}
+
+fn is_valid_tag_name(name: &str) -> bool {
+    name.starts_with(|ch: char| ch.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+}
+
+fn parse_html_attributes(input: &str) -> Vec<(String, String)> {
+    let mut attrs = Vec::new();
+    let mut rest = input.trim_start();
+
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|ch: char| ch.is_whitespace() || ch == '=')
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = rest[name_end..].trim_start();
+
+        let mut value = String::new();
+        if let Some(after_eq) = rest.strip_prefix('=') {
+            let after_eq = after_eq.trim_start();
+            match after_eq.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let quoted = &after_eq[1..];
+                    let close = quoted.find(quote).unwrap_or(quoted.len());
+                    value = decode_html_entities(&quoted[..close]);
+                    rest = quoted.get(close + 1..).unwrap_or_default();
+                }
+                _ => {
+                    let end = after_eq.find(char::is_whitespace).unwrap_or(after_eq.len());
+                    value = decode_html_entities(&after_eq[..end]);
+                    rest = &after_eq[end..];
+                }
+            }
+        }
+
+        if !name.is_empty() {
+            attrs.push((name, value));
+        }
+        rest = rest.trim_start();
+    }
+
+    attrs
+}
+
+fn decode_html_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        let candidate = &rest[start..];
+        let entity = candidate
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_html_entity(&candidate[1..end]).map(|ch| (ch, end)));
+        match entity {
+            Some((ch, end)) => {
+                decoded.push(ch);
+                rest = &candidate[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &candidate[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_html_entity(entity: &str) -> Option<char> {
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = if let Some(hex) = entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+            {
+                u32::from_str_radix(hex, 16).ok()?
+            } else {
+                entity.strip_prefix('#')?.parse().ok()?
+            };
+            char::from_u32(code)
+        }
+    }
 }
 
 pub fn convert_matrix_emoticons(content: &str) -> String {
@@ -215,12 +973,23 @@ pub fn create_feishu_card_message(title: &str, content: &str) -> Value {
 
 #[cfg(test)]
 mod tests {
-    use serde_json::Value;
+    use std::collections::HashMap;
+
+    use serde_json::{Value, json};
 
     use super::{
-        convert_matrix_emoticons, convert_matrix_html_to_feishu, convert_matrix_text_to_feishu,
-        create_feishu_rich_text, extract_matrix_mentions,
+        MatrixHtmlContext, convert_matrix_emoticons, convert_matrix_html_to_feishu,
+        convert_matrix_text_to_feishu, create_feishu_rich_text, extract_matrix_html_image_sources,
+        extract_matrix_html_mentions, extract_matrix_mentions, insert_feishu_at_tag,
+        matrix_html_to_plain_text,
     };
+    use crate::formatter::{FeishuRenderContext, escape_html, feishu_post_to_matrix};
+
+    fn post_rows(post: &Value) -> Value {
+        post.pointer("/zh_cn/content")
+            .cloned()
+            .expect("post should have content rows")
+    }
 
     #[test]
     fn convert_matrix_text_to_feishu_strips_html_and_mentions() {
@@ -249,10 +1018,150 @@ mod tests {
     }
 
     #[test]
-    fn convert_matrix_html_to_feishu_keeps_link_text_and_url() {
-        let html = r#"<p>Hello <a href="https://example.com">example</a></p>"#;
-        let converted = convert_matrix_html_to_feishu(html);
-        assert!(converted.contains("example (https//example.com)"));
+    fn convert_matrix_html_to_feishu_builds_styled_runs_and_links() {
+        let html = r#"<p>Hello <strong>bold <em>both</em></strong> <a href="https://example.com/?a=1&amp;b=2">example</a></p>"#;
+        let post = convert_matrix_html_to_feishu(html, &MatrixHtmlContext::default());
+        assert_eq!(
+            post_rows(&post),
+            json!([[
+                {"tag": "text", "text": "Hello "},
+                {"tag": "text", "text": "bold ", "style": ["bold"]},
+                {"tag": "text", "text": "both", "style": ["bold", "italic"]},
+                {"tag": "text", "text": " "},
+                {"tag": "a", "text": "example", "href": "https://example.com/?a=1&b=2"}
+            ]])
+        );
+    }
+
+    #[test]
+    fn convert_matrix_html_keeps_bullets_with_paragraphs_inside_list_items() {
+        let html = "<ul><li><p>one</p></li><li><p><strong>two</strong></p><p>more</p></li></ul>";
+        let post = convert_matrix_html_to_feishu(html, &MatrixHtmlContext::default());
+        assert_eq!(
+            post_rows(&post),
+            json!([
+                [{"tag": "text", "text": "• one"}],
+                [
+                    {"tag": "text", "text": "• "},
+                    {"tag": "text", "text": "two", "style": ["bold"]}
+                ],
+                [{"tag": "text", "text": "more"}]
+            ])
+        );
+    }
+
+    #[test]
+    fn convert_matrix_html_to_feishu_handles_block_structure() {
+        let html = concat!(
+            r#"<mx-reply><blockquote><a href="https://matrix.to/#/!room/$ev">In reply to</a> old</blockquote></mx-reply>"#,
+            "<h2>Title</h2>",
+            "<ul><li>one</li><li>two<ol start=\"3\"><li>nested</li></ol></li></ul>",
+            "<blockquote><p>quoted <del>text</del></p></blockquote>",
+            "<pre><code class=\"language-rust\">fn main() {\n    println!(\"&lt;hi&gt;\");\n}\n</code></pre>",
+            "use <code>cargo</code><br/>done<hr/>",
+        );
+        let post = convert_matrix_html_to_feishu(html, &MatrixHtmlContext::default());
+        assert_eq!(
+            post_rows(&post),
+            json!([
+                [{"tag": "text", "text": "Title", "style": ["bold"]}],
+                [{"tag": "text", "text": "• one"}],
+                [{"tag": "text", "text": "• two"}],
+                [{"tag": "text", "text": "  3. nested"}],
+                [
+                    {"tag": "text", "text": "> quoted "},
+                    {"tag": "text", "text": "text", "style": ["lineThrough"]}
+                ],
+                [{
+                    "tag": "code_block",
+                    "language": "RUST",
+                    "text": "fn main() {\n    println!(\"<hi>\");\n}"
+                }],
+                [{"tag": "text", "text": "use `cargo`"}],
+                [{"tag": "text", "text": "done"}],
+                [{"tag": "hr"}]
+            ])
+        );
+    }
+
+    #[test]
+    fn convert_matrix_html_to_feishu_resolves_images_and_mentions() {
+        let html = concat!(
+            r#"<a href="https://matrix.to/#/@alice:example.com">Alice</a> "#,
+            r#"<a href="https://matrix.to/#/@bob:example.com">Bob</a> "#,
+            r#"<img data-mx-emoticon src="mxc://example.com/emote" alt=":party:"/>"#,
+            r#"<img src="mxc://example.com/missing" alt="diagram">"#,
+            r#"<img src="mxc://example.com/cat" alt="cat">"#,
+        );
+        assert_eq!(
+            extract_matrix_html_image_sources(html),
+            vec!["mxc://example.com/missing", "mxc://example.com/cat"]
+        );
+
+        let context = MatrixHtmlContext {
+            image_keys: HashMap::from([(
+                "mxc://example.com/cat".to_string(),
+                "img_v2_cat".to_string(),
+            )]),
+            feishu_user_ids: HashMap::from([(
+                "@alice:example.com".to_string(),
+                "ou_alice".to_string(),
+            )]),
+        };
+        let post = convert_matrix_html_to_feishu(html, &context);
+        assert_eq!(
+            post_rows(&post),
+            json!([
+                [
+                    {"tag": "at", "user_id": "ou_alice", "user_name": "Alice"},
+                    {"tag": "text", "text": " @Bob :party:[diagram]"}
+                ],
+                [{"tag": "img", "image_key": "img_v2_cat"}]
+            ])
+        );
+    }
+
+    #[test]
+    fn convert_matrix_html_to_feishu_degrades_unknown_markup() {
+        let html = "<table><tr><td>a</td><td>b &lt; c</td></tr></table><span data-mx-spoiler>secret</span> 1 < 2 <!-- note -->";
+        assert_eq!(matrix_html_to_plain_text(html), "a | b < c\nsecret 1 < 2");
+    }
+
+    #[test]
+    fn convert_matrix_html_round_trips_plain_text_like_create_feishu_rich_text() {
+        for plain in [
+            "hello world",
+            "@alice check https://example.com",
+            "a < b && c > d",
+        ] {
+            let html = escape_html(plain);
+            let from_html = convert_matrix_html_to_feishu(&html, &MatrixHtmlContext::default());
+            let from_text: Value = serde_json::from_str(&create_feishu_rich_text(plain))
+                .expect("valid rich text json");
+            assert_eq!(
+                feishu_post_to_matrix(&from_html, &FeishuRenderContext::default()).body,
+                feishu_post_to_matrix(&from_text, &FeishuRenderContext::default()).body,
+                "plain text {plain:?} should flatten identically"
+            );
+        }
+    }
+
+    #[test]
+    fn convert_matrix_html_round_trips_through_feishu_post_rendering() {
+        let html = concat!(
+            "<strong>bold</strong> and <del><em>gone</em></del>",
+            r#"<a href="https://example.com/?a=1&amp;b=2"> docs</a><br/>"#,
+            "plain",
+            r#"<pre><code class="language-rust">let x = 1 &lt; 2;</code></pre>"#,
+            "<hr/>",
+        );
+        let post = convert_matrix_html_to_feishu(html, &MatrixHtmlContext::default());
+        let rendered = feishu_post_to_matrix(&post, &FeishuRenderContext::default());
+        assert_eq!(rendered.formatted_body, html);
+        assert_eq!(
+            rendered.body,
+            "bold and gone docs (https://example.com/?a=1&b=2)\nplain\n```rust\nlet x = 1 < 2;\n```\n---"
+        );
     }
 
     #[test]
@@ -266,4 +1175,73 @@ mod tests {
         let converted = convert_matrix_emoticons("Great 😊 👍");
         assert_eq!(converted, "Great [微笑] [赞]");
     }
+
+    #[test]
+    fn matrix_mentions_become_feishu_at_elements_and_tags() {
+        let html = concat!(
+            r#"<a href="https://matrix.to/#/@feishu_ou_1:example.com">Alice</a>: ping "#,
+            r#"<a href="https://matrix.to/#/!room:example.com">a room</a>"#,
+        );
+        assert_eq!(
+            extract_matrix_html_mentions(html),
+            vec![("@feishu_ou_1:example.com".to_string(), "Alice".to_string())]
+        );
+
+        let context = MatrixHtmlContext {
+            feishu_user_ids: HashMap::from([
+                ("@feishu_ou_1:example.com".to_string(), "ou_1".to_string()),
+                ("@feishu_ou_2:example.com".to_string(), "ou_2".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let post = convert_matrix_html_to_feishu(html, &context);
+        assert_eq!(
+            post_rows(&post),
+            json!([[
+                {"tag": "at", "user_id": "ou_1", "user_name": "Alice"},
+                {"tag": "text", "text": ": ping "},
+                {"tag": "a", "text": "a room", "href": "https://matrix.to/#/!room:example.com"},
+                {"tag": "text", "text": " "},
+                {"tag": "at", "user_id": "ou_2"}
+            ]])
+        );
+
+        assert_eq!(
+            insert_feishu_at_tag("Alice: ping", "Alice", "ou_1"),
+            r#"<at user_id="ou_1">Alice</at>: ping"#
+        );
+        assert_eq!(
+            insert_feishu_at_tag("hi @Bob!", "Bob", "ou_2"),
+            r#"hi <at user_id="ou_2">Bob</at>!"#
+        );
+        assert_eq!(
+            insert_feishu_at_tag("hello ", "Carol", "ou_3"),
+            r#"hello <at user_id="ou_3">Carol</at>"#
+        );
+    }
+
+    #[test]
+    fn insert_feishu_at_tag_skips_existing_tags_and_partial_words() {
+        let content = insert_feishu_at_tag("Alice and Ali", "Alice", "ou_1");
+        assert_eq!(
+            insert_feishu_at_tag(&content, "Ali", "ou_2"),
+            r#"<at user_id="ou_1">Alice</at> and <at user_id="ou_2">Ali</at>"#
+        );
+        assert_eq!(
+            insert_feishu_at_tag(&content, "Alice", "ou_1"),
+            r#"<at user_id="ou_1">Alice</at> and Ali <at user_id="ou_1">Alice</at>"#
+        );
+        assert_eq!(
+            insert_feishu_at_tag("Also ping Al", "Al", "ou_3"),
+            r#"Also ping <at user_id="ou_3">Al</at>"#
+        );
+        assert_eq!(
+            insert_feishu_at_tag("Also", "Al", "ou_3"),
+            r#"Also <at user_id="ou_3">Al</at>"#
+        );
+        assert_eq!(
+            insert_feishu_at_tag("张三你好", "张三", "ou_4"),
+            r#"<at user_id="ou_4">张三</at>你好"#
+        );
+    }
 }
@@ -1,5 +1,7 @@
+pub mod emoji;
 pub mod feishu_to_matrix;
 pub mod matrix_to_feishu;
 
+pub use emoji::*;
 pub use feishu_to_matrix::*;
 pub use matrix_to_feishu::*;
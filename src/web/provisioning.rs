@@ -8,7 +8,9 @@ use salvo::prelude::*;
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
-use crate::bridge::{FeishuBridge, PendingBridgeRequest, ProvisioningCoordinator};
+use crate::bridge::{
+    FeishuBridge, PendingBridgeRequest, ProvisioningCoordinator, ProvisioningError,
+};
 use crate::database::{DeadLetterStore, RoomStore};
 
 #[derive(Clone)]
@@ -56,6 +58,7 @@ impl ProvisioningApi {
                     .get(list_bridges)
                     .post(create_bridge),
             )
+            .push(Router::with_path("chats").post(create_feishu_chat))
             .push(Router::with_path("mappings").get(list_mappings))
             .push(Router::with_path("bridges/<room_id>").delete(delete_bridge))
             .push(Router::with_path("pending").get(list_pending))
@@ -74,6 +77,12 @@ pub struct BridgeRequest {
     pub requestor: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateChatRequest {
+    pub matrix_room_id: String,
+    pub requestor: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BridgeResponse {
     pub success: bool,
@@ -258,6 +267,64 @@ async fn create_bridge(
     }
 }
 
+#[handler]
+async fn create_feishu_chat(
+    raw_req: &mut Request,
+    req: JsonBody<CreateChatRequest>,
+    depot: &mut Depot,
+    res: &mut Response,
+) {
+    let api: &ProvisioningApi = depot.obtain().unwrap();
+    let Some(auth) = require_auth(raw_req, api, AuthScope::Write, res) else {
+        return;
+    };
+    info!(
+        action = "create_feishu_chat",
+        actor = %auth.actor,
+        actor_source = %auth.actor_source,
+        request_id = %auth.request_id,
+        auth_scope = auth.scope.as_str(),
+        matrix_room_id = %req.matrix_room_id,
+        "Provisioning chat creation requested"
+    );
+
+    match api
+        .bridge
+        .create_feishu_chat_for_room(&req.matrix_room_id, &req.requestor)
+        .await
+    {
+        Ok(mapping) => {
+            res.status_code(StatusCode::CREATED);
+            res.render(Json(serde_json::json!({
+                "success": true,
+                "message": "Feishu chat created",
+                "matrix_room_id": mapping.matrix_room_id,
+                "feishu_chat_id": mapping.feishu_chat_id,
+            })));
+        }
+        Err(e) => {
+            res.status_code(match e.downcast_ref::<ProvisioningError>() {
+                Some(ProvisioningError::AlreadyExists) => StatusCode::CONFLICT,
+                Some(ProvisioningError::Upstream(_)) => StatusCode::BAD_GATEWAY,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            });
+            warn!(
+                action = "create_feishu_chat",
+                actor = %auth.actor,
+                actor_source = %auth.actor_source,
+                request_id = %auth.request_id,
+                matrix_room_id = %req.matrix_room_id,
+                error = %e,
+                "Provisioning chat creation failed"
+            );
+            res.render(Json(BridgeResponse {
+                success: false,
+                message: e.to_string(),
+            }));
+        }
+    }
+}
+
 #[handler]
 async fn delete_bridge(req: &mut Request, depot: &mut Depot, res: &mut Response) {
     let api: &ProvisioningApi = depot.obtain().unwrap();
@@ -277,7 +344,11 @@ async fn delete_bridge(req: &mut Request, depot: &mut Depot, res: &mut Response)
 
     match api.room_store.get_room_by_matrix_id(&room_id).await {
         Ok(Some(mapping)) => {
-            if let Err(e) = api.room_store.delete_room_mapping(mapping.id).await {
+            let deleted = match api.room_store.delete_room_mapping(mapping.id).await {
+                Ok(()) => api.bridge.delete_portal_by_mxid(&room_id).await,
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = deleted {
                 res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
                 warn!(
                     action = "delete_bridge",
@@ -6,12 +6,18 @@ use std::time::Duration;
 
 use diesel::r2d2::{ConnectionManager, Pool};
 use diesel::sqlite::SqliteConnection;
-use matrix_bridge_feishu::bridge::{MatrixEvent, MatrixEventProcessor, MessageFlow};
+use matrix_bridge_feishu::bridge::message::{BridgeMessage, MessageType};
+use matrix_bridge_feishu::bridge::portal::BridgePortal;
+use matrix_bridge_feishu::bridge::{
+    FeishuBridge, MatrixEvent, MatrixEventProcessor, MessageFlow, ProvisioningError,
+};
 use matrix_bridge_feishu::config::{
     BridgeConfig, Config, DatabaseConfig, LoggingConfig, LoggingWriterConfig, RegistrationConfig,
 };
 use matrix_bridge_feishu::database::sqlite_stores::SqliteStores;
-use matrix_bridge_feishu::database::{Database, MessageMapping, RoomMapping};
+use matrix_bridge_feishu::database::{
+    Database, MessageMapping, Portal, ReactionMapping, RoomMapping, UserMapping,
+};
 use matrix_bridge_feishu::feishu::FeishuService;
 use salvo::affix_state;
 use salvo::prelude::*;
@@ -159,6 +165,7 @@ async fn matrix_to_feishu_pipeline_with_mock_servers_persists_mapping() {
             "body": "",
             "url": format!("{}/media/cat.png", matrix_base),
         })),
+        prev_content: None,
         timestamp: None,
     };
     let parsed =
@@ -225,6 +232,7 @@ async fn matrix_to_feishu_pipeline_with_mock_servers_persists_mapping() {
             "msgtype": "m.text",
             "body": "duplicate should be skipped"
         })),
+        prev_content: None,
         timestamp: None,
     };
     let create_calls_before_duplicate = feishu_state.create_calls.load(Ordering::Relaxed);
@@ -274,6 +282,7 @@ async fn matrix_to_feishu_pipeline_with_mock_servers_persists_mapping() {
                 }
             }
         })),
+        prev_content: None,
         timestamp: None,
     };
     processor
@@ -307,6 +316,7 @@ async fn matrix_to_feishu_pipeline_with_mock_servers_persists_mapping() {
                 "body": "updated body"
             }
         })),
+        prev_content: None,
         timestamp: None,
     };
     processor
@@ -325,6 +335,7 @@ async fn matrix_to_feishu_pipeline_with_mock_servers_persists_mapping() {
         sender: "@alice:localhost".to_string(),
         state_key: None,
         content: Some(json!({ "redacts": "$evt1" })),
+        prev_content: None,
         timestamp: None,
     };
     processor
@@ -456,6 +467,7 @@ async fn matrix_to_feishu_media_oversize_fails_without_degrade() {
             "body": "cat.png",
             "url": format!("{}/media/cat.png", matrix_base),
         })),
+        prev_content: None,
         timestamp: None,
     };
 
@@ -493,6 +505,606 @@ async fn matrix_to_feishu_media_oversize_fails_without_degrade() {
     let _ = std::fs::remove_file(db_path);
 }
 
+async fn open_test_stores() -> (SqliteStores, std::path::PathBuf) {
+    let db_path = std::env::temp_dir().join(format!("matrix-bridge-test-{}.db", Uuid::new_v4()));
+    let db_uri = format!("sqlite:{}", db_path.to_string_lossy());
+    let db = Database::connect("sqlite", &db_uri, 4, 1)
+        .await
+        .expect("db connect should succeed");
+    db.run_migrations()
+        .await
+        .expect("migrations should succeed");
+
+    let manager = ConnectionManager::<SqliteConnection>::new(db_path.to_string_lossy().to_string());
+    let pool = Pool::builder()
+        .max_size(4)
+        .build(manager)
+        .expect("pool should build");
+    (SqliteStores::new(pool), db_path)
+}
+
+fn test_portal(feishu_room_id: &str, mxid: &str) -> Portal {
+    Portal {
+        id: 0,
+        feishu_room_id: feishu_room_id.to_string(),
+        mxid: mxid.to_string(),
+        name: "Portal".to_string(),
+        topic: None,
+        avatar_url: None,
+        encrypted: false,
+        room_type: "group".to_string(),
+        relay_user_id: None,
+        creator_mxid: "@feishu_bot:localhost".to_string(),
+        bridge_info: "{}".to_string(),
+        creation_content: None,
+        last_event: None,
+    }
+}
+
+#[tokio::test]
+async fn portal_store_saves_and_loads_portals() {
+    let (stores, db_path) = open_test_stores().await;
+    let portal_store = stores.portal_store();
+
+    let saved = portal_store
+        .upsert_portal(&test_portal("oc_chat_1", "!room1:localhost"))
+        .await
+        .expect("portal upsert should succeed");
+    assert!(saved.id > 0);
+
+    let by_mxid = portal_store
+        .get_portal_by_mxid("!room1:localhost")
+        .await
+        .expect("lookup by mxid should succeed")
+        .expect("portal should exist");
+    assert_eq!(by_mxid.feishu_room_id, "oc_chat_1");
+
+    let mut renamed = by_mxid.clone();
+    renamed.name = "Renamed".to_string();
+    let updated = portal_store
+        .upsert_portal(&renamed)
+        .await
+        .expect("portal update should succeed");
+    assert_eq!(updated.id, saved.id);
+    assert_eq!(updated.name, "Renamed");
+
+    let by_room = portal_store
+        .get_portal_by_feishu_room("oc_chat_1")
+        .await
+        .expect("lookup by feishu room should succeed")
+        .expect("portal should exist");
+    assert_eq!(by_room.mxid, "!room1:localhost");
+
+    portal_store
+        .delete_portal(saved.id)
+        .await
+        .expect("portal delete should succeed");
+    assert!(
+        portal_store
+            .get_portal_by_mxid("!room1:localhost")
+            .await
+            .expect("lookup by mxid should succeed")
+            .is_none()
+    );
+
+    let _ = std::fs::remove_file(db_path);
+}
+
+#[tokio::test]
+async fn portal_store_rebridge_replaces_stale_mxid_row() {
+    let (stores, db_path) = open_test_stores().await;
+    let portal_store = stores.portal_store();
+
+    portal_store
+        .upsert_portal(&test_portal("oc_old_chat", "!room1:localhost"))
+        .await
+        .expect("initial portal upsert should succeed");
+    portal_store
+        .upsert_portal(&test_portal("oc_new_chat", "!room1:localhost"))
+        .await
+        .expect("re-bridge upsert should not violate the mxid constraint");
+
+    let by_mxid = portal_store
+        .get_portal_by_mxid("!room1:localhost")
+        .await
+        .expect("lookup by mxid should succeed")
+        .expect("portal should exist");
+    assert_eq!(by_mxid.feishu_room_id, "oc_new_chat");
+    assert!(
+        portal_store
+            .get_portal_by_feishu_room("oc_old_chat")
+            .await
+            .expect("lookup by feishu room should succeed")
+            .is_none()
+    );
+    assert_eq!(
+        portal_store
+            .list_portals(None, None)
+            .await
+            .expect("list should succeed")
+            .len(),
+        1
+    );
+
+    let _ = std::fs::remove_file(db_path);
+}
+
+#[derive(Clone, Debug)]
+struct RecordedCall {
+    method: String,
+    path: String,
+    query: String,
+    body: Value,
+}
+
+type MockResponder = Arc<dyn Fn(&RecordedCall) -> Option<Value> + Send + Sync>;
+
+#[derive(Clone)]
+struct RecordingMockState {
+    calls: Arc<Mutex<Vec<RecordedCall>>>,
+    responder: MockResponder,
+}
+
+impl RecordingMockState {
+    fn calls_to(&self, method: &str, path_fragment: &str) -> Vec<RecordedCall> {
+        self.calls
+            .lock()
+            .expect("recorded calls mutex poisoned")
+            .iter()
+            .filter(|call| call.method == method && call.path.contains(path_fragment))
+            .cloned()
+            .collect()
+    }
+}
+
+async fn start_recording_mock(
+    responder: MockResponder,
+) -> (String, RecordingMockState, tokio::task::JoinHandle<()>) {
+    #[handler]
+    async fn record_handler(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+        let state: &RecordingMockState = depot.obtain().expect("mock state should exist");
+        let body = req
+            .payload()
+            .await
+            .map(|bytes| bytes.to_vec())
+            .unwrap_or_default();
+        let call = RecordedCall {
+            method: req.method().to_string(),
+            path: req.uri().path().to_string(),
+            query: req.uri().query().unwrap_or_default().to_string(),
+            body: serde_json::from_slice(&body).unwrap_or(Value::Null),
+        };
+        state
+            .calls
+            .lock()
+            .expect("recorded calls mutex poisoned")
+            .push(call.clone());
+
+        let response = if call.path.contains("tenant_access_token") {
+            json!({
+                "code": 0,
+                "msg": "ok",
+                "tenant_access_token": "mock_token",
+                "expire": 7200
+            })
+        } else {
+            (state.responder)(&call).unwrap_or_else(|| {
+                json!({
+                    "code": 0,
+                    "msg": "ok",
+                    "data": { "message_id": "om_mock" },
+                    "event_id": "$mock_event"
+                })
+            })
+        };
+        res.render(Json(response));
+    }
+
+    let state = RecordingMockState {
+        calls: Arc::new(Mutex::new(Vec::new())),
+        responder,
+    };
+    let router = Router::new()
+        .hoop(affix_state::inject(state.clone()))
+        .push(Router::with_path("{**rest}").goal(record_handler));
+    let (base, handle) = start_router(router).await;
+    (base, state, handle)
+}
+
+struct BridgeHarness {
+    bridge: FeishuBridge,
+    feishu_base: String,
+    feishu: RecordingMockState,
+    matrix: RecordingMockState,
+    db_path: std::path::PathBuf,
+    prev_no_proxy: Option<String>,
+    prev_no_proxy_lower: Option<String>,
+    _guard: std::sync::MutexGuard<'static, ()>,
+}
+
+impl BridgeHarness {
+    async fn start(
+        configure: impl FnOnce(&mut Config),
+        feishu_responder: MockResponder,
+        matrix_responder: MockResponder,
+    ) -> Self {
+        let guard = integration_test_lock()
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let prev_no_proxy = std::env::var("NO_PROXY").ok();
+        let prev_no_proxy_lower = std::env::var("no_proxy").ok();
+        set_env_var("NO_PROXY", "127.0.0.1,localhost");
+        set_env_var("no_proxy", "127.0.0.1,localhost");
+
+        let (feishu_base, feishu, _) = start_recording_mock(feishu_responder).await;
+        let (matrix_base, matrix, _) = start_recording_mock(matrix_responder).await;
+        wait_for_http_ready(&format!("{}/ready", feishu_base)).await;
+        wait_for_http_ready(&format!("{}/ready", matrix_base)).await;
+        for state in [&feishu, &matrix] {
+            state
+                .calls
+                .lock()
+                .expect("recorded calls mutex poisoned")
+                .clear();
+        }
+
+        let db_path =
+            std::env::temp_dir().join(format!("matrix-bridge-test-{}.db", Uuid::new_v4()));
+        let db_uri = format!("sqlite:{}", db_path.to_string_lossy());
+        let mut config = build_test_config(&matrix_base, &db_uri);
+        configure(&mut config);
+
+        set_env_var("FEISHU_API_BASE_URL", format!("{}/open-apis", feishu_base));
+        let bridge = FeishuBridge::new(config)
+            .await
+            .expect("bridge should initialize");
+        remove_env_var("FEISHU_API_BASE_URL");
+
+        Self {
+            bridge,
+            feishu_base,
+            feishu,
+            matrix,
+            db_path,
+            prev_no_proxy,
+            prev_no_proxy_lower,
+            _guard: guard,
+        }
+    }
+
+    async fn bridge_room(&self, matrix_room_id: &str, feishu_chat_id: &str) {
+        self.bridge
+            .room_store()
+            .create_room_mapping(&RoomMapping::new(
+                matrix_room_id.to_string(),
+                feishu_chat_id.to_string(),
+                Some("Bridged Room".to_string()),
+            ))
+            .await
+            .expect("room mapping should persist");
+    }
+}
+
+impl Drop for BridgeHarness {
+    fn drop(&mut self) {
+        match self.prev_no_proxy.take() {
+            Some(value) => set_env_var("NO_PROXY", value),
+            None => remove_env_var("NO_PROXY"),
+        }
+        match self.prev_no_proxy_lower.take() {
+            Some(value) => set_env_var("no_proxy", value),
+            None => remove_env_var("no_proxy"),
+        }
+        let _ = std::fs::remove_file(&self.db_path);
+    }
+}
+
+fn feishu_user(user_id: &str, name: &str) -> Value {
+    json!({
+        "user": {
+            "user_id": user_id,
+            "name": name,
+            "status": { "is_activated": true, "is_exited": false, "is_resigned": false },
+            "department_ids": [],
+            "employee_type": 1,
+            "join_time": 0,
+            "custom_attrs": null
+        }
+    })
+}
+
+fn feishu_text_message(message_id: &str, chat_id: &str, sender: &str) -> BridgeMessage {
+    BridgeMessage {
+        id: message_id.to_string(),
+        sender: sender.to_string(),
+        room_id: chat_id.to_string(),
+        content: "hello".to_string(),
+        msg_type: MessageType::Text,
+        timestamp: chrono::Utc::now(),
+        attachments: Vec::new(),
+        thread_id: None,
+        root_id: None,
+        parent_id: None,
+        rich_content: None,
+        mentions: Vec::new(),
+    }
+}
+
+fn no_responses() -> MockResponder {
+    Arc::new(|_| None)
+}
+
+fn bot_join_responder() -> MockResponder {
+    Arc::new(|call| {
+        (call.method == "POST" && call.path.contains("/join/"))
+            .then(|| json!({ "room_id": "!room:localhost" }))
+    })
+}
+
+#[tokio::test]
+async fn linked_matrix_user_leave_removes_feishu_member_when_opted_in() {
+    let harness = BridgeHarness::start(
+        |config| config.bridge.matrix_leave_action = "remove".to_string(),
+        no_responses(),
+        no_responses(),
+    )
+    .await;
+    harness.bridge_room("!room:localhost", "oc_chat").await;
+    harness
+        .bridge
+        .user_store()
+        .set_linked_feishu_user("@alice:localhost", Some("u_alice"))
+        .await
+        .expect("link should persist");
+
+    harness
+        .bridge
+        .handle_linked_user_leave("!room:localhost", "@bob:localhost")
+        .await
+        .expect("unlinked leave should be ignored");
+    assert!(harness.feishu.calls_to("DELETE", "/members").is_empty());
+
+    harness
+        .bridge
+        .handle_linked_user_leave("!room:localhost", "@alice:localhost")
+        .await
+        .expect("linked leave should succeed");
+    let removals = harness
+        .feishu
+        .calls_to("DELETE", "/open-apis/im/v1/chats/oc_chat/members");
+    assert_eq!(removals.len(), 1);
+    assert_eq!(removals[0].body["id_list"], json!(["u_alice"]));
+}
+
+#[tokio::test]
+async fn linked_matrix_user_leave_notifies_by_default() {
+    let harness = BridgeHarness::start(|_| {}, no_responses(), no_responses()).await;
+    harness.bridge_room("!room:localhost", "oc_chat").await;
+    harness
+        .bridge
+        .user_store()
+        .set_linked_feishu_user("@alice:localhost", Some("u_alice"))
+        .await
+        .expect("link should persist");
+
+    harness
+        .bridge
+        .handle_linked_user_leave("!room:localhost", "@alice:localhost")
+        .await
+        .expect("linked leave should succeed");
+    assert!(harness.feishu.calls_to("DELETE", "/members").is_empty());
+    let notices = harness.feishu.calls_to("POST", "/open-apis/im/v1/messages");
+    assert_eq!(notices.len(), 1);
+    assert!(
+        notices[0].body["content"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("@alice:localhost left the Matrix room")
+    );
+}
+
+fn feishu_data(data: Value) -> Option<Value> {
+    Some(json!({ "code": 0, "msg": "ok", "data": data }))
+}
+
+fn alias_chat_feishu_responder() -> MockResponder {
+    Arc::new(|call| {
+        if call.path.ends_with("/members/is_in_chat") {
+            feishu_data(json!({ "is_in_chat": true }))
+        } else if call.path.ends_with("/chats/oc_chat/members") {
+            feishu_data(json!({
+                "items": [{ "member_id": "u_alice" }, { "member_id": "u_bob" }],
+                "has_more": false
+            }))
+        } else if call.path.ends_with("/chats/oc_chat") {
+            feishu_data(json!({ "name": "Ops", "chat_mode": "group", "chat_type": "private" }))
+        } else {
+            None
+        }
+    })
+}
+
+#[tokio::test]
+async fn alias_query_creates_and_binds_a_joinable_portal() {
+    let matrix_responder: MockResponder = Arc::new(|call| {
+        call.path
+            .ends_with("/createRoom")
+            .then(|| json!({ "room_id": "!alias:localhost" }))
+    });
+    let harness =
+        BridgeHarness::start(|_| {}, alias_chat_feishu_responder(), matrix_responder).await;
+    harness
+        .bridge
+        .user_store()
+        .set_linked_feishu_user("@alice:localhost", Some("u_alice"))
+        .await
+        .expect("link should persist");
+
+    let room_id = harness
+        .bridge
+        .create_alias_portal("#feishu_oc_chat:localhost")
+        .await
+        .expect("alias query should succeed");
+    assert_eq!(room_id.as_deref(), Some("!alias:localhost"));
+    let created = harness.matrix.calls_to("POST", "/createRoom");
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0].body["name"], "Ops");
+    assert_eq!(created[0].body["preset"], "public_chat");
+    assert_eq!(created[0].body["visibility"], "private");
+    assert_eq!(created[0].body["room_alias_name"], "feishu_oc_chat");
+    assert_eq!(created[0].body["invite"], json!(["@alice:localhost"]));
+    let mapping = harness
+        .bridge
+        .room_store()
+        .get_room_by_feishu_id("oc_chat")
+        .await
+        .expect("mapping lookup should succeed")
+        .expect("alias portal should be bound before the query returns");
+    assert_eq!(mapping.matrix_room_id, "!alias:localhost");
+
+    let again = harness
+        .bridge
+        .create_alias_portal("#feishu_oc_chat:localhost")
+        .await
+        .expect("alias query should succeed");
+    assert!(again.is_none(), "a bridged chat must not get a second room");
+    assert_eq!(harness.matrix.calls_to("POST", "/createRoom").len(), 1);
+}
+
+#[tokio::test]
+async fn failed_alias_portal_leaves_chat_free_for_feishu_messages() {
+    let create_calls = Arc::new(AtomicU64::new(0));
+    let matrix_responder: MockResponder = {
+        let create_calls = create_calls.clone();
+        Arc::new(move |call| {
+            if !call.path.ends_with("/createRoom") {
+                return None;
+            }
+            if create_calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                Some(json!({ "errcode": "M_UNKNOWN", "error": "boom" }))
+            } else {
+                Some(json!({ "room_id": "!portal:localhost" }))
+            }
+        })
+    };
+    let harness =
+        BridgeHarness::start(|_| {}, alias_chat_feishu_responder(), matrix_responder).await;
+
+    harness
+        .bridge
+        .create_alias_portal("#feishu_oc_chat:localhost")
+        .await
+        .expect_err("room creation should fail");
+    assert!(
+        harness
+            .bridge
+            .room_store()
+            .get_room_by_feishu_id("oc_chat")
+            .await
+            .expect("mapping lookup should succeed")
+            .is_none()
+    );
+
+    let started = std::time::Instant::now();
+    harness
+        .bridge
+        .handle_feishu_message(feishu_text_message("om_after_alias", "oc_chat", "u_bob"))
+        .await
+        .expect("message should bridge");
+    assert!(
+        started.elapsed() < Duration::from_secs(4),
+        "a failed alias query must not leave the chat reserved"
+    );
+    assert_eq!(harness.matrix.calls_to("POST", "/createRoom").len(), 2);
+    let mapping = harness
+        .bridge
+        .room_store()
+        .get_room_by_feishu_id("oc_chat")
+        .await
+        .expect("mapping lookup should succeed")
+        .expect("the Feishu message should create the portal");
+    assert_eq!(mapping.matrix_room_id, "!portal:localhost");
+}
+
+#[tokio::test]
+async fn matrix_power_level_changes_only_touch_changed_feishu_managers() {
+    let feishu_responder: MockResponder = Arc::new(|call| {
+        if call.method == "GET" && call.path.ends_with("/chats/oc_chat") {
+            feishu_data(json!({
+                "chat_mode": "group",
+                "owner_id": "u_owner",
+                "user_manager_id_list": ["u_bob", "u_carol", "u_erin"]
+            }))
+        } else {
+            None
+        }
+    });
+    let harness = BridgeHarness::start(
+        |config| config.bridge.bridge_matrix_power_levels = true,
+        feishu_responder,
+        no_responses(),
+    )
+    .await;
+    harness.bridge_room("!room:localhost", "oc_chat").await;
+    for feishu_user_id in ["u_bob", "u_carol"] {
+        harness
+            .bridge
+            .user_store()
+            .create_user_mapping(&UserMapping::new(
+                format!("@feishu_{}:localhost", feishu_user_id),
+                feishu_user_id.to_string(),
+                None,
+            ))
+            .await
+            .expect("user mapping should persist");
+    }
+    harness
+        .bridge
+        .user_store()
+        .set_linked_feishu_user("@dave:localhost", Some("u_dave"))
+        .await
+        .expect("link should persist");
+
+    let prev_content = json!({
+        "users": {
+            "@feishubot:localhost": 100,
+            "@feishu_u_bob:localhost": 50,
+            "@feishu_u_carol:localhost": 50
+        }
+    });
+    let content = json!({
+        "users": {
+            "@feishubot:localhost": 100,
+            "@feishu_u_bob:localhost": 50,
+            "@feishu_u_carol:localhost": 0,
+            "@dave:localhost": 50
+        }
+    });
+    harness
+        .bridge
+        .push_power_levels_to_feishu("!room:localhost", &content, Some(&prev_content))
+        .await
+        .expect("power level push should succeed");
+
+    let added = harness.feishu.calls_to("POST", "/managers/add_managers");
+    assert_eq!(added.len(), 1);
+    assert_eq!(added[0].body["manager_ids"], json!(["u_dave"]));
+    let removed = harness.feishu.calls_to("POST", "/managers/delete_managers");
+    assert_eq!(removed.len(), 1);
+    assert_eq!(
+        removed[0].body["manager_ids"],
+        json!(["u_carol"]),
+        "managers whose Matrix level did not change must be left alone"
+    );
+
+    harness
+        .bridge
+        .push_power_levels_to_feishu("!room:localhost", &content, Some(&content))
+        .await
+        .expect("unchanged power levels should be a no-op");
+    assert_eq!(harness.feishu.calls_to("GET", "/chats/oc_chat").len(), 1);
+}
+
 fn build_test_config(matrix_base: &str, db_uri: &str) -> Config {
     let mut permissions = HashMap::new();
     permissions.insert("*".to_string(), "relay".to_string());
@@ -525,8 +1137,12 @@ fn build_test_config(matrix_base: &str, db_uri: &str) -> Config {
             bridge_matrix_reactions: false,
             bridge_matrix_redactions: true,
             bridge_matrix_leave: true,
+            matrix_leave_action: "notify".to_string(),
             bridge_feishu_join: true,
             bridge_feishu_leave: true,
+            bridge_feishu_reactions: true,
+            bridge_feishu_read_receipts: true,
+            bridge_matrix_power_levels: false,
             allow_plain_text: true,
             allow_markdown: true,
             allow_html: false,
@@ -549,6 +1165,9 @@ fn build_test_config(matrix_base: &str, db_uri: &str) -> Config {
             api_timeout: 60,
             enable_rich_text: true,
             convert_cards: true,
+            enable_tenant_spaces: false,
+            enable_user_spaces: false,
+            disband_policy: "archive".to_string(),
         },
         logging: LoggingConfig {
             min_level: "info".to_string(),
@@ -731,3 +1350,1362 @@ async fn wait_for_http_ready(url: &str) {
         tokio::time::sleep(Duration::from_millis(50)).await;
     }
 }
+
+#[tokio::test]
+async fn matrix_reactions_share_one_feishu_reaction_and_skip_feishu_origin_rows() {
+    let feishu_responder: MockResponder = Arc::new(|call| {
+        if call.method == "POST" && call.path.ends_with("/reactions") {
+            feishu_data(json!({ "reaction_id": "rc_shared" }))
+        } else {
+            None
+        }
+    });
+    let harness = BridgeHarness::start(|_| {}, feishu_responder, no_responses()).await;
+    harness
+        .bridge
+        .message_store()
+        .create_message_mapping(&MessageMapping::new(
+            "$target".to_string(),
+            "om_target".to_string(),
+            "!room:localhost".to_string(),
+            "@alice:localhost".to_string(),
+            "matrix".to_string(),
+        ))
+        .await
+        .expect("target mapping should persist");
+    harness
+        .bridge
+        .reaction_store()
+        .create_reaction_mapping(
+            &ReactionMapping::new(
+                "$puppet-reaction".to_string(),
+                "om_target".to_string(),
+                "om_target:u_bob:THUMBSUP".to_string(),
+                "!room:localhost".to_string(),
+                "@feishu_u_bob:localhost".to_string(),
+                "👍".to_string(),
+            )
+            .with_origin(ReactionMapping::ORIGIN_FEISHU),
+        )
+        .await
+        .expect("inbound reaction mapping should persist");
+
+    for (event_id, sender) in [("$r1", "@alice:localhost"), ("$r2", "@carol:localhost")] {
+        harness
+            .bridge
+            .bridge_matrix_reaction(event_id, "!room:localhost", sender, "$target", "👍")
+            .await
+            .expect("matrix reaction should bridge");
+    }
+    assert_eq!(harness.feishu.calls_to("POST", "/reactions").len(), 1);
+
+    assert!(
+        !harness
+            .bridge
+            .redact_bridged_reaction("$puppet-reaction")
+            .await
+            .expect("puppet reaction redaction should not fail")
+    );
+    assert!(
+        harness
+            .bridge
+            .redact_bridged_reaction("$r1")
+            .await
+            .expect("first redaction should succeed")
+    );
+    assert!(harness.feishu.calls_to("DELETE", "/reactions").is_empty());
+
+    assert!(
+        harness
+            .bridge
+            .redact_bridged_reaction("$r2")
+            .await
+            .expect("last redaction should succeed")
+    );
+    let deleted = harness.feishu.calls_to("DELETE", "/reactions");
+    assert_eq!(deleted.len(), 1);
+    assert!(
+        deleted[0]
+            .path
+            .ends_with("/messages/om_target/reactions/rc_shared")
+    );
+}
+
+fn p2p_feishu_responder() -> MockResponder {
+    Arc::new(|call| {
+        if call.method == "GET" && call.path.ends_with("/contact/v3/users/u_sam") {
+            feishu_data(feishu_user("u_sam", "Sam"))
+        } else if call.method == "GET" && call.path.ends_with("/chats/oc_p2p") {
+            feishu_data(json!({ "chat_id": "oc_p2p", "chat_mode": "p2p" }))
+        } else {
+            None
+        }
+    })
+}
+
+fn direct_room_matrix_responder() -> MockResponder {
+    Arc::new(|call| {
+        if call.path.ends_with("/createRoom") {
+            Some(json!({ "room_id": "!dm:localhost" }))
+        } else if call.method == "GET" && call.path.ends_with("/account_data/m.direct") {
+            Some(json!({ "errcode": "M_NOT_FOUND", "error": "not found" }))
+        } else {
+            None
+        }
+    })
+}
+
+fn direct_account_data_owners(harness: &BridgeHarness) -> Vec<String> {
+    let mut owners = harness
+        .matrix
+        .calls_to("PUT", "/account_data/m.direct")
+        .into_iter()
+        .map(|call| call.query)
+        .collect::<Vec<_>>();
+    owners.sort();
+    owners
+}
+
+#[tokio::test]
+async fn feishu_p2p_chat_from_unlinked_user_is_not_bridged() {
+    let harness = BridgeHarness::start(
+        |config| {
+            config
+                .bridge
+                .permissions
+                .insert("@admin:localhost".to_string(), "admin".to_string());
+        },
+        p2p_feishu_responder(),
+        direct_room_matrix_responder(),
+    )
+    .await;
+
+    harness
+        .bridge
+        .handle_feishu_message(feishu_text_message("om_p2p_1", "oc_p2p", "u_sam"))
+        .await
+        .expect("p2p message should be handled");
+
+    assert!(harness.matrix.calls_to("POST", "/createRoom").is_empty());
+    assert!(harness.matrix.calls_to("PUT", "/send/").is_empty());
+    assert!(
+        harness
+            .bridge
+            .room_store()
+            .get_room_by_feishu_id("oc_p2p")
+            .await
+            .expect("room lookup should succeed")
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn feishu_p2p_chat_from_linked_user_becomes_their_dm_with_the_bot() {
+    let harness = BridgeHarness::start(
+        |config| {
+            config
+                .bridge
+                .permissions
+                .insert("@admin:localhost".to_string(), "admin".to_string());
+        },
+        p2p_feishu_responder(),
+        direct_room_matrix_responder(),
+    )
+    .await;
+    harness
+        .bridge
+        .user_store()
+        .set_linked_feishu_user("@sam:localhost", Some("u_sam"))
+        .await
+        .expect("link should persist");
+
+    harness
+        .bridge
+        .handle_feishu_message(feishu_text_message("om_p2p_2", "oc_p2p", "u_sam"))
+        .await
+        .expect("p2p message should bridge");
+
+    let created = harness.matrix.calls_to("POST", "/createRoom");
+    assert_eq!(created.len(), 1);
+    assert!(created[0].query.contains("feishubot"));
+    assert_eq!(created[0].body["is_direct"], json!(true));
+    assert_eq!(created[0].body["invite"], json!(["@sam:localhost"]));
+    assert_eq!(
+        direct_account_data_owners(&harness),
+        vec![
+            "user_id=%40feishubot%3Alocalhost".to_string(),
+            "user_id=%40sam%3Alocalhost".to_string(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn unmapped_feishu_group_gets_a_room_with_its_linked_members_invited() {
+    let feishu_responder: MockResponder = Arc::new(|call| {
+        if call.method != "GET" {
+            return None;
+        }
+        if call.path.ends_with("/contact/v3/users/u_bob") {
+            feishu_data(feishu_user("u_bob", "Bob"))
+        } else if call.path.ends_with("/chats/oc_new/members") {
+            feishu_data(json!({
+                "items": [{ "member_id": "u_alice" }, { "member_id": "u_bob" }],
+                "has_more": false
+            }))
+        } else if call.path.ends_with("/chats/oc_new") {
+            feishu_data(json!({
+                "chat_id": "oc_new",
+                "name": "Launch",
+                "description": "Launch planning",
+                "chat_mode": "group"
+            }))
+        } else {
+            None
+        }
+    });
+    let matrix_responder: MockResponder = Arc::new(|call| {
+        call.path
+            .ends_with("/createRoom")
+            .then(|| json!({ "room_id": "!new:localhost" }))
+    });
+    let harness = BridgeHarness::start(
+        |config| {
+            config
+                .bridge
+                .permissions
+                .insert("@admin:localhost".to_string(), "admin".to_string());
+        },
+        feishu_responder,
+        matrix_responder,
+    )
+    .await;
+    harness
+        .bridge
+        .user_store()
+        .set_linked_feishu_user("@alice:localhost", Some("u_alice"))
+        .await
+        .expect("link should persist");
+
+    harness
+        .bridge
+        .handle_feishu_message(feishu_text_message("om_new_1", "oc_new", "u_bob"))
+        .await
+        .expect("message from unmapped chat should bridge");
+
+    let created = harness.matrix.calls_to("POST", "/createRoom");
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0].body["name"], "Launch");
+    assert_eq!(created[0].body["topic"], "Launch planning");
+    assert_eq!(
+        created[0].body["invite"],
+        json!(["@alice:localhost", "@feishu_u_bob:localhost"])
+    );
+    let mapping = harness
+        .bridge
+        .room_store()
+        .get_room_by_feishu_id("oc_new")
+        .await
+        .expect("room lookup should succeed")
+        .expect("room mapping should persist");
+    assert_eq!(mapping.matrix_room_id, "!new:localhost");
+    let sent = harness.matrix.calls_to("PUT", "/send/m.room.message/");
+    assert!(
+        sent.iter()
+            .any(|call| call.path.contains("%21new%3Alocalhost") && call.body["body"] == "hello"),
+        "message should be delivered to the new room: {sent:?}"
+    );
+}
+
+fn matrix_pm_feishu_responder() -> MockResponder {
+    Arc::new(|call| {
+        if call.method == "GET" && call.path.ends_with("/contact/v3/users/u_sam") {
+            feishu_data(feishu_user("u_sam", "Sam"))
+        } else if call.method == "GET" && call.path.ends_with("/chats/oc_p2p") {
+            feishu_data(json!({ "chat_id": "oc_p2p", "chat_mode": "p2p" }))
+        } else if call.method == "POST" && call.path.ends_with("/im/v1/messages") {
+            feishu_data(json!({ "message_id": "om_intro", "chat_id": "oc_p2p" }))
+        } else if call.method == "GET" && call.path.ends_with("/contact/v3/scopes") {
+            feishu_data(json!({ "user_ids": ["u_sam", "u_sammy"], "has_more": false }))
+        } else if call.method == "GET" && call.path.ends_with("/contact/v3/users/batch") {
+            feishu_data(json!({
+                "items": [
+                    { "user_id": "u_sam", "name": "Sam" },
+                    { "user_id": "u_sammy", "name": "Sammy" }
+                ]
+            }))
+        } else {
+            None
+        }
+    })
+}
+
+fn matrix_pm_matrix_responder() -> MockResponder {
+    Arc::new(|call| {
+        if call.path.ends_with("/createRoom") {
+            Some(json!({ "room_id": "!dm:localhost" }))
+        } else if call.method == "POST" && call.path.contains("/join/") {
+            Some(json!({ "room_id": "!dm:localhost" }))
+        } else if call.method == "GET" && call.path.ends_with("/account_data/m.direct") {
+            Some(json!({ "errcode": "M_NOT_FOUND", "error": "not found" }))
+        } else {
+            None
+        }
+    })
+}
+
+#[tokio::test]
+async fn matrix_pm_binds_the_real_p2p_chat_and_keeps_it_private_to_the_requester() {
+    let harness = BridgeHarness::start(
+        |_| {},
+        matrix_pm_feishu_responder(),
+        matrix_pm_matrix_responder(),
+    )
+    .await;
+
+    let reply = harness
+        .bridge
+        .start_direct_chat("@alice:localhost", "u_sam")
+        .await
+        .expect("pm should succeed");
+    assert!(reply.contains("!dm:localhost"), "{reply}");
+
+    let sent = harness.feishu.calls_to("POST", "/im/v1/messages");
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].query.contains("receive_id_type=user_id"));
+    assert_eq!(sent[0].body["receive_id"], json!("u_sam"));
+
+    let room_store = harness.bridge.room_store();
+    let mapping = room_store
+        .get_room_by_feishu_id("oc_p2p")
+        .await
+        .expect("mapping lookup should succeed")
+        .expect("p2p chat should be mapped");
+    assert_eq!(mapping.matrix_room_id, "!dm:localhost");
+    assert!(
+        room_store
+            .get_room_by_feishu_id("u_sam")
+            .await
+            .expect("mapping lookup should succeed")
+            .is_none()
+    );
+    let direct_chat = harness
+        .bridge
+        .direct_chat_store()
+        .get_direct_chat_by_feishu_user("u_sam")
+        .await
+        .expect("direct chat lookup should succeed")
+        .expect("direct chat should be recorded");
+    assert_eq!(direct_chat.owner_mxid, "@alice:localhost");
+    assert_eq!(direct_chat.feishu_chat_id, "oc_p2p");
+
+    let refused = harness
+        .bridge
+        .start_direct_chat("@mallory:localhost", "u_sam")
+        .await
+        .expect("pm should answer");
+    assert!(refused.contains("another Matrix user"), "{refused}");
+    assert!(
+        harness
+            .matrix
+            .calls_to("POST", "/invite")
+            .iter()
+            .all(|call| call.body["user_id"] != json!("@mallory:localhost"))
+    );
+
+    let reused = harness
+        .bridge
+        .start_direct_chat("@alice:localhost", "u_sam")
+        .await
+        .expect("pm should answer");
+    assert!(reused.starts_with("Reusing direct chat"), "{reused}");
+
+    harness
+        .bridge
+        .handle_feishu_message(feishu_text_message("om_p2p_3", "oc_p2p", "u_sam"))
+        .await
+        .expect("p2p reply should bridge");
+    assert_eq!(harness.matrix.calls_to("POST", "/createRoom").len(), 1);
+    assert_eq!(harness.feishu.calls_to("POST", "/im/v1/messages").len(), 1);
+}
+
+#[tokio::test]
+async fn matrix_pm_resolves_matrix_ids_and_contact_names() {
+    let harness = BridgeHarness::start(
+        |_| {},
+        matrix_pm_feishu_responder(),
+        matrix_pm_matrix_responder(),
+    )
+    .await;
+    harness
+        .bridge
+        .user_store()
+        .set_linked_feishu_user("@sam:localhost", Some("u_sam"))
+        .await
+        .expect("link should persist");
+
+    let by_name = harness
+        .bridge
+        .start_direct_chat("@alice:localhost", "sam")
+        .await
+        .expect("pm should succeed");
+    assert!(
+        by_name.starts_with("Started direct chat with Sam:"),
+        "{by_name}"
+    );
+    assert_eq!(
+        harness
+            .feishu
+            .calls_to("GET", "/contact/v3/users/batch")
+            .len(),
+        1
+    );
+    let sent = harness.feishu.calls_to("POST", "/im/v1/messages");
+    assert_eq!(sent[0].body["receive_id"], json!("u_sam"));
+
+    let by_mxid = harness
+        .bridge
+        .start_direct_chat("@alice:localhost", "@sam:localhost")
+        .await
+        .expect("pm should answer");
+    assert!(
+        by_mxid.starts_with("Reusing direct chat with Sam"),
+        "{by_mxid}"
+    );
+    assert!(
+        harness
+            .feishu
+            .calls_to("POST", "/contact/v3/users/batch_get_id")
+            .is_empty()
+    );
+
+    let unknown = harness
+        .bridge
+        .start_direct_chat("@alice:localhost", "@nobody:localhost")
+        .await
+        .expect("pm should answer");
+    assert!(unknown.starts_with("Could not find"), "{unknown}");
+}
+
+#[tokio::test]
+async fn thirdparty_user_lookup_searches_feishu_contacts_without_creating_mappings() {
+    let feishu_responder: MockResponder = Arc::new(|call| {
+        if call.method == "GET" && call.path.ends_with("/contact/v3/scopes") {
+            feishu_data(json!({ "user_ids": ["u_ann", "u_bob"], "has_more": false }))
+        } else if call.method == "GET" && call.path.ends_with("/contact/v3/users/batch") {
+            feishu_data(json!({
+                "items": [
+                    { "user_id": "u_ann", "name": "Ann Lee" },
+                    { "user_id": "u_bob", "name": "Bob" }
+                ]
+            }))
+        } else if call.method == "GET" && call.path.ends_with("/contact/v3/users/u_carl") {
+            feishu_data(feishu_user("u_carl", "Carl"))
+        } else {
+            None
+        }
+    });
+    let harness = BridgeHarness::start(|_| {}, feishu_responder, no_responses()).await;
+
+    let by_name = harness
+        .bridge
+        .thirdparty_users(&HashMap::from([("name".to_string(), "ann".to_string())]))
+        .await
+        .expect("name lookup should succeed");
+    assert_eq!(by_name.len(), 1);
+    assert_eq!(by_name[0]["userid"], json!("@feishu_u_ann:localhost"));
+    assert_eq!(by_name[0]["fields"]["name"], json!("Ann Lee"));
+    let batch = harness.feishu.calls_to("GET", "/contact/v3/users/batch");
+    assert_eq!(batch.len(), 1);
+    assert!(batch[0].query.contains("user_ids=u_ann"));
+    assert!(batch[0].query.contains("user_ids=u_bob"));
+
+    let by_id = harness
+        .bridge
+        .thirdparty_users(&HashMap::from([("id".to_string(), "u_carl".to_string())]))
+        .await
+        .expect("id lookup should succeed");
+    assert_eq!(by_id.len(), 1);
+    assert_eq!(by_id[0]["userid"], json!("@feishu_u_carl:localhost"));
+
+    let user_store = harness.bridge.user_store();
+    for feishu_user_id in ["u_ann", "u_carl"] {
+        assert!(
+            user_store
+                .get_user_by_feishu_id(feishu_user_id)
+                .await
+                .expect("user lookup should succeed")
+                .is_none()
+        );
+    }
+}
+
+#[tokio::test]
+async fn thirdparty_location_lookup_does_not_publish_aliases() {
+    let feishu_responder: MockResponder = Arc::new(|call| {
+        if call.method == "GET" && call.path.ends_with("/chats/oc_chat") {
+            feishu_data(json!({ "chat_id": "oc_chat", "name": "Team" }))
+        } else {
+            None
+        }
+    });
+    let harness = BridgeHarness::start(|_| {}, feishu_responder, no_responses()).await;
+    harness.bridge_room("!room:localhost", "oc_chat").await;
+
+    let locations = harness
+        .bridge
+        .thirdparty_locations(&HashMap::from([("id".to_string(), "oc_chat".to_string())]))
+        .await
+        .expect("location lookup should succeed");
+    assert_eq!(
+        locations,
+        vec![json!({
+            "alias": "#feishu_oc_chat:localhost",
+            "protocol": "feishu",
+            "fields": { "id": "oc_chat", "name": "Team" }
+        })]
+    );
+    assert!(harness.matrix.calls_to("PUT", "/directory/room").is_empty());
+}
+
+fn space_child_responder(existing_children: &'static [&'static str]) -> MockResponder {
+    Arc::new(move |call| {
+        if call.method == "GET" && call.path.contains("/state/m.space.child/") {
+            let present = existing_children.iter().any(|child| {
+                call.path
+                    .ends_with(&child.replace('!', "%21").replace(':', "%3A"))
+            });
+            Some(if present {
+                json!({ "via": ["localhost"] })
+            } else {
+                json!({ "errcode": "M_NOT_FOUND", "error": "not found" })
+            })
+        } else {
+            None
+        }
+    })
+}
+
+#[tokio::test]
+async fn tenant_space_backfill_adds_only_missing_bridged_rooms() {
+    let harness = BridgeHarness::start(
+        |config| config.bridge.enable_tenant_spaces = true,
+        no_responses(),
+        space_child_responder(&["!a:localhost"]),
+    )
+    .await;
+    harness
+        .bridge
+        .space_store()
+        .set_tenant_space("default", "!space:localhost")
+        .await
+        .expect("tenant space should persist");
+    harness.bridge_room("!a:localhost", "oc_a").await;
+    harness.bridge_room("!b:localhost", "oc_b").await;
+
+    let added = harness
+        .bridge
+        .backfill_portal_spaces()
+        .await
+        .expect("backfill should succeed");
+
+    assert_eq!(added, 1);
+    let puts = harness.matrix.calls_to("PUT", "/state/m.space.child/");
+    assert_eq!(puts.len(), 1);
+    assert!(puts[0].path.contains("%21space%3Alocalhost"));
+    assert!(puts[0].path.ends_with("%21b%3Alocalhost"));
+    for mxid in ["!a:localhost", "!b:localhost"] {
+        assert!(
+            harness
+                .bridge
+                .portal_store()
+                .get_portal_by_mxid(mxid)
+                .await
+                .expect("portal lookup should succeed")
+                .is_some()
+        );
+    }
+}
+
+#[tokio::test]
+async fn user_space_join_skips_puppets_and_existing_children() {
+    let harness = BridgeHarness::start(
+        |config| config.bridge.enable_user_spaces = true,
+        no_responses(),
+        space_child_responder(&["!room:localhost"]),
+    )
+    .await;
+    harness
+        .bridge
+        .portal_store()
+        .upsert_portal(&test_portal("oc_chat", "!room:localhost"))
+        .await
+        .expect("portal should persist");
+    harness
+        .bridge
+        .space_store()
+        .set_user_space("@alice:localhost", "!alice-space:localhost")
+        .await
+        .expect("user space should persist");
+
+    for user_id in ["@feishu_u_bob:localhost", "@alice:localhost"] {
+        harness
+            .bridge
+            .add_portal_to_user_space(user_id, "!room:localhost")
+            .await
+            .expect("user space update should succeed");
+    }
+
+    let state_reads = harness.matrix.calls_to("GET", "/state/m.space.child/");
+    assert_eq!(state_reads.len(), 1);
+    assert!(state_reads[0].path.contains("%21alice-space%3Alocalhost"));
+    assert!(harness.matrix.calls_to("PUT", "/state/").is_empty());
+    assert!(harness.matrix.calls_to("POST", "/createRoom").is_empty());
+}
+
+#[tokio::test]
+async fn created_feishu_chat_includes_puppets_linked_members_and_requester() {
+    let feishu_responder: MockResponder = Arc::new(|call| {
+        if call.method == "POST" && call.path.ends_with("/im/v1/chats") {
+            feishu_data(json!({ "chat_id": "oc_new" }))
+        } else {
+            None
+        }
+    });
+    let matrix_responder: MockResponder = Arc::new(|call| {
+        if call.path.ends_with("/joined_members") {
+            Some(json!({
+                "joined": {
+                    "@feishubot:localhost": {},
+                    "@feishu_u_bob:localhost": {},
+                    "@carol:localhost": {},
+                    "@dave:localhost": {}
+                }
+            }))
+        } else if call.method == "GET" && call.path.contains("/state/") {
+            Some(json!({ "errcode": "M_NOT_FOUND", "error": "not found" }))
+        } else {
+            None
+        }
+    });
+    let harness = BridgeHarness::start(|_| {}, feishu_responder, matrix_responder).await;
+    harness
+        .bridge
+        .user_store()
+        .create_user_mapping(&UserMapping::new(
+            "@feishu_u_bob:localhost".to_string(),
+            "u_bob".to_string(),
+            None,
+        ))
+        .await
+        .expect("puppet mapping should persist");
+    for (matrix_user_id, feishu_user_id) in [
+        ("@carol:localhost", "u_carol"),
+        ("@erin:localhost", "u_erin"),
+    ] {
+        harness
+            .bridge
+            .user_store()
+            .set_linked_feishu_user(matrix_user_id, Some(feishu_user_id))
+            .await
+            .expect("link should persist");
+    }
+
+    let mapping = harness
+        .bridge
+        .create_feishu_chat_for_room("!room:localhost", "@erin:localhost")
+        .await
+        .expect("chat creation should succeed");
+
+    assert_eq!(mapping.feishu_chat_id, "oc_new");
+    let created = harness.feishu.calls_to("POST", "/im/v1/chats");
+    assert_eq!(created.len(), 1);
+    let mut members = created[0].body["user_id_list"]
+        .as_array()
+        .expect("user_id_list should be an array")
+        .iter()
+        .filter_map(Value::as_str)
+        .collect::<Vec<_>>();
+    members.sort();
+    assert_eq!(members, vec!["u_bob", "u_carol", "u_erin"]);
+}
+
+fn created_chat_matrix_responder() -> MockResponder {
+    Arc::new(|call| {
+        if call.path.ends_with("/joined_members") {
+            Some(json!({ "joined": { "@feishubot:localhost": {}, "@erin:localhost": {} } }))
+        } else if call.method == "GET" && call.path.contains("/state/") {
+            Some(json!({ "errcode": "M_NOT_FOUND", "error": "not found" }))
+        } else {
+            None
+        }
+    })
+}
+
+#[tokio::test]
+async fn created_feishu_chat_reports_feishu_failures_as_upstream_errors() {
+    let feishu_responder: MockResponder = Arc::new(|call| {
+        (call.method == "POST" && call.path.ends_with("/im/v1/chats"))
+            .then(|| json!({ "code": 232001, "msg": "chat create failed" }))
+    });
+    let harness =
+        BridgeHarness::start(|_| {}, feishu_responder, created_chat_matrix_responder()).await;
+
+    let err = harness
+        .bridge
+        .create_feishu_chat_for_room("!room:localhost", "@erin:localhost")
+        .await
+        .expect_err("chat creation should fail");
+    assert!(matches!(
+        err.downcast_ref::<ProvisioningError>(),
+        Some(ProvisioningError::Upstream(_))
+    ));
+    assert!(
+        harness
+            .bridge
+            .room_store()
+            .get_room_by_matrix_id("!room:localhost")
+            .await
+            .expect("mapping lookup should succeed")
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn created_feishu_chat_is_removed_when_its_mapping_cannot_be_saved() {
+    let feishu_responder: MockResponder = Arc::new(|call| {
+        (call.method == "POST" && call.path.ends_with("/im/v1/chats"))
+            .then(|| json!({ "code": 0, "msg": "ok", "data": { "chat_id": "oc_taken" } }))
+    });
+    let harness =
+        BridgeHarness::start(|_| {}, feishu_responder, created_chat_matrix_responder()).await;
+    harness.bridge_room("!other:localhost", "oc_taken").await;
+
+    let err = harness
+        .bridge
+        .create_feishu_chat_for_room("!room:localhost", "@erin:localhost")
+        .await
+        .expect_err("mapping should conflict");
+    assert!(matches!(
+        err.downcast_ref::<ProvisioningError>(),
+        Some(ProvisioningError::Other(message)) if message.contains("was removed again")
+    ));
+    let deleted = harness.feishu.calls_to("DELETE", "/im/v1/chats/");
+    assert_eq!(deleted.len(), 1);
+    assert!(deleted[0].path.ends_with("/im/v1/chats/oc_taken"));
+}
+
+fn disbanded_room_matrix_responder() -> MockResponder {
+    Arc::new(|call| {
+        if call.method == "GET" && call.path.contains("/state/m.room.power_levels") {
+            Some(json!({
+                "users": {
+                    "@feishubot:localhost": 100,
+                    "@feishu_u_owner:localhost": 95
+                },
+                "events": { "m.room.name": 50, "m.reaction": 0 },
+                "events_default": 0,
+                "state_default": 50
+            }))
+        } else if call.method == "GET" && call.path.contains("/state/m.room.name") {
+            Some(json!({ "name": "Team" }))
+        } else if call.path.ends_with("/joined_members") {
+            Some(json!({
+                "joined": {
+                    "@feishubot:localhost": {},
+                    "@feishu_u_owner:localhost": {},
+                    "@alice:localhost": {}
+                }
+            }))
+        } else if call.method == "POST" && call.path.ends_with("/createRoom") {
+            Some(json!({ "room_id": "!archive:localhost" }))
+        } else if call.method == "POST" && call.path.contains("/join/") {
+            Some(json!({ "room_id": call.path.rsplit('/').next() }))
+        } else {
+            None
+        }
+    })
+}
+
+async fn disband_with_policy(policy: &'static str) -> BridgeHarness {
+    let harness = BridgeHarness::start(
+        |config| config.bridge.disband_policy = policy.to_string(),
+        no_responses(),
+        disbanded_room_matrix_responder(),
+    )
+    .await;
+    harness.bridge_room("!room:localhost", "oc_chat").await;
+    harness
+        .bridge
+        .handle_feishu_chat_disbanded("oc_chat")
+        .await
+        .expect("disband should succeed");
+    harness
+}
+
+fn assert_room_locked(harness: &BridgeHarness) {
+    let power_levels = harness.matrix.calls_to("PUT", "/state/m.room.power_levels");
+    assert_eq!(power_levels.len(), 1);
+    let content = &power_levels[0].body;
+    for key in ["events_default", "state_default", "invite", "redact"] {
+        assert_eq!(
+            content[key],
+            json!(100),
+            "{key} should require the bridge bot"
+        );
+    }
+    assert_eq!(content["events"]["m.reaction"], json!(100));
+    assert_eq!(content["events"]["m.room.name"], json!(100));
+    assert_eq!(content["users"]["@feishu_u_owner:localhost"], json!(95));
+}
+
+#[tokio::test]
+async fn disband_archive_policy_locks_room_below_the_bot() {
+    let harness = disband_with_policy("archive").await;
+
+    assert_room_locked(&harness);
+    assert!(
+        harness
+            .matrix
+            .calls_to("PUT", "/state/m.room.tombstone")
+            .is_empty()
+    );
+    assert!(harness.matrix.calls_to("POST", "/leave").is_empty());
+}
+
+#[tokio::test]
+async fn disband_tombstone_policy_points_to_new_archive_room() {
+    let harness = disband_with_policy("tombstone").await;
+
+    assert_room_locked(&harness);
+    let created = harness.matrix.calls_to("POST", "/createRoom");
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0].query, "user_id=%40feishubot%3Alocalhost");
+    assert_eq!(created[0].body["name"], json!("Team (archived)"));
+    assert_eq!(created[0].body["invite"], json!(["@alice:localhost"]));
+    assert_eq!(
+        created[0].body["creation_content"]["predecessor"]["room_id"],
+        json!("!room:localhost")
+    );
+    let tombstones = harness.matrix.calls_to("PUT", "/state/m.room.tombstone");
+    assert_eq!(tombstones.len(), 1);
+    assert!(tombstones[0].path.contains("%21room%3Alocalhost"));
+    assert_eq!(
+        tombstones[0].body["replacement_room"],
+        json!("!archive:localhost")
+    );
+}
+
+#[tokio::test]
+async fn disband_kick_policy_removes_puppets_and_bot_only() {
+    let harness = disband_with_policy("kick").await;
+
+    let mut leavers = harness
+        .matrix
+        .calls_to("POST", "/leave")
+        .into_iter()
+        .map(|call| call.query)
+        .collect::<Vec<_>>();
+    leavers.sort();
+    assert_eq!(
+        leavers,
+        vec![
+            "user_id=%40feishu_u_owner%3Alocalhost".to_string(),
+            "user_id=%40feishubot%3Alocalhost".to_string(),
+        ]
+    );
+    assert!(
+        harness
+            .matrix
+            .calls_to("PUT", "/state/m.room.power_levels")
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn bridge_info_backfill_sends_state_only_to_outdated_rooms() {
+    let current = BridgePortal::new(
+        "oc_a".to_string(),
+        "!a:localhost".to_string(),
+        "Bridged Room".to_string(),
+        "@feishubot:localhost".to_string(),
+    )
+    .bridge_state_content();
+    let harness = BridgeHarness::start(
+        |_| {},
+        no_responses(),
+        Arc::new(move |call| {
+            if call.method != "GET" || !call.path.contains("/state/m.bridge/") {
+                None
+            } else if call.path.contains("%21a%3Alocalhost") {
+                Some(current.clone())
+            } else {
+                Some(json!({ "errcode": "M_NOT_FOUND", "error": "not found" }))
+            }
+        }),
+    )
+    .await;
+    harness.bridge_room("!a:localhost", "oc_a").await;
+    harness.bridge_room("!b:localhost", "oc_b").await;
+
+    let updated = harness
+        .bridge
+        .backfill_portal_bridge_info()
+        .await
+        .expect("backfill should succeed");
+
+    assert_eq!(updated, 1);
+    for event_type in ["/state/m.bridge/", "/state/uk.half-shot.bridge/"] {
+        let puts = harness.matrix.calls_to("PUT", event_type);
+        assert_eq!(puts.len(), 1, "{event_type}");
+        assert!(puts[0].path.contains("%21b%3Alocalhost"));
+        assert!(puts[0].path.ends_with("feishu%3A%2F%2Foc_b"));
+        assert_eq!(puts[0].body["channel"]["id"], json!("oc_b"));
+        assert_eq!(puts[0].body["bridgebot"], json!("@feishubot:localhost"));
+    }
+}
+
+fn chat_avatar_responder(avatar: Arc<Mutex<Option<String>>>) -> MockResponder {
+    Arc::new(move |call| {
+        if call.method == "GET" && call.path.ends_with("/im/v1/chats/oc_chat") {
+            let avatar = avatar.lock().expect("avatar mutex poisoned").clone();
+            feishu_data(json!({ "chat_id": "oc_chat", "name": "Portal", "avatar": avatar }))
+        } else {
+            None
+        }
+    })
+}
+
+/// Starts a harness whose portal was last synced from `{feishu_base}/avatar.png`.
+async fn start_with_avatar_portal(feishu_avatar: Arc<Mutex<Option<String>>>) -> BridgeHarness {
+    let harness =
+        BridgeHarness::start(|_| {}, chat_avatar_responder(feishu_avatar), no_responses()).await;
+    let synced_avatar = format!("{}/avatar.png", harness.feishu_base);
+    let mut portal = test_portal("oc_chat", "!room:localhost");
+    portal.avatar_url = Some("mxc://localhost/avatar".to_string());
+    portal.bridge_info = json!({
+        "bridgebot": "@feishubot:localhost",
+        "creator": "@feishubot:localhost",
+        "protocol": "feishu",
+        "channel": { "feishu_avatar": synced_avatar }
+    })
+    .to_string();
+    harness
+        .bridge
+        .portal_store()
+        .upsert_portal(&portal)
+        .await
+        .expect("portal should persist");
+    harness.bridge_room("!room:localhost", "oc_chat").await;
+    harness
+}
+
+#[tokio::test]
+async fn chat_update_skips_avatar_download_when_feishu_avatar_is_unchanged() {
+    let feishu_avatar = Arc::new(Mutex::new(None));
+    let harness = start_with_avatar_portal(feishu_avatar.clone()).await;
+    *feishu_avatar.lock().expect("avatar mutex poisoned") =
+        Some(format!("{}/avatar.png", harness.feishu_base));
+
+    harness
+        .bridge
+        .handle_feishu_chat_updated("oc_chat", None, None, None, None)
+        .await
+        .expect("chat update should succeed");
+
+    assert!(harness.feishu.calls_to("GET", "/avatar.png").is_empty());
+    assert!(
+        harness
+            .matrix
+            .calls_to("PUT", "/state/m.room.avatar")
+            .is_empty()
+    );
+    assert!(harness.matrix.calls_to("POST", "/media/").is_empty());
+}
+
+#[tokio::test]
+async fn chat_update_removes_matrix_avatar_when_feishu_avatar_is_cleared() {
+    let harness = start_with_avatar_portal(Arc::new(Mutex::new(None))).await;
+
+    harness
+        .bridge
+        .handle_feishu_chat_updated("oc_chat", None, None, None, None)
+        .await
+        .expect("chat update should succeed");
+
+    let avatar_puts = harness.matrix.calls_to("PUT", "/state/m.room.avatar");
+    assert_eq!(avatar_puts.len(), 1);
+    assert_eq!(avatar_puts[0].body, json!({}));
+    assert!(harness.feishu.calls_to("GET", "/avatar.png").is_empty());
+    let portal = harness
+        .bridge
+        .portal_store()
+        .get_portal_by_mxid("!room:localhost")
+        .await
+        .expect("portal lookup should succeed")
+        .expect("portal should exist");
+    assert_eq!(portal.avatar_url, None);
+    assert!(!portal.bridge_info.contains("feishu_avatar"));
+}
+
+async fn thread_harness() -> BridgeHarness {
+    let harness = BridgeHarness::start(|_| {}, no_responses(), bot_join_responder()).await;
+    harness.bridge_room("!room:localhost", "oc_chat").await;
+    for (event_id, message_id) in [("$root", "om_root"), ("$middle", "om_middle")] {
+        harness
+            .bridge
+            .message_store()
+            .create_message_mapping(&MessageMapping::new(
+                event_id.to_string(),
+                message_id.to_string(),
+                "!room:localhost".to_string(),
+                "@feishu_u_bob:localhost".to_string(),
+                "u_bob".to_string(),
+            ))
+            .await
+            .expect("message mapping should persist");
+    }
+    harness
+}
+
+async fn bridged_thread_relation(
+    harness: &BridgeHarness,
+    root_id: &str,
+    parent_id: Option<&str>,
+) -> Value {
+    let mut message = feishu_text_message("om_reply", "oc_chat", "u_bob");
+    message.thread_id = Some("omt_thread".to_string());
+    message.root_id = Some(root_id.to_string());
+    message.parent_id = parent_id.map(str::to_owned);
+    harness
+        .bridge
+        .handle_feishu_message(message)
+        .await
+        .expect("thread message should bridge");
+    let sends = harness.matrix.calls_to("PUT", "/send/m.room.message/");
+    assert_eq!(sends.len(), 1);
+    sends[0].body["m.relates_to"].clone()
+}
+
+#[tokio::test]
+async fn feishu_thread_reply_to_root_resolves_root_through_message_store() {
+    let harness = thread_harness().await;
+
+    let relation = bridged_thread_relation(&harness, "om_root", Some("om_root")).await;
+
+    assert_eq!(
+        relation,
+        json!({
+            "rel_type": "m.thread",
+            "event_id": "$root",
+            "is_falling_back": true,
+            "m.in_reply_to": { "event_id": "$root" }
+        })
+    );
+}
+
+#[tokio::test]
+async fn feishu_thread_reply_to_inner_message_is_a_real_reply() {
+    let harness = thread_harness().await;
+
+    let relation = bridged_thread_relation(&harness, "om_root", Some("om_middle")).await;
+
+    assert_eq!(
+        relation,
+        json!({
+            "rel_type": "m.thread",
+            "event_id": "$root",
+            "is_falling_back": false,
+            "m.in_reply_to": { "event_id": "$middle" }
+        })
+    );
+}
+
+#[tokio::test]
+async fn feishu_thread_message_with_unknown_root_is_sent_without_thread_relation() {
+    let harness = thread_harness().await;
+
+    let relation = bridged_thread_relation(&harness, "om_missing", None).await;
+
+    assert_eq!(relation, Value::Null);
+}
+
+#[tokio::test]
+async fn feishu_message_update_sends_matrix_replacement_as_original_sender() {
+    let harness = BridgeHarness::start(|_| {}, no_responses(), no_responses()).await;
+    harness.bridge_room("!room:localhost", "oc_chat").await;
+    harness
+        .bridge
+        .message_store()
+        .create_message_mapping(&MessageMapping::new(
+            "$original".to_string(),
+            "om_edited".to_string(),
+            "!room:localhost".to_string(),
+            "@feishu_u_bob:localhost".to_string(),
+            "u_bob".to_string(),
+        ))
+        .await
+        .expect("message mapping should persist");
+    let mut message = feishu_text_message("om_edited", "oc_chat", "u_bob");
+    message.content = "hello again".to_string();
+
+    harness
+        .bridge
+        .handle_feishu_message_updated(message)
+        .await
+        .expect("message update should succeed");
+
+    let sends = harness.matrix.calls_to("PUT", "/send/m.room.message/");
+    assert_eq!(sends.len(), 1);
+    assert!(sends[0].path.contains("%21room%3Alocalhost"));
+    assert_eq!(sends[0].query, "user_id=%40feishu_u_bob%3Alocalhost");
+    let content = &sends[0].body;
+    assert_eq!(content["body"], json!("* hello again"));
+    assert_eq!(content["m.new_content"]["body"], json!("hello again"));
+    assert_eq!(
+        content["m.relates_to"],
+        json!({ "rel_type": "m.replace", "event_id": "$original" })
+    );
+}
+
+#[tokio::test]
+async fn feishu_message_update_skips_unknown_and_matrix_origin_messages() {
+    let harness = BridgeHarness::start(|_| {}, no_responses(), no_responses()).await;
+    harness.bridge_room("!room:localhost", "oc_chat").await;
+    harness
+        .bridge
+        .message_store()
+        .create_message_mapping(&MessageMapping::new(
+            "$from_matrix".to_string(),
+            "om_from_matrix".to_string(),
+            "!room:localhost".to_string(),
+            "@alice:localhost".to_string(),
+            "matrix".to_string(),
+        ))
+        .await
+        .expect("message mapping should persist");
+
+    for message_id in ["om_from_matrix", "om_unknown"] {
+        harness
+            .bridge
+            .handle_feishu_message_updated(feishu_text_message(message_id, "oc_chat", "u_bob"))
+            .await
+            .expect("message update should succeed");
+    }
+
+    assert!(harness.matrix.calls_to("PUT", "/send/").is_empty());
+}
+
+#[tokio::test]
+async fn feishu_member_added_joins_puppet_and_announces_member() {
+    let feishu_responder: MockResponder = Arc::new(|call| {
+        if call.method == "GET" && call.path.ends_with("/contact/v3/users/u_bob") {
+            feishu_data(feishu_user("u_bob", "Bob"))
+        } else {
+            None
+        }
+    });
+    let harness = BridgeHarness::start(|_| {}, feishu_responder, bot_join_responder()).await;
+    harness.bridge_room("!room:localhost", "oc_chat").await;
+
+    harness
+        .bridge
+        .handle_feishu_chat_member_added("oc_chat", &["u_bob".to_string()])
+        .await
+        .expect("member added should succeed");
+
+    let joins = harness.matrix.calls_to("POST", "/join");
+    assert!(
+        joins
+            .iter()
+            .any(|call| call.query.contains("user_id=%40feishu_u_bob%3Alocalhost")),
+        "puppet should join the portal: {joins:?}"
+    );
+    let notices = harness.matrix.calls_to("PUT", "/send/");
+    assert!(
+        notices
+            .iter()
+            .any(|call| call.body["body"] == json!("Feishu members joined: Bob(u_bob)"))
+    );
+}
+
+#[tokio::test]
+async fn feishu_member_deleted_removes_puppet_and_announces_member() {
+    let feishu_responder: MockResponder = Arc::new(|call| {
+        if call.method == "GET" && call.path.ends_with("/contact/v3/users/u_bob") {
+            feishu_data(feishu_user("u_bob", "Bob"))
+        } else {
+            None
+        }
+    });
+    let harness = BridgeHarness::start(|_| {}, feishu_responder, bot_join_responder()).await;
+    harness.bridge_room("!room:localhost", "oc_chat").await;
+    harness
+        .bridge
+        .user_store()
+        .create_user_mapping(&UserMapping::new(
+            "@feishu_u_bob:localhost".to_string(),
+            "u_bob".to_string(),
+            Some("Bob".to_string()),
+        ))
+        .await
+        .expect("user mapping should persist");
+
+    harness
+        .bridge
+        .handle_feishu_chat_member_deleted("oc_chat", &["u_bob".to_string()])
+        .await
+        .expect("member deleted should succeed");
+
+    let leaves = harness.matrix.calls_to("POST", "/leave");
+    assert_eq!(leaves.len(), 1);
+    assert!(leaves[0].path.contains("%21room%3Alocalhost"));
+    assert_eq!(leaves[0].query, "user_id=%40feishu_u_bob%3Alocalhost");
+    let notices = harness.matrix.calls_to("PUT", "/send/");
+    assert!(
+        notices
+            .iter()
+            .any(|call| call.body["body"] == json!("Feishu members left: Bob(u_bob)"))
+    );
+}
+
+#[tokio::test]
+async fn member_sync_pages_feishu_members_and_removes_stale_puppets() {
+    let feishu_responder: MockResponder = Arc::new(|call| {
+        if call.method != "GET" {
+            return None;
+        }
+        if call.path.ends_with("/chats/oc_chat/members") {
+            if call.query.contains("page_token=next") {
+                feishu_data(json!({ "items": [{ "member_id": "u_bob" }], "has_more": false }))
+            } else {
+                feishu_data(json!({
+                    "items": [{ "member_id": "u_alice" }],
+                    "has_more": true,
+                    "page_token": "next"
+                }))
+            }
+        } else if call.path.ends_with("/contact/v3/users/u_alice") {
+            feishu_data(feishu_user("u_alice", "Alice"))
+        } else if call.path.ends_with("/contact/v3/users/u_bob") {
+            feishu_data(feishu_user("u_bob", "Bob"))
+        } else {
+            None
+        }
+    });
+    let matrix_responder: MockResponder = Arc::new(|call| {
+        if call.path.ends_with("/joined_members") {
+            Some(json!({
+                "joined": {
+                    "@feishubot:localhost": {},
+                    "@feishu_u_alice:localhost": {},
+                    "@feishu_u_gone:localhost": {},
+                    "@carol:localhost": {}
+                }
+            }))
+        } else if call.method == "POST" && call.path.contains("/join/") {
+            Some(json!({ "room_id": "!room:localhost" }))
+        } else {
+            None
+        }
+    });
+    let harness = BridgeHarness::start(|_| {}, feishu_responder, matrix_responder).await;
+    harness.bridge_room("!room:localhost", "oc_chat").await;
+
+    let reply = harness
+        .bridge
+        .sync_room_members("!room:localhost")
+        .await
+        .expect("member sync should succeed");
+
+    assert_eq!(reply, "Synced 2 Feishu members into this room.");
+    let pages = harness
+        .feishu
+        .calls_to("GET", "/open-apis/im/v1/chats/oc_chat/members");
+    assert_eq!(pages.len(), 2);
+    let joins = harness.matrix.calls_to("POST", "/join");
+    for puppet in [
+        "%40feishu_u_alice%3Alocalhost",
+        "%40feishu_u_bob%3Alocalhost",
+    ] {
+        assert!(
+            joins.iter().any(|call| call.query.contains(puppet)),
+            "{puppet} should join the portal: {joins:?}"
+        );
+    }
+    let leaves = harness.matrix.calls_to("POST", "/leave");
+    assert_eq!(
+        leaves.len(),
+        1,
+        "only the stale puppet should leave: {leaves:?}"
+    );
+    assert_eq!(leaves[0].query, "user_id=%40feishu_u_gone%3Alocalhost");
+}
+
+#[tokio::test]
+async fn feishu_roles_sync_to_matrix_power_levels_without_touching_manual_grants() {
+    let feishu_responder: MockResponder = Arc::new(|call| {
+        if call.method != "GET" {
+            return None;
+        }
+        if call.path.ends_with("/im/v1/chats/oc_chat") {
+            return feishu_data(json!({
+                "chat_id": "oc_chat",
+                "owner_id": "u_owner",
+                "user_manager_id_list": ["u_mgr"]
+            }));
+        }
+        let user_id = call.path.rsplit_once("/contact/v3/users/")?.1;
+        feishu_data(feishu_user(user_id, user_id))
+    });
+    let matrix_responder: MockResponder = Arc::new(|call| {
+        (call.method == "GET" && call.path.contains("/state/m.room.power_levels")).then(|| {
+            json!({
+                "users": {
+                    "@feishubot:localhost": 100,
+                    "@carol:localhost": 100,
+                    "@feishu_u_dave:localhost": 50,
+                    "@feishu_u_erin:localhost": 20,
+                    "@alice:localhost": 50
+                },
+                "events_default": 0
+            })
+        })
+    });
+    let harness = BridgeHarness::start(|_| {}, feishu_responder, matrix_responder).await;
+    let user_store = harness.bridge.user_store();
+    for feishu_user_id in ["u_dave", "u_erin"] {
+        user_store
+            .create_user_mapping(&UserMapping::new(
+                format!("@feishu_{feishu_user_id}:localhost"),
+                feishu_user_id.to_string(),
+                None,
+            ))
+            .await
+            .expect("user mapping should persist");
+    }
+    user_store
+        .set_linked_feishu_user("@carol:localhost", Some("u_owner"))
+        .await
+        .expect("link should persist");
+
+    harness
+        .bridge
+        .sync_portal_power_levels("oc_chat", "!room:localhost")
+        .await
+        .expect("power level sync should succeed");
+
+    let puts = harness.matrix.calls_to("PUT", "/state/m.room.power_levels");
+    assert_eq!(puts.len(), 1);
+    assert_eq!(
+        puts[0].body["users"],
+        json!({
+            "@feishubot:localhost": 100,
+            "@carol:localhost": 100,
+            "@feishu_u_owner:localhost": 95,
+            "@feishu_u_mgr:localhost": 50,
+            "@feishu_u_erin:localhost": 20,
+            "@alice:localhost": 50
+        })
+    );
+    assert_eq!(puts[0].body["events_default"], json!(0));
+}